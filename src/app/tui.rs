@@ -145,6 +145,7 @@ pub async fn run_tui() -> io::Result<()> {
                     TuiStep::Welcome => {
                         state.step = TuiStep::ChooseDbType;
                     }
+                    #[allow(clippy::collapsible_match)]
                     TuiStep::ChooseDbType => match key.code {
                         KeyCode::Up => {
                             if state.db_type_index > 0 {
@@ -2,6 +2,7 @@ use crate::db::accessors::*;
 use crate::export::exporter::MetadataExporter;
 use crate::datamimic::datamimic::DataMimicModelGenerator;
 use crate::db::models::*;
+use crate::history::store::{HistoryStore, default_history_path, profile_from_connection};
 use anyhow::{Result, anyhow};
 use chrono::Utc;
 
@@ -11,21 +12,18 @@ pub async fn tui_export_flow(state: &super::tui::TuiState) -> Result<String> {
     let db_type = state.db_types[state.db_type_index];
     let connection_string = &state.connection_string;
     let schema = if !state.schema.is_empty() { Some(state.schema.as_str()) } else { None };
-    let output_file = "output.json"; // TODO: let user customize
+    let format = "json"; // TODO: let user customize
+    let exporter = MetadataExporter::default();
+    let output_file = exporter.registry().require(format)?.default_output_path();
     let datamimic_output = "output_datamimic.json";
-    let format = "json";
 
     // 1. Create DB accessor (delegated to db::accessors)
-    let mut accessor: Box<dyn DatabaseAccessor + Send> = match db_type {
-        "sqlite" => Box::new(SqliteAccessor::new(connection_string).await.map_err(|e| anyhow!("SQLite connection failed: {}", e))?),
-        "postgres" => Box::new(PostgresAccessor::new(connection_string).await.map_err(|e| anyhow!("Postgres connection failed: {}", e))?),
-        "mysql" => Box::new(MySqlAccessor::new(connection_string).await.map_err(|e| anyhow!("MySQL connection failed: {}", e))?),
-        _ => return Err(anyhow!("Unsupported DB type: {}", db_type)),
-    };
+    let mut accessor = connect(db_type, connection_string).await.map_err(|e| anyhow!("{} connection failed: {}", db_type, e))?;
 
     // 2. Extract metadata (delegated to db::accessors)
     let extracted_metadata = accessor.extract_full_metadata(schema).await.map_err(|e| anyhow!("Metadata extraction failed: {}", e))?;
-    let final_schema = DbMetaDataSchema {
+    let mut final_schema = DbMetaDataSchema {
+        format_version: Some(FORMAT_VERSION),
         id: None,
         system_environment_id: 0,
         tc_creation_src: None,
@@ -34,16 +32,24 @@ pub async fn tui_export_flow(state: &super::tui::TuiState) -> Result<String> {
         tc_update: Some(Utc::now()),
         db_metadata: extracted_metadata,
         user_config_db_metadata: None,
+        source_database_type: Some(db_type.to_string()),
     };
 
-    // 3. Export metadata (delegated to export::exporter)
-    let exporter = MetadataExporter;
-    exporter.export_schema_to_file(&final_schema, output_file, format).map_err(|e| anyhow!("Export to file failed: {}", e))?;
+    // 3. Record the snapshot in history; it is only kept once the export succeeded (delegated to history::store)
+    let history_path = default_history_path();
+    let store = HistoryStore::open(&history_path).await.map_err(|e| anyhow!("Opening history failed: {}", e))?;
+    let pending = store.save(&profile_from_connection(connection_string), &final_schema).await
+        .map_err(|e| anyhow!("Saving snapshot to history failed: {}", e))?;
+    pending.stamp(&mut final_schema);
+
+    // 4. Export metadata (delegated to export::exporter)
+    exporter.export_schema_to_file(&final_schema, &output_file, format).map_err(|e| anyhow!("Export to file failed: {}", e))?;
 
-    // 4. Generate DataMimic model (delegated to datamimic::datamimic)
+    // 5. Generate DataMimic model (delegated to datamimic::datamimic)
     let generator = DataMimicModelGenerator;
     let datamimic_model = generator.generate_from_metadata(&final_schema.db_metadata, db_type).map_err(|e| anyhow!("DataMimic model generation failed: {}", e))?;
-    generator.export_model_to_file(&datamimic_model, datamimic_output).map_err(|e| anyhow!("Export DataMimic model failed: {}", e))?;
+    generator.export_model_to_file(&datamimic_model, datamimic_output, format).map_err(|e| anyhow!("Export DataMimic model failed: {}", e))?;
 
-    Ok("Export completed!".to_string())
+    let record = pending.commit().await.map_err(|e| anyhow!("Saving snapshot to history failed: {}", e))?;
+    Ok(format!("Export completed! Saved as snapshot {} in {}", record.id, history_path))
 }
@@ -0,0 +1,119 @@
+// commands/check.rs
+// `check` command: detect drift between a live database and a baseline snapshot
+
+use crate::db::accessors::connect;
+use crate::db::models::{DbMetaDataSchema, FORMAT_VERSION};
+use crate::diff::breaking::{Allowlist, classify};
+use crate::diff::report::ReportFormat;
+use crate::diff::schema_diff::diff_metadata;
+use crate::export::loader::load_schema_from_file;
+use crate::export::writer::write_output;
+use anyhow::{Result, bail};
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Baseline snapshot (JSON or YAML) the database is expected to match
+    #[arg(long)]
+    pub baseline: String,
+    #[arg(long)]
+    pub db_type: String,
+    #[arg(long)]
+    pub connection_string: String,
+    #[arg(long)]
+    pub schema_or_database: Option<String>,
+    /// Report format: text, markdown or json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+    /// Output file for the drift report (prints to stdout when omitted)
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+/// Objects only in the database show up as added, objects only in the baseline as removed.
+pub async fn run(args: &CheckArgs) -> Result<()> {
+    let format = ReportFormat::parse(&args.format)?;
+    let baseline = load_schema_from_file(&args.baseline)?;
+
+    let mut accessor = connect(&args.db_type, &args.connection_string).await?;
+    let live = DbMetaDataSchema {
+        format_version: Some(FORMAT_VERSION),
+        db_metadata: accessor.extract_full_metadata(args.schema_or_database.as_deref()).await?,
+        source_database_type: Some(args.db_type.to_lowercase()),
+        ..Default::default()
+    };
+
+    let diff = diff_metadata(&baseline.db_metadata, &live.db_metadata);
+    let changes = classify(&diff, &baseline, &live, &Allowlist::default());
+    write_output(&format.render(&diff, &changes)?, args.output.as_deref())?;
+
+    if !diff.is_empty() {
+        bail!("Database has drifted from baseline {}", args.baseline);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    async fn execute(database: &str, statement: &str) {
+        let pool = sqlx::SqlitePool::connect(database).await.unwrap();
+        sqlx::raw_sql(statement).execute(&pool).await.unwrap();
+        pool.close().await;
+    }
+
+    fn check_args(dir: &Path, database: &str) -> CheckArgs {
+        CheckArgs {
+            baseline: dir.join("baseline.json").to_str().unwrap().to_string(),
+            db_type: "sqlite".to_string(),
+            connection_string: database.to_string(),
+            schema_or_database: None,
+            format: "text".to_string(),
+            output: Some(dir.join("drift.txt").to_str().unwrap().to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn drift_is_reported_and_fails_the_check() {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-check-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let database = format!("sqlite://{}?mode=rwc", dir.join("shop.db").display());
+        execute(&database, "CREATE TABLE customers (id INTEGER PRIMARY KEY, email TEXT NOT NULL);
+            CREATE TABLE orders (id INTEGER PRIMARY KEY, customer_id INTEGER REFERENCES customers (id));").await;
+
+        let args = check_args(&dir, &database);
+        let mut accessor = connect("sqlite", &database).await.unwrap();
+        let baseline = DbMetaDataSchema {
+            db_metadata: accessor.extract_full_metadata(None).await.unwrap(),
+            source_database_type: Some("sqlite".to_string()),
+            ..Default::default()
+        };
+        drop(accessor);
+        std::fs::write(&args.baseline, serde_json::to_string(&baseline).unwrap()).unwrap();
+
+        run(&args).await.unwrap();
+        assert_eq!(std::fs::read_to_string(args.output.as_ref().unwrap()).unwrap(), "No differences\n");
+
+        execute(&database, "ALTER TABLE customers ADD COLUMN name TEXT; DROP TABLE orders;
+            CREATE TABLE invoices (id INTEGER PRIMARY KEY);").await;
+        let error = run(&args).await.unwrap_err();
+        assert_eq!(error.to_string(), format!("Database has drifted from baseline {}", args.baseline));
+        assert_eq!(
+            std::fs::read_to_string(args.output.as_ref().unwrap()).unwrap(),
+            r#"1 table(s) added, 1 removed, 1 changed
+
++ table invoices
+- table orders
+~ table customers
+    + column name
+
+Breaking changes (1):
+  ! drop_table:orders: table dropped
+"#
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+// commands/ddl.rs
+// `ddl` command: recreate a schema from a metadata snapshot without the source server
+
+use crate::ddl::dialect::Dialect;
+use crate::ddl::generator::DdlGenerator;
+use crate::ddl::translate::translate;
+use crate::export::loader::load_schema_from_file;
+use crate::export::writer::write_output;
+use anyhow::Result;
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct DdlArgs {
+    /// Metadata snapshot (JSON or YAML) to generate DDL from
+    #[arg(long)]
+    pub input: String,
+    /// Target dialect: postgres, mysql or sqlite
+    #[arg(long)]
+    pub dialect: String,
+    /// Dialect the snapshot was extracted from (defaults to the one recorded in the snapshot)
+    #[arg(long)]
+    pub source_dialect: Option<String>,
+    /// Output file (prints to stdout when omitted)
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+pub fn run(args: &DdlArgs) -> Result<()> {
+    let schema = load_schema_from_file(&args.input)?;
+    let target = Dialect::parse(&args.dialect)?;
+    let source = match args.source_dialect.as_deref().or(schema.source_database_type.as_deref()) {
+        Some(source) => Dialect::parse(source)?,
+        None => {
+            eprintln!("Snapshot does not record its source dialect, assuming {} (use --source-dialect)", target.name());
+            target
+        }
+    };
+
+    let translation = translate(&schema.db_metadata, source, target);
+    let mut warnings = translation.warnings;
+    let generated = DdlGenerator { dialect: target }.generate(&translation.metadata, &mut warnings);
+    let mut sql = String::new();
+    for warning in &warnings {
+        eprintln!("Warning: {}", warning);
+        sql.push_str(&format!("-- WARNING: {}\n", warning));
+    }
+    sql.push_str(&generated);
+    write_output(&sql, args.output.as_deref())
+}
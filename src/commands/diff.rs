@@ -0,0 +1,51 @@
+// commands/diff.rs
+// `diff` command: compare two metadata snapshots
+
+use crate::diff::breaking::{Allowlist, blocking_changes, classify};
+use crate::diff::report::ReportFormat;
+use crate::diff::schema_diff::diff_metadata;
+use crate::export::loader::load_schema_from_file;
+use crate::export::writer::write_output;
+use anyhow::{Result, bail};
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Baseline snapshot (JSON or YAML)
+    pub old: String,
+    /// Snapshot to compare against the baseline (JSON or YAML)
+    pub new: String,
+    /// Report format: text, markdown or json
+    #[arg(long, default_value = "text")]
+    pub format: String,
+    /// Output file (prints to stdout when omitted)
+    #[arg(long)]
+    pub output: Option<String>,
+    /// Report breaking changes without failing. By default the command exits with an error when
+    /// it finds breaking changes that the allowlist does not accept
+    #[arg(long)]
+    pub no_fail_on_breaking: bool,
+    /// File with accepted breaking change ids (`kind:object`, `*` wildcards), one per line
+    #[arg(long)]
+    pub allowlist: Option<String>,
+}
+
+pub fn run(args: &DiffArgs) -> Result<()> {
+    let format = ReportFormat::parse(&args.format)?;
+    let old = load_schema_from_file(&args.old)?;
+    let new = load_schema_from_file(&args.new)?;
+    let allowlist = match &args.allowlist {
+        Some(path) => Allowlist::load(path)?,
+        None => Allowlist::default(),
+    };
+
+    let diff = diff_metadata(&old.db_metadata, &new.db_metadata);
+    let changes = classify(&diff, &old, &new, &allowlist);
+    write_output(&format.render(&diff, &changes)?, args.output.as_deref())?;
+
+    let blocking = blocking_changes(&changes);
+    if !args.no_fail_on_breaking && !blocking.is_empty() {
+        bail!("{} breaking change(s) not covered by the allowlist", blocking.len());
+    }
+    Ok(())
+}
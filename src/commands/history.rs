@@ -0,0 +1,94 @@
+// commands/history.rs
+// `history` command: browse and compare snapshots saved by previous exports
+
+use crate::diff::breaking::{Allowlist, classify};
+use crate::diff::report::ReportFormat;
+use crate::diff::schema_diff::diff_metadata;
+use crate::export::writer::{SerializationFormat, write_output};
+use crate::history::store::{HistoryStore, default_history_path};
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+#[derive(Args, Debug)]
+pub struct HistoryArgs {
+    #[command(subcommand)]
+    pub command: HistoryCommand,
+    /// History catalog (defaults to $DBMETAEXPORTER_HISTORY or ~/.dbmetaexporter/history.db)
+    #[arg(long, global = true)]
+    pub history_db: Option<String>,
+    /// Only consider snapshots of this profile (the connection string without credentials by default)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+}
+
+/// Snapshots are referenced by id or by date (`YYYY-MM-DD` or an RFC 3339 timestamp), which
+/// selects the latest snapshot taken at or before that time.
+#[derive(Subcommand, Debug)]
+pub enum HistoryCommand {
+    /// List saved snapshots, oldest first
+    List,
+    /// Print a saved snapshot
+    Show {
+        snapshot: String,
+        /// Output format: json or yaml
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Output file (prints to stdout when omitted)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Compare two saved snapshots
+    Diff {
+        old: String,
+        new: String,
+        /// Report format: text, markdown or json
+        #[arg(long, default_value = "text")]
+        format: String,
+        /// Output file (prints to stdout when omitted)
+        #[arg(long)]
+        output: Option<String>,
+    },
+}
+
+pub async fn run(args: &HistoryArgs) -> Result<()> {
+    let path = args.history_db.clone().unwrap_or_else(default_history_path);
+    let store = HistoryStore::open(&path).await?;
+    let profile = args.profile.as_deref();
+
+    match &args.command {
+        HistoryCommand::List => {
+            let snapshots = store.list(profile).await?;
+            if snapshots.is_empty() {
+                println!("No snapshots in {}", path);
+            }
+            for snapshot in snapshots {
+                println!(
+                    "{:>5}  {}  {}  {:<8}  {}  {}",
+                    snapshot.id,
+                    snapshot.created_at.format("%Y-%m-%d %H:%M:%S"),
+                    snapshot.updated_at.format("%Y-%m-%d %H:%M:%S"),
+                    snapshot.source_database_type.as_deref().unwrap_or("-"),
+                    &snapshot.hash[..12],
+                    snapshot.profile
+                );
+            }
+            Ok(())
+        }
+        HistoryCommand::Show { snapshot, format, output } => {
+            let format = SerializationFormat::parse(format)?;
+            let record = store.resolve(snapshot, profile).await?;
+            let schema = store.load(&record).await?;
+            let mut buffer = Vec::new();
+            format.write(&schema, &mut buffer)?;
+            write_output(&String::from_utf8(buffer)?, output.as_deref())
+        }
+        HistoryCommand::Diff { old, new, format, output } => {
+            let format = ReportFormat::parse(format)?;
+            let old = store.load(&store.resolve(old, profile).await?).await?;
+            let new = store.load(&store.resolve(new, profile).await?).await?;
+            let diff = diff_metadata(&old.db_metadata, &new.db_metadata);
+            let changes = classify(&diff, &old, &new, &Allowlist::default());
+            write_output(&format.render(&diff, &changes)?, output.as_deref())
+        }
+    }
+}
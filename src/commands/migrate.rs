@@ -0,0 +1,80 @@
+// commands/migrate.rs
+// `migrate` command: migration scripts between two metadata snapshots
+
+use crate::db::models::{DatabaseMetadata, DbMetaDataSchema};
+use crate::ddl::dialect::Dialect;
+use crate::ddl::migration::MigrationGenerator;
+use crate::ddl::translate::translate;
+use crate::export::loader::load_schema_from_file;
+use crate::export::writer::{write_output, write_string};
+use anyhow::Result;
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct MigrateArgs {
+    /// Snapshot of the current schema (JSON or YAML)
+    pub old: String,
+    /// Snapshot of the desired schema (JSON or YAML)
+    pub new: String,
+    /// Target dialect: postgres, mysql or sqlite
+    #[arg(long)]
+    pub dialect: String,
+    /// Dialect the snapshots were extracted from (defaults to the one recorded in each snapshot)
+    #[arg(long)]
+    pub source_dialect: Option<String>,
+    /// Output file for the forward migration (prints to stdout when omitted)
+    #[arg(long)]
+    pub output: Option<String>,
+    /// Also write the backward migration, restoring the old schema, to this file
+    #[arg(long)]
+    pub backward_output: Option<String>,
+}
+
+pub fn run(args: &MigrateArgs) -> Result<()> {
+    let target = Dialect::parse(&args.dialect)?;
+    let old = load_schema_from_file(&args.old)?;
+    let new = load_schema_from_file(&args.new)?;
+
+    let mut warnings = Vec::new();
+    let old_metadata = translated(&old, args, target, &mut warnings)?;
+    let new_metadata = translated(&new, args, target, &mut warnings)?;
+    let generator = MigrationGenerator { dialect: target };
+
+    let forward = generator.generate(&old_metadata, &new_metadata);
+    warnings.extend(forward.warnings.iter().map(|w| format!("forward: {}", w)));
+    let backward = args.backward_output.as_ref().map(|path| (path, generator.generate(&new_metadata, &old_metadata)));
+    if let Some((_, migration)) = &backward {
+        warnings.extend(migration.warnings.iter().map(|w| format!("backward: {}", w)));
+    }
+
+    // Both snapshots usually share most columns, so translation warnings repeat
+    let mut seen = Vec::new();
+    for warning in warnings {
+        if !seen.contains(&warning) {
+            eprintln!("Warning: {}", warning);
+            seen.push(warning);
+        }
+    }
+
+    write_output(&with_warnings(&forward.sql, &forward.warnings), args.output.as_deref())?;
+    if let Some((path, migration)) = backward {
+        write_string(&with_warnings(&migration.sql, &migration.warnings), path)?;
+    }
+    Ok(())
+}
+
+fn translated(schema: &DbMetaDataSchema, args: &MigrateArgs, target: Dialect, warnings: &mut Vec<String>) -> Result<DatabaseMetadata> {
+    let source = match args.source_dialect.as_deref().or(schema.source_database_type.as_deref()) {
+        Some(source) => Dialect::parse(source)?,
+        None => target,
+    };
+    let translation = translate(&schema.db_metadata, source, target);
+    warnings.extend(translation.warnings);
+    Ok(translation.metadata)
+}
+
+fn with_warnings(sql: &str, warnings: &[String]) -> String {
+    let mut script: String = warnings.iter().map(|w| format!("-- WARNING: {}\n", w)).collect();
+    script.push_str(sql);
+    script
+}
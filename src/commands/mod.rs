@@ -0,0 +1,6 @@
+pub mod check;
+pub mod ddl;
+pub mod diff;
+pub mod history;
+pub mod migrate;
+pub mod validate;
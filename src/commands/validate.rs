@@ -0,0 +1,65 @@
+// commands/validate.rs
+// `validate` and `schema` commands: check files against the published JSON Schemas
+
+use crate::db::format_schema::{FileKind, validate};
+use crate::export::loader::read_text;
+use crate::export::split::{is_split, load_split};
+use crate::export::writer::{Compression, write_output};
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use serde_json::Value;
+
+#[derive(Args, Debug)]
+pub struct ValidateArgs {
+    /// Metadata snapshot or DATAMIMIC model (JSON or YAML), or a directory written with --split
+    pub file: String,
+    /// metadata or datamimic (detected from the file when omitted)
+    #[arg(long)]
+    pub kind: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct SchemaArgs {
+    /// metadata or datamimic
+    #[arg(long, default_value = "metadata")]
+    pub kind: String,
+    /// Output file (prints to stdout when omitted)
+    #[arg(long)]
+    pub output: Option<String>,
+}
+
+pub fn run_validate(args: &ValidateArgs) -> Result<()> {
+    let value: Value = if is_split(&args.file) {
+        load_split(&args.file)?
+    } else {
+        let contents = read_text(&args.file)?;
+        let path = Compression::strip(&args.file);
+        if path.ends_with(".yaml") || path.ends_with(".yml") {
+            serde_yaml::from_str(&contents).with_context(|| format!("{} is not valid YAML", &args.file))?
+        } else {
+            serde_json::from_str(&contents).with_context(|| format!("{} is not valid JSON", &args.file))?
+        }
+    };
+    let kind = match &args.kind {
+        Some(kind) => FileKind::parse(kind)?,
+        None => FileKind::detect(&value)?,
+    };
+
+    let problems = validate(kind, &value)?;
+    if value.get("format_version").is_none() {
+        eprintln!("Warning: {} has no format_version, it was written before the format was versioned", &args.file);
+    }
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("{}", problem);
+        }
+        bail!("{} does not match {} ({} problem(s))", &args.file, kind.schema_file_name(), problems.len());
+    }
+    println!("{} is valid against {}", &args.file, kind.schema_file_name());
+    Ok(())
+}
+
+pub fn run_schema(args: &SchemaArgs) -> Result<()> {
+    let schema = FileKind::parse(&args.kind)?.schema();
+    write_output(&(serde_json::to_string_pretty(&schema)? + "\n"), args.output.as_deref())
+}
@@ -1,10 +1,35 @@
 // datamimic/datamimic.rs
 // DataMimic model generator and related logic
 
-use crate::db::models::{DatabaseMetadata, DataMimicModel, DataMimicTableConfig, DataMimicColumnConfig};
-use anyhow::Result;
-use std::fs::File;
-use std::io::Write;
+use crate::db::models::{DatabaseMetadata, DataMimicModel, DataMimicTableConfig, DataMimicColumnConfig, FORMAT_VERSION};
+use crate::export::writer::{SerializationFormat, write_serialized, write_string};
+use crate::export::xml::escape_xml;
+use anyhow::{Result, anyhow};
+
+/// Output formats for the DATAMIMIC model: the serialized model itself, or an XML descriptor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataMimicFormat {
+    Serialized(SerializationFormat),
+    Xml,
+}
+
+impl DataMimicFormat {
+    pub fn parse(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "xml" => Ok(Self::Xml),
+            other => SerializationFormat::parse(other)
+                .map(Self::Serialized)
+                .map_err(|_| anyhow!("Unsupported DATAMIMIC format: '{}'. Supported formats: json, yaml, xml", format)),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Serialized(format) => format.extension(),
+            Self::Xml => "xml",
+        }
+    }
+}
 
 pub struct DataMimicModelGenerator;
 
@@ -32,17 +57,66 @@ impl DataMimicModelGenerator {
             }
         }).collect();
         Ok(DataMimicModel {
+            format_version: Some(FORMAT_VERSION),
             version: env!("CARGO_PKG_VERSION").to_string(),
             source_database_type: db_type.to_string(),
             tables,
         })
     }
-    pub fn export_model_to_file(&self, model: &DataMimicModel, output_file: &str) -> Result<()> {
-        let serialized = serde_json::to_string_pretty(model)?;
-        let mut file = File::create(output_file)?;
-        file.write_all(serialized.as_bytes())?;
-        Ok(())
+    pub fn export_model_to_file(&self, model: &DataMimicModel, output_file: &str, format: &str) -> Result<()> {
+        match DataMimicFormat::parse(format)? {
+            DataMimicFormat::Serialized(format) => write_serialized(model, output_file, format),
+            DataMimicFormat::Xml => write_string(&render_xml_descriptor(model), output_file),
+        }
+    }
+}
+
+/// Row count written on every `<generate>` block. A placeholder: the snapshot knows nothing about
+/// how much data to generate, so descriptors are meant to be edited before they are run.
+const PLACEHOLDER_COUNT: u32 = 10;
+/// Target written on every `<generate>` block; prints the rows until a real exporter is set.
+const PLACEHOLDER_TARGET: &str = "ConsoleExporter";
+
+/// Renders the model as a DATAMIMIC `<setup>` descriptor with one `<generate>` block per table.
+/// Counts and targets are placeholders (see `PLACEHOLDER_COUNT`), flagged by a comment in the output.
+fn render_xml_descriptor(model: &DataMimicModel) -> String {
+    let mut tables: Vec<&DataMimicTableConfig> = model.tables.iter().collect();
+    tables.sort_by(|a, b| (&a.schema, &a.name).cmp(&(&b.schema, &b.name)));
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<!-- Generated by DBMetaExporter {} from {} -->\n\
+         <!-- count=\"{}\" and target=\"{}\" are placeholders: set them before running -->\n<setup>\n",
+        escape_xml(&model.version),
+        escape_xml(&model.source_database_type),
+        PLACEHOLDER_COUNT,
+        PLACEHOLDER_TARGET
+    ));
+    for table in tables {
+        xml.push_str(&format!(
+            "    <generate name=\"{}\" count=\"{}\" target=\"{}\">\n",
+            escape_xml(&format!("{}.{}", table.schema, table.name)),
+            PLACEHOLDER_COUNT,
+            PLACEHOLDER_TARGET
+        ));
+        let single_key = table.columns.iter().filter(|c| c.is_primary_key).count() == 1;
+        for col in &table.columns {
+            let generator = if single_key && col.is_primary_key && matches!(col.generator_type.as_str(), "int" | "bigint") {
+                " generator=\"IncrementGenerator\""
+            } else {
+                ""
+            };
+            xml.push_str(&format!(
+                "        <key name=\"{}\" type=\"{}\"{}/>\n",
+                escape_xml(&col.name),
+                escape_xml(&col.generator_type),
+                generator
+            ));
+        }
+        xml.push_str("    </generate>\n");
     }
+    xml.push_str("</setup>\n");
+    xml
 }
 
 fn map_db_type_to_datamimic(data_type: &str, db_type: &str) -> String {
@@ -77,3 +151,31 @@ fn map_db_type_to_datamimic(data_type: &str, db_type: &str) -> String {
         _ => "string".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generator_types_follow_the_recorded_type_name() {
+        for (data_type, db_type, expected) in [
+            ("integer", "postgres", "int"),
+            ("int8", "postgresql", "bigint"),
+            ("character varying", "postgres", "string"),
+            ("timestamp without time zone", "postgres", "datetime"),
+            ("timestamp with time zone", "postgres", "string"),
+            ("numeric", "postgres", "string"),
+            ("real", "postgres", "string"),
+            ("smallint", "postgres", "string"),
+            ("tinyint", "mysql", "bool"),
+            ("bigint", "MySQL", "bigint"),
+            ("datetime", "mysql", "datetime"),
+            ("decimal", "mysql", "string"),
+            ("real", "sqlite", "float"),
+            ("blob", "sqlite", "binary"),
+            ("integer", "oracle", "string"),
+        ] {
+            assert_eq!(map_db_type_to_datamimic(data_type, db_type), expected, "{} on {}", data_type, db_type);
+        }
+    }
+}
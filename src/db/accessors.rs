@@ -1,8 +1,9 @@
 // db/accessors.rs
 // Database accessor implementations for different database systems.
 
+use super::ddl_accessor::sqlite_check_constraints;
 use super::models::*;
-use anyhow::{Result, Context};
+use anyhow::{Result, Context, anyhow};
 use async_trait::async_trait;
 use sqlx::{self, Row, postgres::PgPoolOptions, mysql::MySqlPoolOptions, sqlite::SqlitePoolOptions};
 use std::collections::HashMap;
@@ -36,7 +37,22 @@ impl PostgresAccessor {
 
     async fn get_columns_for_table(&self, schema: &str, table: &str) -> Result<Vec<ColumnMetadata>> {
         let rows = sqlx::query(
-            "SELECT column_name, data_type, is_nullable, character_maximum_length FROM information_schema.columns WHERE table_schema = $1 AND table_name = $2"
+            "SELECT c.column_name,
+                    CASE WHEN c.data_type = 'USER-DEFINED' THEN c.udt_name::text
+                         -- Keep the element type, e.g. integer[], which information_schema reports as ARRAY
+                         WHEN c.data_type = 'ARRAY' THEN format_type(format('%I.%I', c.udt_schema, c.udt_name)::regtype, NULL)
+                         ELSE c.data_type::text END AS data_type,
+                    c.is_nullable, c.character_maximum_length, c.column_default,
+                    c.numeric_precision, c.numeric_scale,
+                    (c.is_identity = 'YES' OR coalesce(c.column_default, '') LIKE 'nextval(%') AS auto_increment,
+                    col_description(format('%I.%I', c.table_schema, c.table_name)::regclass, c.ordinal_position::int) AS description,
+                    (SELECT array_agg(e.enumlabel::text ORDER BY e.enumsortorder)
+                     FROM pg_type t
+                     JOIN pg_namespace n ON n.oid = t.typnamespace
+                     JOIN pg_enum e ON e.enumtypid = t.oid
+                     WHERE t.typname = c.udt_name AND n.nspname = c.udt_schema) AS enum_values
+             FROM information_schema.columns c WHERE c.table_schema = $1 AND c.table_name = $2
+             ORDER BY c.ordinal_position"
         )
         .bind(schema)
         .bind(table)
@@ -47,10 +63,61 @@ impl PostgresAccessor {
             data_type: row.get("data_type"),
             nullable: row.get::<String, _>("is_nullable") == "YES",
             primary_key: false, // set below
-            field_length: row.try_get("character_maximum_length").ok(),
+            field_length: row.try_get::<Option<i32>, _>("character_maximum_length").ok().flatten().map(i64::from),
             unique: None,
             spec: None,
             is_checked: Some(true),
+            default_value: row.try_get("column_default").ok().flatten(),
+            description: row.try_get("description").ok().flatten(),
+            enum_values: row.try_get("enum_values").ok().flatten(),
+            auto_increment: row.try_get("auto_increment").ok(),
+            numeric_precision: row.try_get::<Option<i32>, _>("numeric_precision").ok().flatten().map(i64::from),
+            numeric_scale: row.try_get::<Option<i32>, _>("numeric_scale").ok().flatten().map(i64::from),
+            unsigned: None,
+        }).collect())
+    }
+
+    async fn get_table_description(&self, schema: &str, table: &str) -> Result<Option<String>> {
+        let row = sqlx::query("SELECT obj_description($1::regclass, 'pg_class') AS description")
+            .bind(format!("{}.{}", schema, table))
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.try_get("description").ok().flatten())
+    }
+
+    async fn get_indexes_for_table(&self, schema: &str, table: &str) -> Result<Vec<IndexMetadata>> {
+        let rows = sqlx::query(
+            "SELECT ic.relname AS index_name, ix.indisunique AS is_unique, a.attname AS column_name
+             FROM pg_index ix
+             JOIN pg_class ic ON ic.oid = ix.indexrelid
+             JOIN LATERAL unnest(ix.indkey::int2[]) WITH ORDINALITY AS k(attnum, ord) ON true
+             JOIN pg_attribute a ON a.attrelid = ix.indrelid AND a.attnum = k.attnum
+             WHERE ix.indrelid = $1::regclass AND NOT ix.indisprimary
+             ORDER BY ic.relname, k.ord"
+        )
+        .bind(format!("{}.{}", schema, table))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(group_index_rows(rows.into_iter().map(|row| (
+            row.get::<String, _>("index_name"),
+            row.get::<bool, _>("is_unique"),
+            row.get::<String, _>("column_name"),
+        ))))
+    }
+
+    async fn get_check_constraints_for_table(&self, schema: &str, table: &str) -> Result<Vec<CheckConstraint>> {
+        let rows = sqlx::query(
+            "SELECT conname, pg_get_constraintdef(oid) AS definition
+             FROM pg_constraint
+             WHERE conrelid = $1::regclass AND contype = 'c'
+             ORDER BY conname"
+        )
+        .bind(format!("{}.{}", schema, table))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(|row| CheckConstraint {
+            name: row.get("conname"),
+            expression: check_expression(&row.get::<String, _>("definition")),
         }).collect())
     }
 
@@ -59,7 +126,8 @@ impl PostgresAccessor {
             "SELECT a.attname
              FROM pg_index i
              JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey)
-             WHERE i.indrelid = $1::regclass AND i.indisprimary;"
+             WHERE i.indrelid = $1::regclass AND i.indisprimary
+             ORDER BY array_position(i.indkey::int2[], a.attnum);"
         )
         .bind(format!("{}.{}", schema, table))
         .fetch_all(&self.pool)
@@ -106,10 +174,16 @@ impl DatabaseAccessor for PostgresAccessor {
                 col.primary_key = primary_keys.contains(&col.name);
             }
             let foreign_keys = self.get_foreign_keys_for_table(schema, &table).await?;
+            let indexes = self.get_indexes_for_table(schema, &table).await?;
+            let description = self.get_table_description(schema, &table).await?;
+            let checks = self.get_check_constraints_for_table(schema, &table).await?;
             meta.tables.insert(format!("{}.{}", schema, table), TableMetadata {
                 columns,
                 primary_keys,
                 foreign_keys,
+                indexes,
+                description,
+                checks,
             });
         }
         Ok(meta)
@@ -140,27 +214,101 @@ impl MySqlAccessor {
 
     async fn get_columns_for_table(&self, db: &str, table: &str) -> Result<Vec<ColumnMetadata>> {
         let rows = sqlx::query(
-            "SELECT column_name, data_type, is_nullable, character_maximum_length FROM information_schema.columns WHERE table_schema = ? AND table_name = ?"
+            "SELECT column_name, data_type, is_nullable, character_maximum_length,
+                    column_default AS column_default, column_comment AS column_comment, column_type AS column_type,
+                    numeric_precision AS numeric_precision, numeric_scale AS numeric_scale, extra AS extra
+             FROM information_schema.columns WHERE table_schema = ? AND table_name = ?
+             ORDER BY ordinal_position"
         )
         .bind(db)
         .bind(table)
         .fetch_all(&self.pool)
         .await?;
-        Ok(rows.into_iter().map(|row| ColumnMetadata {
-            name: row.get("column_name"),
-            data_type: row.get("data_type"),
-            nullable: row.get::<String, _>("is_nullable") == "YES",
-            primary_key: false, // set below
-            field_length: row.try_get("character_maximum_length").ok(),
-            unique: None,
-            spec: None,
-            is_checked: Some(true),
+        Ok(rows.into_iter().map(|row| {
+            let column_type = row.try_get::<String, _>("column_type").unwrap_or_default();
+            let data_type: String = row.get("data_type");
+            ColumnMetadata {
+                name: row.get("column_name"),
+                // DATA_TYPE drops the members of SET columns; COLUMN_TYPE keeps them, e.g. set('a','b')
+                data_type: if data_type.eq_ignore_ascii_case("set") { column_type.clone() } else { data_type },
+                nullable: row.get::<String, _>("is_nullable") == "YES",
+                primary_key: false, // set below
+                // The display width of tinyint(1) tells BOOLEAN columns from small integers
+                field_length: row.try_get("character_maximum_length").ok()
+                    .or_else(|| column_type.starts_with("tinyint(1)").then_some(1)),
+                unique: None,
+                spec: None,
+                is_checked: Some(true),
+                default_value: row.try_get("column_default").ok().flatten(),
+                description: row.try_get::<Option<String>, _>("column_comment").ok().flatten().filter(|c| !c.is_empty()),
+                enum_values: parse_mysql_enum(&column_type),
+                auto_increment: row.try_get::<String, _>("extra").ok().map(|e| e.to_lowercase().contains("auto_increment")),
+                numeric_precision: mysql_optional_int(&row, "numeric_precision"),
+                numeric_scale: mysql_optional_int(&row, "numeric_scale"),
+                unsigned: Some(column_type.contains(" unsigned")),
+            }
+        }).collect())
+    }
+
+    async fn get_table_description(&self, db: &str, table: &str) -> Result<Option<String>> {
+        let row = sqlx::query(
+            "SELECT table_comment AS table_comment FROM information_schema.tables WHERE table_schema = ? AND table_name = ?"
+        )
+        .bind(db)
+        .bind(table)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row
+            .and_then(|r| r.try_get::<Option<String>, _>("table_comment").ok().flatten())
+            .filter(|c| !c.is_empty()))
+    }
+
+    async fn get_indexes_for_table(&self, db: &str, table: &str) -> Result<Vec<IndexMetadata>> {
+        let rows = sqlx::query(
+            "SELECT index_name AS index_name, non_unique AS non_unique, column_name AS column_name
+             FROM information_schema.statistics
+             WHERE table_schema = ? AND table_name = ? AND index_name <> 'PRIMARY'
+             ORDER BY index_name, seq_in_index"
+        )
+        .bind(db)
+        .bind(table)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(group_index_rows(rows.into_iter().map(|row| (
+            row.get::<String, _>("index_name"),
+            row.get::<i64, _>("non_unique") == 0,
+            row.get::<String, _>("column_name"),
+        ))))
+    }
+
+    async fn get_check_constraints_for_table(&self, db: &str, table: &str) -> Result<Vec<CheckConstraint>> {
+        let rows = sqlx::query(
+            "SELECT cc.constraint_name AS constraint_name, cc.check_clause AS check_clause
+             FROM information_schema.check_constraints cc
+             JOIN information_schema.table_constraints tc
+               ON tc.constraint_schema = cc.constraint_schema AND tc.constraint_name = cc.constraint_name
+             WHERE tc.table_schema = ? AND tc.table_name = ? AND tc.constraint_type = 'CHECK'
+             ORDER BY cc.constraint_name"
+        )
+        .bind(db)
+        .bind(table)
+        .fetch_all(&self.pool)
+        .await;
+        // Servers before MySQL 8.0.16 have no CHECK_CONSTRAINTS view and ignore CHECK clauses
+        let Ok(rows) = rows else {
+            return Ok(Vec::new());
+        };
+        Ok(rows.into_iter().map(|row| CheckConstraint {
+            name: row.try_get("constraint_name").ok(),
+            expression: row.try_get("check_clause").unwrap_or_default(),
         }).collect())
     }
 
     async fn get_primary_keys_for_table(&self, db: &str, table: &str) -> Result<Vec<String>> {
         let rows = sqlx::query(
-            "SELECT column_name FROM information_schema.key_column_usage WHERE table_schema = ? AND table_name = ? AND constraint_name = 'PRIMARY'"
+            "SELECT column_name FROM information_schema.key_column_usage
+             WHERE table_schema = ? AND table_name = ? AND constraint_name = 'PRIMARY'
+             ORDER BY ordinal_position"
         )
         .bind(db)
         .bind(table)
@@ -202,10 +350,16 @@ impl DatabaseAccessor for MySqlAccessor {
                 col.primary_key = primary_keys.contains(&col.name);
             }
             let foreign_keys = self.get_foreign_keys_for_table(db, &table).await?;
+            let indexes = self.get_indexes_for_table(db, &table).await?;
+            let description = self.get_table_description(db, &table).await?;
+            let checks = self.get_check_constraints_for_table(db, &table).await?;
             meta.tables.insert(format!("{}.{}", db, table), TableMetadata {
                 columns,
                 primary_keys,
                 foreign_keys,
+                indexes,
+                description,
+                checks,
             });
         }
         Ok(meta)
@@ -241,19 +395,69 @@ impl SqliteAccessor {
             name: row.get("name"),
             data_type: row.get("type"),
             nullable: row.get::<i64, _>("notnull") == 0,
-            primary_key: row.get::<i64, _>("pk") == 1,
+            primary_key: row.get::<i64, _>("pk") > 0,
             field_length: None,
             unique: None,
             spec: None,
             is_checked: Some(true),
+            default_value: row.try_get("dflt_value").ok().flatten(),
+            description: None,
+            enum_values: None,
+            auto_increment: None, // set below
+            numeric_precision: None,
+            numeric_scale: None,
+            unsigned: None,
         }).collect())
     }
 
+    async fn get_indexes_for_table(&self, table: &str) -> Result<Vec<IndexMetadata>> {
+        let index_rows = sqlx::query(&format!("PRAGMA index_list('{}')", table))
+            .fetch_all(&self.pool)
+            .await?;
+        let mut indexes = Vec::new();
+        for index_row in index_rows {
+            // Indexes backing the primary key are already covered by primary_keys
+            if index_row.get::<String, _>("origin") == "pk" {
+                continue;
+            }
+            let name = index_row.get::<String, _>("name");
+            let column_rows = sqlx::query(&format!("PRAGMA index_info('{}')", name))
+                .fetch_all(&self.pool)
+                .await?;
+            indexes.push(IndexMetadata {
+                columns: column_rows.into_iter().filter_map(|r| r.try_get::<Option<String>, _>("name").ok().flatten()).collect(),
+                unique: index_row.get::<i64, _>("unique") == 1,
+                name,
+            });
+        }
+        indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(indexes)
+    }
+
     async fn get_primary_keys_for_table(&self, table: &str) -> Result<Vec<String>> {
         let rows = sqlx::query(&format!("PRAGMA table_info('{}')", table))
             .fetch_all(&self.pool)
             .await?;
-        Ok(rows.into_iter().filter(|row| row.get::<i64, _>("pk") == 1).map(|row| row.get("name")).collect())
+        // pk holds the 1-based position within a composite primary key
+        let mut keys: Vec<(i64, String)> = rows.into_iter()
+            .filter(|row| row.get::<i64, _>("pk") > 0)
+            .map(|row| (row.get("pk"), row.get("name")))
+            .collect();
+        keys.sort();
+        Ok(keys.into_iter().map(|(_, name)| name).collect())
+    }
+
+    async fn get_check_constraints_for_table(&self, table: &str) -> Result<Vec<CheckConstraint>> {
+        // SQLite keeps CHECK constraints only in the CREATE TABLE statement
+        let sql: Option<String> = sqlx::query_scalar("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(table)
+            .fetch_optional(&self.pool)
+            .await?
+            .flatten();
+        match sql {
+            Some(sql) => sqlite_check_constraints(&sql),
+            None => Ok(Vec::new()),
+        }
     }
 
     async fn get_foreign_keys_for_table(&self, table: &str) -> Result<HashMap<String, String>> {
@@ -277,15 +481,89 @@ impl DatabaseAccessor for SqliteAccessor {
         let tables = self.get_tables().await?;
         let mut meta = DatabaseMetadata { tables: HashMap::new() };
         for table in tables {
-            let columns = self.get_columns_for_table(&table).await?;
+            let mut columns = self.get_columns_for_table(&table).await?;
             let primary_keys = self.get_primary_keys_for_table(&table).await?;
+            // A single INTEGER PRIMARY KEY column aliases the rowid and is assigned automatically
+            for col in columns.iter_mut() {
+                col.auto_increment = Some(primary_keys.len() == 1 && col.primary_key && col.data_type.eq_ignore_ascii_case("integer"));
+            }
             let foreign_keys = self.get_foreign_keys_for_table(&table).await?;
+            let indexes = self.get_indexes_for_table(&table).await?;
+            let checks = self.get_check_constraints_for_table(&table).await?;
             meta.tables.insert(table.clone(), TableMetadata {
                 columns,
                 primary_keys,
                 foreign_keys,
+                indexes,
+                description: None,
+                checks,
             });
         }
         Ok(meta)
     }
 }
+
+// ------------------- Shared helpers -------------------
+
+/// Opens the accessor for a `--db-type` value.
+pub async fn connect(db_type: &str, connection_string: &str) -> Result<Box<dyn DatabaseAccessor + Send>> {
+    Ok(match db_type.to_lowercase().as_str() {
+        "postgres" | "postgresql" => Box::new(PostgresAccessor::new(connection_string).await?),
+        "mysql" | "mariadb" => Box::new(MySqlAccessor::new(connection_string).await?),
+        "sqlite" => Box::new(SqliteAccessor::new(connection_string).await?),
+        _ => return Err(anyhow!("Unsupported database type: '{}'. Supported types: postgres, mysql, sqlite", db_type)),
+    })
+}
+
+/// Groups (index name, unique, column) rows, ordered by index and column position, into indexes.
+fn group_index_rows(rows: impl Iterator<Item = (String, bool, String)>) -> Vec<IndexMetadata> {
+    let mut indexes: Vec<IndexMetadata> = Vec::new();
+    for (name, unique, column) in rows {
+        match indexes.last_mut() {
+            Some(index) if index.name == name => index.columns.push(column),
+            _ => indexes.push(IndexMetadata { name, columns: vec![column], unique }),
+        }
+    }
+    indexes
+}
+
+/// The expression of a PostgreSQL `CHECK ((price > 0))` constraint definition, without the keyword
+/// and the outer parentheses.
+fn check_expression(definition: &str) -> String {
+    let expression = definition.trim().strip_prefix("CHECK ").unwrap_or(definition).trim();
+    // NOT VALID constraints carry the marker after the expression
+    let expression = expression.strip_suffix(" NOT VALID").unwrap_or(expression);
+    expression.strip_prefix('(').and_then(|e| e.strip_suffix(')')).unwrap_or(expression).to_string()
+}
+
+/// Reads an integer column that MySQL may report as signed or unsigned depending on the server version.
+fn mysql_optional_int(row: &sqlx::mysql::MySqlRow, column: &str) -> Option<i64> {
+    row.try_get::<Option<i64>, _>(column).ok().flatten()
+        .or_else(|| row.try_get::<Option<u64>, _>(column).ok().flatten().map(|v| v as i64))
+}
+
+/// Extracts the labels of a MySQL `enum('a','b')` column type; quotes inside labels are doubled.
+fn parse_mysql_enum(column_type: &str) -> Option<Vec<String>> {
+    let body = column_type.strip_prefix("enum(")?.strip_suffix(')')?;
+    let mut values = Vec::new();
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue;
+        }
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    value.push(chars.next().unwrap());
+                } else {
+                    break;
+                }
+            } else {
+                value.push(c);
+            }
+        }
+        values.push(value);
+    }
+    Some(values)
+}
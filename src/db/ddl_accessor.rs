@@ -0,0 +1,946 @@
+// db/ddl_accessor.rs
+// Offline accessor reading the metadata from SQL DDL scripts instead of a live database.
+
+use super::accessors::DatabaseAccessor;
+use super::models::*;
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use sqlparser::ast::{
+    AlterColumnOperation, AlterTableOperation, ArrayElemTypeDef, ColumnDef, ColumnOption, CommentDef, CommentObject, CreateIndex,
+    CreateTable, DataType, Expr, GeneratedAs, Ident, ObjectName, Statement, TableConstraint, Use,
+    UserDefinedTypeRepresentation, Value,
+};
+use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, TokenWithSpan, Tokenizer};
+use std::collections::HashMap;
+use std::fs;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    fn parse(db_type: &str) -> Result<Self> {
+        match db_type.to_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "mysql" | "mariadb" => Ok(Self::MySql),
+            "sqlite" => Ok(Self::Sqlite),
+            _ => Err(anyhow!("Unsupported database type: '{}'. Supported types: postgres, mysql, sqlite", db_type)),
+        }
+    }
+
+    fn parser_dialect(&self) -> Box<dyn Dialect> {
+        match self {
+            Self::Postgres => Box::new(PostgreSqlDialect {}),
+            Self::MySql => Box::new(MySqlDialect {}),
+            Self::Sqlite => Box::new(SQLiteDialect {}),
+        }
+    }
+}
+
+/// Reads the metadata from the `CREATE TABLE`, `CREATE INDEX`, `CREATE TYPE ... AS ENUM`,
+/// `ALTER TABLE` and `COMMENT ON` statements of SQL scripts such as schema-only dumps, following
+/// the conventions of the live accessor of the dialect (type names, implicit index names, key
+/// format). Other statements are ignored; DDL statements that fail to parse are skipped with a
+/// warning.
+pub struct DdlAccessor {
+    dialect: SqlDialect,
+    statements: Vec<Statement>,
+}
+
+impl DdlAccessor {
+    pub fn from_files(db_type: &str, paths: &[String]) -> Result<Self> {
+        let dialect = SqlDialect::parse(db_type)?;
+        let mut statements = Vec::new();
+        for path in paths {
+            let sql = fs::read_to_string(path).with_context(|| format!("Failed to read DDL script {}", path))?;
+            statements.extend(parse_script(dialect, &sql, path)?);
+        }
+        Ok(Self { dialect, statements })
+    }
+}
+
+#[async_trait]
+impl DatabaseAccessor for DdlAccessor {
+    async fn extract_full_metadata(&mut self, schema_filter: Option<&str>) -> Result<DatabaseMetadata> {
+        let mut builder = Builder::new(self.dialect, schema_filter);
+        builder.build(&self.statements)?;
+        Ok(builder.meta)
+    }
+}
+
+/// CHECK constraints of a SQLite `CREATE TABLE` statement as stored in `sqlite_master`, the only
+/// place SQLite keeps them.
+pub fn sqlite_check_constraints(create_table: &str) -> Result<Vec<CheckConstraint>> {
+    let statements = parse_script(SqlDialect::Sqlite, create_table, "sqlite_master")?;
+    let mut builder = Builder::new(SqlDialect::Sqlite, None);
+    builder.build(&statements)?;
+    Ok(builder.meta.tables.into_values().flat_map(|table| table.checks).collect())
+}
+
+/// Parses the statements of a script that describe tables one at a time; everything else (data,
+/// sequences, functions, grants, ...) is skipped without parsing.
+fn parse_script(dialect: SqlDialect, sql: &str, path: &str) -> Result<Vec<Statement>> {
+    let parser_dialect = dialect.parser_dialect();
+    let tokens = Tokenizer::new(parser_dialect.as_ref(), sql)
+        .tokenize_with_location()
+        .with_context(|| format!("Failed to tokenize {}", path))?;
+    let mut statements = Vec::new();
+    for chunk in tokens.split(|t| t.token == Token::SemiColon) {
+        let Some(first) = chunk.iter().find(|t| !matches!(t.token, Token::Whitespace(_))) else {
+            continue;
+        };
+        if !describes_tables(chunk) {
+            continue;
+        }
+        match Parser::new(parser_dialect.as_ref()).with_tokens_with_locations(chunk.to_vec()).parse_statements() {
+            Ok(parsed) => statements.extend(parsed),
+            Err(e) => eprintln!("Warning: skipping statement at {}:{}: {}", path, first.span.start.line, e),
+        }
+    }
+    Ok(statements)
+}
+
+/// `CREATE TABLE/INDEX/TYPE`, `ALTER TABLE`, `COMMENT` and `USE` statements.
+fn describes_tables(tokens: &[TokenWithSpan]) -> bool {
+    let keywords: Vec<Keyword> = tokens.iter()
+        .filter_map(|t| match &t.token {
+            Token::Word(word) => Some(word.keyword),
+            _ => None,
+        })
+        .take(6)
+        .collect();
+    match keywords.as_slice() {
+        [Keyword::COMMENT | Keyword::USE, ..] | [Keyword::ALTER, Keyword::TABLE, ..] => true,
+        [Keyword::CREATE, rest @ ..] => {
+            let modifiers = [Keyword::OR, Keyword::REPLACE, Keyword::UNIQUE, Keyword::TEMP, Keyword::TEMPORARY, Keyword::UNLOGGED];
+            matches!(rest.iter().find(|k| !modifiers.contains(k)), Some(Keyword::TABLE | Keyword::INDEX | Keyword::TYPE))
+        }
+        _ => false,
+    }
+}
+
+/// Constraints of a table, declared inline with a column or separately.
+enum Constraint {
+    PrimaryKey(Vec<String>),
+    Unique(Option<String>, Vec<String>),
+    /// MySQL `KEY`/`INDEX` inside `CREATE TABLE`.
+    Index(Option<String>, Vec<String>),
+    /// Name, columns, referenced table key and referenced columns (empty for its primary key).
+    ForeignKey(Option<String>, Vec<String>, String, Vec<String>),
+    Check(Option<String>, String),
+}
+
+/// Column type in the form the live accessor reports it.
+struct ColumnType {
+    data_type: String,
+    field_length: Option<i64>,
+    numeric_precision: Option<i64>,
+    numeric_scale: Option<i64>,
+    enum_values: Option<Vec<String>>,
+    /// PostgreSQL `serial` types, which are integers with a sequence default.
+    serial: bool,
+    /// MySQL `UNSIGNED` attribute.
+    unsigned: bool,
+}
+
+struct Builder<'a> {
+    dialect: SqlDialect,
+    schema_filter: Option<&'a str>,
+    /// Database of unqualified MySQL tables, set by `USE`.
+    current_database: Option<String>,
+    /// PostgreSQL enum types by name.
+    enums: HashMap<String, Vec<String>>,
+    meta: DatabaseMetadata,
+    /// Foreign keys referencing the primary key implicitly, resolved once all tables are known.
+    implicit_references: Vec<(String, Vec<String>, String)>,
+    /// MySQL indexes every foreign key gets unless an existing index starts with its columns.
+    foreign_key_indexes: Vec<(String, String, Vec<String>)>,
+    /// Number of `sqlite_autoindex_<table>_<n>` indexes per table.
+    autoindexes: HashMap<String, usize>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(dialect: SqlDialect, schema_filter: Option<&'a str>) -> Self {
+        Self {
+            dialect,
+            schema_filter,
+            current_database: None,
+            enums: HashMap::new(),
+            meta: DatabaseMetadata { tables: HashMap::new() },
+            implicit_references: Vec::new(),
+            foreign_key_indexes: Vec::new(),
+            autoindexes: HashMap::new(),
+        }
+    }
+
+    fn build(&mut self, statements: &[Statement]) -> Result<()> {
+        // Enum types may be declared after the tables using them
+        for statement in statements {
+            if let Statement::CreateType { name, representation: UserDefinedTypeRepresentation::Enum { labels } } = statement {
+                let labels = labels.iter().map(|l| l.value.clone()).collect();
+                self.enums.insert(self.last_name(name), labels);
+            }
+        }
+        for statement in statements {
+            match statement {
+                Statement::Use(Use::Object(name) | Use::Database(name)) => self.current_database = Some(self.last_name(name)),
+                Statement::CreateTable(create) => self.create_table(create)?,
+                Statement::CreateIndex(create) => self.create_index(create)?,
+                Statement::AlterTable { name, operations, .. } => self.alter_table(name, operations)?,
+                Statement::Comment { object_type, object_name, comment, .. } => self.comment(object_type, object_name, comment.clone())?,
+                _ => {}
+            }
+        }
+        self.finish();
+        Ok(())
+    }
+
+    fn create_table(&mut self, create: &CreateTable) -> Result<()> {
+        let key = self.table_key(&create.name)?;
+        if !self.in_scope(&key) {
+            return Ok(());
+        }
+        let description = create.comment.as_ref().map(|comment| match comment {
+            CommentDef::WithEq(text) | CommentDef::WithoutEq(text) | CommentDef::AfterColumnDefsWithoutEq(text) => text.clone(),
+        });
+        self.meta.tables.insert(key.clone(), TableMetadata { description, ..Default::default() });
+        for def in &create.columns {
+            self.add_column(&key, def)?;
+        }
+        for constraint in &create.constraints {
+            if let Some(constraint) = self.table_constraint(constraint)? {
+                self.apply(&key, constraint);
+            }
+        }
+        Ok(())
+    }
+
+    fn create_index(&mut self, create: &CreateIndex) -> Result<()> {
+        let key = self.table_key(&create.table_name)?;
+        if !self.meta.tables.contains_key(&key) {
+            return Ok(());
+        }
+        let columns: Vec<String> = create.columns.iter()
+            .map(|column| match &column.expr {
+                Expr::Identifier(ident) => self.name(ident),
+                expr => expr.to_string(),
+            })
+            .collect();
+        let name = match &create.name {
+            Some(name) => self.last_name(name),
+            None => format!("{}_{}_idx", bare_name(&key), columns.join("_")),
+        };
+        self.table(&key).indexes.push(IndexMetadata { name, columns, unique: create.unique });
+        Ok(())
+    }
+
+    fn alter_table(&mut self, name: &ObjectName, operations: &[AlterTableOperation]) -> Result<()> {
+        let key = self.table_key(name)?;
+        if !self.meta.tables.contains_key(&key) {
+            return Ok(());
+        }
+        for operation in operations {
+            match operation {
+                AlterTableOperation::AddColumn { column_def, .. } => self.add_column(&key, column_def)?,
+                AlterTableOperation::AddConstraint(constraint) => {
+                    if let Some(constraint) = self.table_constraint(constraint)? {
+                        self.apply(&key, constraint);
+                    }
+                }
+                AlterTableOperation::AlterColumn { column_name, op } => {
+                    let column_name = self.name(column_name);
+                    let default = match op {
+                        AlterColumnOperation::SetDefault { value } => Some(self.default_value(value)),
+                        _ => None,
+                    };
+                    let Some(col) = self.table(&key).columns.iter_mut().find(|c| c.name == column_name) else {
+                        continue;
+                    };
+                    match op {
+                        AlterColumnOperation::SetNotNull => col.nullable = false,
+                        AlterColumnOperation::DropNotNull => col.nullable = true,
+                        AlterColumnOperation::DropDefault => col.default_value = None,
+                        AlterColumnOperation::AddGenerated { .. } => col.auto_increment = Some(true),
+                        _ => {}
+                    }
+                    if let Some(default) = default {
+                        col.auto_increment = Some(col.auto_increment == Some(true) || is_sequence_default(default.as_deref()));
+                        col.default_value = default;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn comment(&mut self, object_type: &CommentObject, object_name: &ObjectName, comment: Option<String>) -> Result<()> {
+        match object_type {
+            CommentObject::Table => {
+                let key = self.table_key(object_name)?;
+                if let Some(table) = self.meta.tables.get_mut(&key) {
+                    table.description = comment;
+                }
+            }
+            CommentObject::Column => {
+                let Some((column, table_name)) = object_name.0.split_last() else {
+                    return Ok(());
+                };
+                let column = column.as_ident().map(|i| self.name(i)).unwrap_or_default();
+                let key = self.table_key(&ObjectName(table_name.to_vec()))?;
+                if let Some(col) = self.meta.tables.get_mut(&key).and_then(|t| t.columns.iter_mut().find(|c| c.name == column)) {
+                    col.description = comment;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn add_column(&mut self, key: &str, def: &ColumnDef) -> Result<()> {
+        let name = self.name(&def.name);
+        let column_type = self.column_type(&def.data_type);
+        let mut col = ColumnMetadata {
+            name: name.clone(),
+            data_type: column_type.data_type,
+            nullable: true,
+            primary_key: false, // set in finish
+            field_length: column_type.field_length,
+            unique: None,
+            spec: None,
+            is_checked: Some(true),
+            default_value: None,
+            description: None,
+            enum_values: column_type.enum_values,
+            auto_increment: (self.dialect != SqlDialect::Sqlite).then_some(column_type.serial),
+            numeric_precision: column_type.numeric_precision,
+            numeric_scale: column_type.numeric_scale,
+            unsigned: (self.dialect == SqlDialect::MySql).then_some(column_type.unsigned),
+        };
+        // serial columns are integers defaulting to the sequence PostgreSQL creates for them
+        if column_type.serial {
+            col.default_value = Some(format!("nextval('{}'::regclass)", self.sequence_name(key, &name)));
+        }
+        let mut constraints = Vec::new();
+        for option in &def.options {
+            let constraint_name = option.name.as_ref().map(|n| self.name(n));
+            match &option.option {
+                ColumnOption::Null => col.nullable = true,
+                ColumnOption::NotNull => col.nullable = false,
+                ColumnOption::Default(expr) => {
+                    col.default_value = self.default_value(expr);
+                    if is_sequence_default(col.default_value.as_deref()) {
+                        col.auto_increment = Some(true);
+                    }
+                }
+                ColumnOption::Unique { is_primary: true, .. } => constraints.push(Constraint::PrimaryKey(vec![name.clone()])),
+                ColumnOption::Unique { is_primary: false, .. } => constraints.push(Constraint::Unique(constraint_name, vec![name.clone()])),
+                ColumnOption::ForeignKey { foreign_table, referred_columns, .. } => constraints.push(Constraint::ForeignKey(
+                    constraint_name,
+                    vec![name.clone()],
+                    self.table_key(foreign_table)?,
+                    referred_columns.iter().map(|c| self.name(c)).collect(),
+                )),
+                ColumnOption::Check(expr) => constraints.push(Constraint::Check(constraint_name, self.check_expression(expr))),
+                ColumnOption::Comment(text) => col.description = Some(text.clone()),
+                ColumnOption::DialectSpecific(tokens) if tokens.iter().any(is_auto_increment) && self.dialect == SqlDialect::MySql => {
+                    col.auto_increment = Some(true)
+                }
+                ColumnOption::Generated { generated_as: GeneratedAs::Always | GeneratedAs::ByDefault, generation_expr: None, .. } => {
+                    col.auto_increment = Some(true)
+                }
+                _ => {}
+            }
+        }
+        self.table(key).columns.push(col);
+        for constraint in constraints {
+            self.apply(key, constraint);
+        }
+        Ok(())
+    }
+
+    fn table_constraint(&self, constraint: &TableConstraint) -> Result<Option<Constraint>> {
+        let names = |columns: &[Ident]| columns.iter().map(|c| self.name(c)).collect::<Vec<_>>();
+        let constraint_name = |name: &Option<Ident>| name.as_ref().map(|n| self.name(n));
+        Ok(Some(match constraint {
+            TableConstraint::PrimaryKey { columns, .. } => Constraint::PrimaryKey(names(columns)),
+            TableConstraint::Unique { name, index_name, columns, .. } => {
+                Constraint::Unique(constraint_name(index_name).or(constraint_name(name)), names(columns))
+            }
+            TableConstraint::Index { name, columns, .. } => Constraint::Index(constraint_name(name), names(columns)),
+            TableConstraint::ForeignKey { name, columns, foreign_table, referred_columns, .. } => Constraint::ForeignKey(
+                constraint_name(name),
+                names(columns),
+                self.table_key(foreign_table)?,
+                names(referred_columns),
+            ),
+            TableConstraint::Check { name, expr } => Constraint::Check(constraint_name(name), self.check_expression(expr)),
+            _ => return Ok(None),
+        }))
+    }
+
+    fn apply(&mut self, key: &str, constraint: Constraint) {
+        let table_name = bare_name(key).to_string();
+        match constraint {
+            Constraint::PrimaryKey(columns) => {
+                // Only a single INTEGER primary key aliases the rowid, any other gets an autoindex
+                if self.dialect == SqlDialect::Sqlite && !self.is_rowid_alias(key, &columns) {
+                    self.next_autoindex(key);
+                }
+                self.table(key).primary_keys = columns;
+            }
+            Constraint::Unique(name, columns) => {
+                let name = match self.dialect {
+                    SqlDialect::Postgres => name.unwrap_or_else(|| format!("{}_{}_key", table_name, columns.join("_"))),
+                    SqlDialect::MySql => name.unwrap_or_else(|| self.mysql_index_name(key, &columns[0])),
+                    SqlDialect::Sqlite => format!("sqlite_autoindex_{}_{}", table_name, self.next_autoindex(key)),
+                };
+                self.table(key).indexes.push(IndexMetadata { name, columns, unique: true });
+            }
+            Constraint::Index(name, columns) => {
+                let name = name.unwrap_or_else(|| self.mysql_index_name(key, &columns[0]));
+                self.table(key).indexes.push(IndexMetadata { name, columns, unique: false });
+            }
+            Constraint::ForeignKey(name, columns, target, referred_columns) => {
+                if self.dialect == SqlDialect::MySql {
+                    let count = self.foreign_key_indexes.iter().filter(|(k, _, _)| k == key).count();
+                    let name = name.unwrap_or_else(|| format!("{}_ibfk_{}", table_name, count + 1));
+                    self.foreign_key_indexes.push((key.to_string(), name, columns.clone()));
+                }
+                if referred_columns.is_empty() {
+                    self.implicit_references.push((key.to_string(), columns, target));
+                } else {
+                    for (column, referred) in columns.into_iter().zip(referred_columns) {
+                        self.table(key).foreign_keys.insert(column, format!("{}.{}", target, referred));
+                    }
+                }
+            }
+            Constraint::Check(name, expression) => self.table(key).checks.push(CheckConstraint { name, expression }),
+        }
+    }
+
+    /// Resolves implicit foreign key targets and derives what the live accessors report for
+    /// primary key columns.
+    fn finish(&mut self) {
+        for (key, columns, target) in std::mem::take(&mut self.implicit_references) {
+            let referred = self.meta.tables.get(&target).map(|t| t.primary_keys.clone()).unwrap_or_default();
+            for (column, referred) in columns.into_iter().zip(referred) {
+                self.table(&key).foreign_keys.insert(column, format!("{}.{}", target, referred));
+            }
+        }
+        for (key, name, columns) in std::mem::take(&mut self.foreign_key_indexes) {
+            let table = self.table(&key);
+            let covered = table.primary_keys.starts_with(&columns)
+                || table.indexes.iter().any(|index| index.columns.starts_with(&columns));
+            if !covered {
+                table.indexes.push(IndexMetadata { name, columns, unique: false });
+            }
+        }
+        let dialect = self.dialect;
+        for table in self.meta.tables.values_mut() {
+            let single_key = table.primary_keys.len() == 1;
+            for col in table.columns.iter_mut() {
+                col.primary_key = table.primary_keys.contains(&col.name);
+                match dialect {
+                    // A single INTEGER PRIMARY KEY column aliases the rowid and is assigned automatically
+                    SqlDialect::Sqlite => {
+                        col.auto_increment = Some(single_key && col.primary_key && col.data_type.eq_ignore_ascii_case("integer"))
+                    }
+                    // SQLite is the only dialect allowing NULL in primary key columns
+                    _ if col.primary_key => col.nullable = false,
+                    _ => {}
+                }
+            }
+            table.indexes.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+    }
+
+    fn column_type(&self, data_type: &DataType) -> ColumnType {
+        let declared = data_type.to_string();
+        let (base, params) = split_declared(&declared);
+        let mut column_type = ColumnType {
+            data_type: declared.clone(),
+            field_length: None,
+            numeric_precision: None,
+            numeric_scale: None,
+            enum_values: None,
+            serial: false,
+            unsigned: false,
+        };
+        match self.dialect {
+            // PRAGMA table_info reports the type as declared
+            SqlDialect::Sqlite => {}
+            SqlDialect::Postgres => {
+                // Arrays keep their element type, e.g. `integer[]`, like the live accessor reports them
+                if let DataType::Array(ArrayElemTypeDef::SquareBracket(element, _) | ArrayElemTypeDef::AngleBracket(element) | ArrayElemTypeDef::Parenthesis(element)) = data_type {
+                    column_type.data_type = format!("{}[]", self.column_type(element).data_type);
+                    return column_type;
+                }
+                if let DataType::Custom(name, _) = data_type {
+                    if let Some(labels) = self.enums.get(&self.last_name(name)) {
+                        column_type.data_type = self.last_name(name);
+                        column_type.enum_values = Some(labels.clone());
+                        return column_type;
+                    }
+                }
+                let (name, serial) = match base.as_str() {
+                    "serial" | "serial4" => ("integer", true),
+                    "bigserial" | "serial8" => ("bigint", true),
+                    "smallserial" | "serial2" => ("smallint", true),
+                    "int" | "int4" | "integer" => ("integer", false),
+                    "int8" | "bigint" => ("bigint", false),
+                    "int2" | "smallint" => ("smallint", false),
+                    "decimal" | "numeric" => ("numeric", false),
+                    "float4" | "real" => ("real", false),
+                    "float8" | "float" | "double precision" => ("double precision", false),
+                    "bool" | "boolean" => ("boolean", false),
+                    "varchar" | "character varying" => ("character varying", false),
+                    "char" | "character" | "bpchar" => ("character", false),
+                    "timestamp" | "timestamp without time zone" => ("timestamp without time zone", false),
+                    "timestamptz" | "timestamp with time zone" => ("timestamp with time zone", false),
+                    "time" | "time without time zone" => ("time without time zone", false),
+                    "timetz" | "time with time zone" => ("time with time zone", false),
+                    other if other.ends_with("[]") || other.starts_with("array") => ("ARRAY", false),
+                    other => (other, false),
+                };
+                column_type.data_type = name.to_string();
+                column_type.serial = serial;
+                match name {
+                    "character varying" => column_type.field_length = params.first().copied(),
+                    "character" => column_type.field_length = Some(params.first().copied().unwrap_or(1)),
+                    "numeric" => {
+                        column_type.numeric_precision = params.first().copied();
+                        column_type.numeric_scale = params.get(1).copied().or(params.first().map(|_| 0));
+                    }
+                    "smallint" | "integer" | "bigint" => {
+                        column_type.numeric_precision = Some(match name {
+                            "smallint" => 16,
+                            "integer" => 32,
+                            _ => 64,
+                        });
+                        column_type.numeric_scale = Some(0);
+                    }
+                    "real" => column_type.numeric_precision = Some(24),
+                    "double precision" => column_type.numeric_precision = Some(53),
+                    _ => {}
+                }
+            }
+            SqlDialect::MySql => {
+                if let DataType::Enum(members, _) = data_type {
+                    column_type.data_type = "enum".to_string();
+                    column_type.enum_values = Some(members.iter()
+                        .map(|member| match member {
+                            sqlparser::ast::EnumMember::Name(name) | sqlparser::ast::EnumMember::NamedValue(name, _) => name.clone(),
+                        })
+                        .collect());
+                    return column_type;
+                }
+                // Members are kept in the type the way COLUMN_TYPE reports them, e.g. set('a','b')
+                if let DataType::Set(members) = data_type {
+                    column_type.data_type = format!("set({})", members.iter().map(|m| format!("'{}'", m.replace('\'', "''"))).collect::<Vec<_>>().join(","));
+                    return column_type;
+                }
+                let unsigned = base.split_whitespace().any(|word| word == "unsigned");
+                let base = base.split_whitespace().filter(|word| *word != "unsigned").collect::<Vec<_>>().join(" ");
+                column_type.unsigned = unsigned;
+                let name = match base.as_str() {
+                    "integer" => "int",
+                    "character varying" => "varchar",
+                    "character" => "char",
+                    "numeric" => "decimal",
+                    "double precision" | "real" => "double",
+                    "bool" | "boolean" => "tinyint",
+                    other => other.split_whitespace().next().unwrap_or(other),
+                };
+                column_type.data_type = name.to_string();
+                match name {
+                    "char" | "binary" => column_type.field_length = Some(params.first().copied().unwrap_or(1)),
+                    "varchar" | "varbinary" => column_type.field_length = params.first().copied(),
+                    "tinyint" if matches!(base.as_str(), "bool" | "boolean") || params.first() == Some(&1) => {
+                        column_type.field_length = Some(1);
+                        column_type.numeric_precision = Some(3);
+                        column_type.numeric_scale = Some(0);
+                    }
+                    "tinytext" => column_type.field_length = Some(255),
+                    "text" => column_type.field_length = Some(65_535),
+                    "mediumtext" => column_type.field_length = Some(16_777_215),
+                    "longtext" => column_type.field_length = Some(4_294_967_295),
+                    "decimal" => {
+                        column_type.numeric_precision = Some(params.first().copied().unwrap_or(10));
+                        column_type.numeric_scale = Some(params.get(1).copied().unwrap_or(0));
+                    }
+                    "tinyint" | "smallint" | "mediumint" | "int" | "bigint" => {
+                        column_type.numeric_precision = Some(match name {
+                            "tinyint" => 3,
+                            "smallint" => 5,
+                            "mediumint" if unsigned => 8,
+                            "mediumint" => 7,
+                            "int" => 10,
+                            "bigint" if unsigned => 20,
+                            _ => 19,
+                        });
+                        column_type.numeric_scale = Some(0);
+                    }
+                    "bit" => column_type.numeric_precision = Some(params.first().copied().unwrap_or(1)),
+                    "float" => column_type.numeric_precision = Some(12),
+                    "double" => column_type.numeric_precision = Some(22),
+                    _ => {}
+                }
+            }
+        }
+        column_type
+    }
+
+    /// MySQL reports string literal defaults without quotes and bit literals as `b'1'`; PostgreSQL
+    /// reports cast types in lower case, e.g. `nextval('users_id_seq'::regclass)`. NULL defaults
+    /// are reported as none.
+    fn default_value(&self, expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Value(value) => match &value.value {
+                Value::Null => None,
+                Value::SingleQuotedString(text) | Value::DoubleQuotedString(text) if self.dialect == SqlDialect::MySql => {
+                    Some(text.clone())
+                }
+                Value::SingleQuotedByteStringLiteral(bits) if self.dialect == SqlDialect::MySql => Some(format!("b'{}'", bits)),
+                _ => Some(expr.to_string()),
+            },
+            // pg_dump qualifies every sequence; the live server omits schemas on the search path
+            _ if self.dialect == SqlDialect::Postgres => Some(lowercase_casts(&expr.to_string()).replace("nextval('public.", "nextval('")),
+            _ => Some(expr.to_string()),
+        }
+    }
+
+    /// CHECK expression with PostgreSQL casts printed like `pg_get_constraintdef` does.
+    fn check_expression(&self, expr: &Expr) -> String {
+        match self.dialect {
+            SqlDialect::Postgres => lowercase_casts(&expr.to_string()),
+            _ => expr.to_string(),
+        }
+    }
+
+    /// Name of the sequence backing a serial column as `regclass` prints it: `<table>_<column>_seq`,
+    /// qualified when the table is not in the `public` schema and quoted when needed.
+    fn sequence_name(&self, key: &str, column: &str) -> String {
+        let (schema, table) = key.rsplit_once('.').unwrap_or(("public", key));
+        let sequence = regclass_identifier(&format!("{}_{}_seq", table, column));
+        if schema == "public" {
+            sequence
+        } else {
+            format!("{}.{}", regclass_identifier(schema), sequence)
+        }
+    }
+
+    /// Key of a table as the live accessor reports it: `schema.table` on PostgreSQL, `database.table`
+    /// on MySQL and the bare name on SQLite.
+    fn table_key(&self, name: &ObjectName) -> Result<String> {
+        let parts: Vec<String> = name.0.iter().filter_map(|part| part.as_ident()).map(|ident| self.name(ident)).collect();
+        let Some((table, qualifiers)) = parts.split_last() else {
+            bail!("Missing table name in {}", name);
+        };
+        let schema = qualifiers.last().cloned();
+        Ok(match self.dialect {
+            SqlDialect::Sqlite => table.clone(),
+            SqlDialect::Postgres => format!("{}.{}", schema.as_deref().unwrap_or("public"), table),
+            SqlDialect::MySql => {
+                let database = schema
+                    .or_else(|| self.current_database.clone())
+                    .or_else(|| self.schema_filter.map(str::to_string))
+                    .ok_or_else(|| anyhow!("Table {} has no database; pass --schema-or-database or add a USE statement", name))?;
+                format!("{}.{}", database, table)
+            }
+        })
+    }
+
+    /// Like the live accessors, PostgreSQL reads one schema (`public` by default) and MySQL the
+    /// filtered database; SQLite has only one.
+    fn in_scope(&self, key: &str) -> bool {
+        let schema = key.rsplit_once('.').map(|(schema, _)| schema);
+        match self.dialect {
+            SqlDialect::Sqlite => true,
+            SqlDialect::Postgres => schema == Some(self.schema_filter.unwrap_or("public")),
+            SqlDialect::MySql => self.schema_filter.map_or(true, |filter| schema == Some(filter)),
+        }
+    }
+
+    /// PostgreSQL folds unquoted identifiers to lower case.
+    fn name(&self, ident: &Ident) -> String {
+        match (self.dialect, ident.quote_style) {
+            (SqlDialect::Postgres, None) => ident.value.to_lowercase(),
+            _ => ident.value.clone(),
+        }
+    }
+
+    fn last_name(&self, name: &ObjectName) -> String {
+        name.0.last().and_then(|part| part.as_ident()).map(|ident| self.name(ident)).unwrap_or_default()
+    }
+
+    fn table(&mut self, key: &str) -> &mut TableMetadata {
+        self.meta.tables.get_mut(key).expect("constraints are only applied to known tables")
+    }
+
+    fn is_rowid_alias(&self, key: &str, columns: &[String]) -> bool {
+        let table = &self.meta.tables[key];
+        columns.len() == 1 && table.columns.iter().any(|c| c.name == columns[0] && c.data_type.eq_ignore_ascii_case("integer"))
+    }
+
+    fn next_autoindex(&mut self, key: &str) -> usize {
+        let count = self.autoindexes.entry(key.to_string()).or_default();
+        *count += 1;
+        *count
+    }
+
+    /// MySQL names unnamed indexes after their first column, numbering duplicates from `_2`.
+    fn mysql_index_name(&self, key: &str, column: &str) -> String {
+        let indexes = &self.meta.tables[key].indexes;
+        let mut name = column.to_string();
+        let mut n = 2;
+        while indexes.iter().any(|index| index.name == name) {
+            name = format!("{}_{}", column, n);
+            n += 1;
+        }
+        name
+    }
+}
+
+/// Splits a declared type such as `NUMERIC(10,2)` or `TIMESTAMP(3) WITH TIME ZONE` into the
+/// lower-cased name without parameters and the numeric parameters.
+fn split_declared(declared: &str) -> (String, Vec<i64>) {
+    let mut base = String::new();
+    let mut params = String::new();
+    let mut depth = 0;
+    for c in declared.to_lowercase().chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ if depth > 0 => params.push(c),
+            _ => base.push(c),
+        }
+    }
+    let base = base.split_whitespace().collect::<Vec<_>>().join(" ");
+    (base, params.split(',').filter_map(|p| p.trim().parse().ok()).collect())
+}
+
+/// Lower-cases the type names of `::` casts outside string literals, as PostgreSQL prints them.
+fn lowercase_casts(expression: &str) -> String {
+    // Words that continue a multi-word type name such as `character varying`
+    const CONTINUATIONS: [&str; 6] = ["varying", "precision", "with", "without", "time", "zone"];
+    let mut result = String::with_capacity(expression.len());
+    let mut rest = expression;
+    let mut in_string = false;
+    while let Some(c) = rest.chars().next() {
+        if !in_string && rest.starts_with("::") {
+            result.push_str("::");
+            rest = &rest[2..];
+            loop {
+                let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '[' | ']'))).unwrap_or(rest.len());
+                result.push_str(&rest[..end].to_lowercase());
+                rest = &rest[end..];
+                let next_word = rest.strip_prefix(' ').and_then(|r| r.split(|c: char| !c.is_ascii_alphabetic()).next());
+                if !next_word.is_some_and(|word| CONTINUATIONS.contains(&word.to_lowercase().as_str())) {
+                    break;
+                }
+                result.push(' ');
+                rest = &rest[1..];
+            }
+            continue;
+        }
+        if c == '\'' {
+            in_string = !in_string;
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+/// Identifier as PostgreSQL prints it in a `regclass`: quoted unless it is a plain lower-case name.
+fn regclass_identifier(name: &str) -> String {
+    let plain = name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '$'));
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+fn bare_name(key: &str) -> &str {
+    key.rsplit_once('.').map_or(key, |(_, table)| table)
+}
+
+fn is_auto_increment(token: &Token) -> bool {
+    matches!(token, Token::Word(word) if word.keyword == Keyword::AUTO_INCREMENT)
+}
+
+fn is_sequence_default(default: Option<&str>) -> bool {
+    default.is_some_and(|d| d.to_lowercase().starts_with("nextval("))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Excerpt of `pg_dump --schema-only`.
+    const POSTGRES_DUMP: &str = r#"
+SET statement_timeout = 0;
+SELECT pg_catalog.set_config('search_path', '', false);
+CREATE TYPE public.mood AS ENUM ('happy', 'sad');
+CREATE TABLE public.users (
+    id integer NOT NULL,
+    "Email" character varying(120) NOT NULL,
+    mood public.mood DEFAULT 'happy'::public.mood,
+    status character varying(10) DEFAULT 'new'::character varying,
+    balance numeric(12,2),
+    CONSTRAINT users_balance_check CHECK ((balance >= (0)::numeric))
+);
+CREATE SEQUENCE public.users_id_seq AS integer START WITH 1 INCREMENT BY 1 NO MINVALUE NO MAXVALUE CACHE 1;
+ALTER SEQUENCE public.users_id_seq OWNED BY public.users.id;
+CREATE TABLE public.posts (
+    id bigserial PRIMARY KEY,
+    user_id integer,
+    scores integer[]
+);
+CREATE TABLE audit.events (id serial PRIMARY KEY);
+ALTER TABLE ONLY public.users ALTER COLUMN id SET DEFAULT nextval('public.users_id_seq'::regclass);
+ALTER TABLE ONLY public.users ADD CONSTRAINT users_pkey PRIMARY KEY (id);
+ALTER TABLE ONLY public.posts ADD CONSTRAINT posts_user_id_fkey FOREIGN KEY (user_id) REFERENCES public.users(id);
+COMMENT ON TABLE public.users IS 'Registered users';
+"#;
+
+    /// Excerpt of `mysqldump --no-data`.
+    const MYSQL_DUMP: &str = r#"
+/*!40101 SET NAMES utf8mb4 */;
+USE `shop`;
+CREATE TABLE `orders` (
+  `id` bigint NOT NULL AUTO_INCREMENT,
+  `customer_id` int unsigned NOT NULL COMMENT 'Buyer',
+  `quantity` smallint unsigned DEFAULT '1',
+  `views` bigint unsigned NOT NULL,
+  `tags` set('gift','rush''s') DEFAULT NULL,
+  `paid` bit(1) NOT NULL DEFAULT b'0',
+  `status` enum('open','paid') DEFAULT 'open',
+  `total` decimal(10,2) DEFAULT NULL,
+  PRIMARY KEY (`id`),
+  KEY `customer_id` (`customer_id`),
+  CONSTRAINT `orders_chk_1` CHECK ((`total` >= 0))
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+"#;
+
+    /// Output of the sqlite3 `.schema` command.
+    const SQLITE_SCHEMA: &str = r#"
+CREATE TABLE customers (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE);
+CREATE TABLE orders (
+    id INTEGER PRIMARY KEY,
+    customer_id INTEGER REFERENCES customers,
+    quantity INTEGER DEFAULT 1 CHECK (quantity > 0),
+    CONSTRAINT known_customer CHECK (customer_id IS NOT NULL)
+);
+CREATE INDEX orders_customer ON orders (customer_id);
+"#;
+
+    fn metadata(db_type: &str, sql: &str, schema_filter: Option<&str>) -> DatabaseMetadata {
+        let dialect = SqlDialect::parse(db_type).unwrap();
+        let mut builder = Builder::new(dialect, schema_filter);
+        builder.build(&parse_script(dialect, sql, "dump.sql").unwrap()).unwrap();
+        builder.meta
+    }
+
+    fn column<'a>(meta: &'a DatabaseMetadata, table: &str, name: &str) -> &'a ColumnMetadata {
+        meta.tables[table].columns.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[test]
+    fn postgres_dump() {
+        let meta = metadata("postgres", POSTGRES_DUMP, None);
+        let mut tables: Vec<&String> = meta.tables.keys().collect();
+        tables.sort();
+        assert_eq!(tables, ["public.posts", "public.users"]);
+
+        let users = &meta.tables["public.users"];
+        assert_eq!(users.primary_keys, ["id"]);
+        assert_eq!(users.description.as_deref(), Some("Registered users"));
+        assert_eq!(users.checks, [CheckConstraint {
+            name: Some("users_balance_check".to_string()),
+            expression: "(balance >= (0)::numeric)".to_string(),
+        }]);
+        let id = column(&meta, "public.users", "id");
+        assert_eq!(id.default_value.as_deref(), Some("nextval('users_id_seq'::regclass)"));
+        assert_eq!(id.auto_increment, Some(true));
+        let email = column(&meta, "public.users", "Email");
+        assert_eq!((email.data_type.as_str(), email.field_length), ("character varying", Some(120)));
+        let mood = column(&meta, "public.users", "mood");
+        assert_eq!(mood.enum_values.as_deref(), Some(&["happy".to_string(), "sad".to_string()][..]));
+        assert_eq!(mood.default_value.as_deref(), Some("'happy'::public.mood"));
+        assert_eq!(column(&meta, "public.users", "status").default_value.as_deref(), Some("'new'::character varying"));
+
+        let post_id = column(&meta, "public.posts", "id");
+        assert_eq!(post_id.data_type, "bigint");
+        assert_eq!(post_id.default_value.as_deref(), Some("nextval('posts_id_seq'::regclass)"));
+        assert_eq!(column(&meta, "public.posts", "scores").data_type, "integer[]");
+        assert_eq!(meta.tables["public.posts"].foreign_keys["user_id"], "public.users.id");
+
+        // Sequences outside the search path are qualified
+        let audit = metadata("postgres", POSTGRES_DUMP, Some("audit"));
+        assert_eq!(column(&audit, "audit.events", "id").default_value.as_deref(), Some("nextval('audit.events_id_seq'::regclass)"));
+    }
+
+    #[test]
+    fn mysql_dump() {
+        let meta = metadata("mysql", MYSQL_DUMP, None);
+        let orders = &meta.tables["shop.orders"];
+        assert_eq!(orders.primary_keys, ["id"]);
+        assert_eq!(orders.indexes, [IndexMetadata { name: "customer_id".to_string(), columns: vec!["customer_id".to_string()], unique: false }]);
+        assert_eq!(orders.checks, [CheckConstraint { name: Some("orders_chk_1".to_string()), expression: "(`total` >= 0)".to_string() }]);
+
+        assert_eq!(column(&meta, "shop.orders", "id").auto_increment, Some(true));
+        let customer_id = column(&meta, "shop.orders", "customer_id");
+        assert_eq!(customer_id.description.as_deref(), Some("Buyer"));
+        assert_eq!((customer_id.data_type.as_str(), customer_id.numeric_precision, customer_id.unsigned), ("int", Some(10), Some(true)));
+        let quantity = column(&meta, "shop.orders", "quantity");
+        assert_eq!((quantity.data_type.as_str(), quantity.numeric_precision, quantity.unsigned), ("smallint", Some(5), Some(true)));
+        let views = column(&meta, "shop.orders", "views");
+        assert_eq!((views.data_type.as_str(), views.numeric_precision, views.unsigned), ("bigint", Some(20), Some(true)));
+        assert_eq!(column(&meta, "shop.orders", "id").unsigned, Some(false));
+        assert_eq!(column(&meta, "shop.orders", "tags").data_type, "set('gift','rush''s')");
+        let paid = column(&meta, "shop.orders", "paid");
+        assert_eq!((paid.default_value.as_deref(), paid.numeric_precision), (Some("b'0'"), Some(1)));
+        let status = column(&meta, "shop.orders", "status");
+        assert_eq!(status.default_value.as_deref(), Some("open"));
+        assert_eq!(status.enum_values.as_deref(), Some(&["open".to_string(), "paid".to_string()][..]));
+        assert_eq!(column(&meta, "shop.orders", "total").default_value, None);
+    }
+
+    #[test]
+    fn sqlite_schema() {
+        let meta = metadata("sqlite", SQLITE_SCHEMA, None);
+        let customers = &meta.tables["customers"];
+        assert_eq!(customers.indexes, [IndexMetadata {
+            name: "sqlite_autoindex_customers_1".to_string(),
+            columns: vec!["email".to_string()],
+            unique: true,
+        }]);
+        assert_eq!(column(&meta, "customers", "id").auto_increment, Some(true));
+
+        let orders = &meta.tables["orders"];
+        assert_eq!(orders.foreign_keys["customer_id"], "customers.id");
+        assert_eq!(orders.checks, [
+            CheckConstraint { name: None, expression: "quantity > 0".to_string() },
+            CheckConstraint { name: Some("known_customer".to_string()), expression: "customer_id IS NOT NULL".to_string() },
+        ]);
+        assert_eq!(sqlite_check_constraints(SQLITE_SCHEMA).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn postgres_casts_are_lower_case() {
+        assert_eq!(lowercase_casts("nextval('Seq'::REGCLASS)"), "nextval('Seq'::regclass)");
+        assert_eq!(lowercase_casts("'a::B'::CHARACTER VARYING"), "'a::B'::character varying");
+        assert_eq!(lowercase_casts("('now'::TEXT)::DATE"), "('now'::text)::date");
+        assert_eq!(lowercase_casts("x::INT AND TRUE"), "x::int AND TRUE");
+    }
+}
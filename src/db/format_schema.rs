@@ -0,0 +1,109 @@
+// db/format_schema.rs
+// JSON Schemas of the tool's own file formats, generated from the serde models
+
+use super::models::{DataMimicModel, DbMetaDataSchema, FORMAT_VERSION};
+use anyhow::{Result, anyhow};
+use jsonschema::JSONSchema;
+use serde_json::Value;
+
+/// The file formats the tool writes and can validate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Metadata,
+    DataMimic,
+}
+
+impl FileKind {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "metadata" => Ok(Self::Metadata),
+            "datamimic" => Ok(Self::DataMimic),
+            _ => Err(anyhow!("Unsupported file kind: '{}'. Supported kinds: metadata, datamimic", name)),
+        }
+    }
+
+    /// Guesses the kind from the top-level keys of a parsed file.
+    pub fn detect(value: &Value) -> Result<Self> {
+        if value.get("db_metadata").is_some() {
+            Ok(Self::Metadata)
+        } else if value.get("tables").is_some() && value.get("version").is_some() {
+            Ok(Self::DataMimic)
+        } else {
+            Err(anyhow!("Cannot tell whether this is a metadata snapshot or a DATAMIMIC model, pass --kind"))
+        }
+    }
+
+    /// File name the schema is published under in `schemas/`.
+    pub fn schema_file_name(&self) -> String {
+        match self {
+            Self::Metadata => format!("dbmetadata.v{}.schema.json", FORMAT_VERSION),
+            Self::DataMimic => format!("datamimic-model.v{}.schema.json", FORMAT_VERSION),
+        }
+    }
+
+    /// Versioned schema identifier. A URN, since the schemas are shipped with the tool rather
+    /// than hosted anywhere.
+    pub fn schema_id(&self) -> String {
+        match self {
+            Self::Metadata => format!("urn:dbmetaexporter:schema:dbmetadata:v{}", FORMAT_VERSION),
+            Self::DataMimic => format!("urn:dbmetaexporter:schema:datamimic-model:v{}", FORMAT_VERSION),
+        }
+    }
+
+    pub fn schema(&self) -> Value {
+        let root = match self {
+            Self::Metadata => schemars::schema_for!(DbMetaDataSchema),
+            Self::DataMimic => schemars::schema_for!(DataMimicModel),
+        };
+        let mut schema = serde_json::to_value(root).expect("generated schemas serialize");
+        schema["$id"] = Value::from(self.schema_id());
+        schema
+    }
+}
+
+/// Validates a parsed file against the schema of `kind`. Returns one message per violation,
+/// prefixed with the JSON pointer of the offending value.
+pub fn validate(kind: FileKind, value: &Value) -> Result<Vec<String>> {
+    let schema = kind.schema();
+    let compiled = JSONSchema::compile(&schema).map_err(|e| anyhow!("Invalid schema: {}", e))?;
+    let mut problems = match compiled.validate(value) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors
+            .map(|e| {
+                let path = e.instance_path.to_string();
+                format!("{}: {}", if path.is_empty() { "/" } else { path.as_str() }, e)
+            })
+            .collect(),
+    };
+    if let Some(version) = value.get("format_version").and_then(Value::as_u64) {
+        if version > FORMAT_VERSION as u64 {
+            problems.push(format!(
+                "/format_version: written by a newer version of the tool (format {}, this build understands {})",
+                version, FORMAT_VERSION
+            ));
+        }
+    }
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// The files under `schemas/` are published; they must match what the models generate.
+    #[test]
+    fn published_schemas_are_up_to_date() {
+        for (kind, name) in [(FileKind::Metadata, "metadata"), (FileKind::DataMimic, "datamimic")] {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("schemas").join(kind.schema_file_name());
+            let published: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            assert!(
+                published == kind.schema(),
+                "{} is out of date, regenerate it with `DBMetaExporter schema --kind {} --output {}`",
+                path.display(),
+                name,
+                Path::new("schemas").join(kind.schema_file_name()).display()
+            );
+        }
+    }
+}
@@ -1,2 +1,5 @@
 pub mod accessors;
+pub mod ddl_accessor;
+pub mod format_schema;
 pub mod models;
+pub mod types;
@@ -1,17 +1,23 @@
 // Data structures for metadata and DataMimic models
 
 use serde::{Serialize, Deserialize};
+use schemars::JsonSchema;
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+/// Version of the `DbMetaDataSchema` and `DataMimicModel` file formats. Bump it whenever a
+/// field is renamed, removed or changes meaning, and regenerate the files under `schemas/`.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
 pub struct AttributeSpecification {
     pub placeholder: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
 pub struct ColumnMetadata {
     pub name: String,
+    /// SQL type as reported by the database; serialized as `type`.
     #[serde(rename = "type")]
     pub data_type: String,
     pub nullable: bool,
@@ -19,24 +25,124 @@ pub struct ColumnMetadata {
     pub field_length: Option<i64>,
     pub unique: Option<bool>,
     pub spec: Option<AttributeSpecification>,
+    /// Whether the column is selected for data generation; serialized as `isChecked`.
     #[serde(rename = "isChecked")]
     pub is_checked: Option<bool>,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Allowed labels when the column has an enum type.
+    #[serde(default)]
+    pub enum_values: Option<Vec<String>>,
+    /// Identity, serial or AUTO_INCREMENT column.
+    #[serde(default)]
+    pub auto_increment: Option<bool>,
+    #[serde(default)]
+    pub numeric_precision: Option<i64>,
+    #[serde(default)]
+    pub numeric_scale: Option<i64>,
+    /// MySQL numeric column declared UNSIGNED.
+    #[serde(default)]
+    pub unsigned: Option<bool>,
+}
+
+impl ColumnMetadata {
+    /// Name of the column's enum type: the named type on PostgreSQL, `<table>_<column>`
+    /// for inline MySQL enums. None when the column is not an enum.
+    pub fn enum_type_name(&self, table_name: &str) -> Option<String> {
+        self.enum_values.as_ref()?;
+        if self.data_type.eq_ignore_ascii_case("enum") {
+            let table = table_name.rsplit_once('.').map_or(table_name, |(_, t)| t);
+            Some(format!("{}_{}", table, self.name))
+        } else {
+            Some(self.data_type.clone())
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone, PartialEq, Eq)]
+pub struct IndexMetadata {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub unique: bool,
+}
+
+/// A CHECK constraint with its SQL expression as written in the source dialect.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone, PartialEq, Eq)]
+pub struct CheckConstraint {
+    pub name: Option<String>,
+    pub expression: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 pub struct TableMetadata {
     pub columns: Vec<ColumnMetadata>,
     pub primary_keys: Vec<String>,
     pub foreign_keys: HashMap<String, String>,
+    #[serde(default)]
+    pub indexes: Vec<IndexMetadata>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// CHECK constraints; omitted when there are none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<CheckConstraint>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 pub struct DatabaseMetadata {
     pub tables: HashMap<String, TableMetadata>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+impl DatabaseMetadata {
+    /// Table names in sorted order, so exports do not depend on HashMap iteration order.
+    pub fn sorted_table_names(&self) -> Vec<&String> {
+        let mut names: Vec<&String> = self.tables.keys().collect();
+        names.sort();
+        names
+    }
+
+    /// Resolves a foreign key target to the referenced table name and column.
+    /// Targets are `schema.table.column` (PostgreSQL, MySQL) or `table.column` (SQLite).
+    pub fn resolve_foreign_key<'a>(&self, target: &'a str) -> Option<(&'a str, &'a str)> {
+        let (table, column) = target.rsplit_once('.')?;
+        self.tables.contains_key(table).then_some((table, column))
+    }
+
+    /// Incoming references to `table_name` as (referencing table, column, referenced column), sorted.
+    pub fn referenced_by(&self, table_name: &str) -> Vec<(&str, &str, &str)> {
+        let mut refs: Vec<(&str, &str, &str)> = self.tables.iter()
+            .flat_map(|(name, table)| table.foreign_keys.iter().filter_map(move |(col, target)| {
+                let (ref_table, ref_col) = target.rsplit_once('.')?;
+                (ref_table == table_name).then_some((name.as_str(), col.as_str(), ref_col))
+            }))
+            .collect();
+        refs.sort();
+        refs
+    }
+}
+
+impl TableMetadata {
+    /// True when the column alone is a primary key, unique column or single-column unique index.
+    pub fn is_unique_column(&self, column: &str) -> bool {
+        (self.primary_keys.len() == 1 && self.primary_keys[0] == column)
+            || self.columns.iter().any(|c| c.name == column && c.unique == Some(true))
+            || self.indexes.iter().any(|i| i.unique && i.columns.len() == 1 && i.columns[0] == column)
+    }
+
+    /// Foreign keys sorted by column name.
+    pub fn sorted_foreign_keys(&self) -> Vec<(&String, &String)> {
+        let mut fks: Vec<(&String, &String)> = self.foreign_keys.iter().collect();
+        fks.sort();
+        fks
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
 pub struct DbMetaDataSchema {
+    /// `FORMAT_VERSION` of the writer; missing in files written before versioning.
+    #[serde(default)]
+    pub format_version: Option<u32>,
     pub id: Option<i64>,
     pub system_environment_id: i64,
     pub tc_creation_src: Option<String>,
@@ -45,9 +151,12 @@ pub struct DbMetaDataSchema {
     pub tc_update: Option<DateTime<Utc>>,
     pub db_metadata: DatabaseMetadata,
     pub user_config_db_metadata: Option<DatabaseMetadata>,
+    /// `--db-type` the snapshot was extracted from (postgres, mysql, sqlite).
+    #[serde(default)]
+    pub source_database_type: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct DataMimicColumnConfig {
     pub name: String,
     pub generator_type: String,
@@ -55,15 +164,19 @@ pub struct DataMimicColumnConfig {
     pub is_primary_key: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct DataMimicTableConfig {
     pub schema: String,
     pub name: String,
     pub columns: Vec<DataMimicColumnConfig>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 pub struct DataMimicModel {
+    /// `FORMAT_VERSION` of the writer; missing in files written before versioning.
+    #[serde(default)]
+    pub format_version: Option<u32>,
+    /// Version of the tool that generated the model.
     pub version: String,
     pub source_database_type: String,
     pub tables: Vec<DataMimicTableConfig>,
@@ -0,0 +1,124 @@
+// db/types.rs
+// Dialect-independent view of column types, shared by the DATAMIMIC mapping, DDL translation and generators
+
+use super::models::ColumnMetadata;
+
+/// A column type normalized across PostgreSQL, MySQL and SQLite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogicalType {
+    Boolean,
+    SmallInt,
+    Integer,
+    BigInt,
+    Decimal { precision: Option<i64>, scale: Option<i64> },
+    Float,
+    Double,
+    Char(Option<i64>),
+    Varchar(Option<i64>),
+    Text,
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+    Interval,
+    Uuid,
+    Json,
+    Binary,
+    Enum(Vec<String>),
+    /// Array with its element type, when the accessor reported one (`integer[]`).
+    Array(Option<Box<LogicalType>>),
+    /// Anything not recognized, with the original type name.
+    Other(String),
+}
+
+impl LogicalType {
+    /// Classifies a column as reported by the accessor of `db_type`.
+    pub fn from_column(col: &ColumnMetadata, db_type: &str) -> Self {
+        if let Some(values) = &col.enum_values {
+            return Self::Enum(values.clone());
+        }
+        let (base, params) = split_type(&col.data_type);
+        let length = params.first().copied().or(col.field_length);
+        let precision = params.first().copied().or(col.numeric_precision);
+        let scale = params.get(1).copied().or(col.numeric_scale);
+        let db_type = db_type.to_lowercase();
+
+        // The unsigned range of a MySQL integer needs the next wider signed type
+        if col.unsigned == Some(true) {
+            match base.as_str() {
+                "tinyint" if length != Some(1) => return Self::SmallInt,
+                "smallint" => return Self::Integer,
+                "mediumint" | "int" | "integer" => return Self::BigInt,
+                "bigint" => return Self::Decimal { precision: Some(20), scale: Some(0) },
+                _ => {}
+            }
+        }
+        match base.as_str() {
+            "boolean" | "bool" => Self::Boolean,
+            // MySQL has no boolean type, BOOLEAN columns are reported as tinyint(1)
+            "tinyint" if (db_type == "mysql" || db_type == "mariadb") && length == Some(1) => Self::Boolean,
+            "bit" if (db_type == "mysql" || db_type == "mariadb") && precision.unwrap_or(1) == 1 => Self::Boolean,
+            "smallint" | "int2" | "tinyint" | "smallserial" => Self::SmallInt,
+            "integer" | "int" | "int4" | "mediumint" | "serial" => Self::Integer,
+            "bigint" | "int8" | "bigserial" => Self::BigInt,
+            "numeric" | "decimal" => Self::Decimal { precision, scale },
+            "real" | "float4" | "float" => Self::Float,
+            "double precision" | "double" | "float8" => Self::Double,
+            "character" | "char" | "bpchar" | "nchar" => Self::Char(length),
+            "character varying" | "varchar" | "nvarchar" => Self::Varchar(length),
+            "text" | "tinytext" | "mediumtext" | "longtext" | "clob" | "citext" => Self::Text,
+            // MySQL stores SET values as comma-separated member lists
+            "set" if db_type == "mysql" || db_type == "mariadb" => Self::Text,
+            "date" => Self::Date,
+            "time" | "time without time zone" | "time with time zone" | "timetz" => Self::Time,
+            "timestamp" | "timestamp without time zone" | "datetime" => Self::Timestamp,
+            "timestamp with time zone" | "timestamptz" => Self::TimestampTz,
+            "interval" => Self::Interval,
+            "uuid" => Self::Uuid,
+            "json" | "jsonb" => Self::Json,
+            "bytea" | "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => Self::Binary,
+            "array" => Self::Array(None),
+            _ if base.ends_with("[]") => Self::Array(array_element(col, &db_type)),
+            // SQLite accepts any type name; apply its affinity rules to what is left
+            _ if db_type == "sqlite" && base.contains("int") => Self::Integer,
+            _ if db_type == "sqlite" && (base.contains("char") || base.contains("clob") || base.contains("text")) => Self::Text,
+            _ if db_type == "sqlite" && (base.contains("real") || base.contains("floa") || base.contains("doub")) => Self::Double,
+            _ => Self::Other(col.data_type.clone()),
+        }
+    }
+}
+
+/// Element type of a column declared as `<element>[]`.
+fn array_element(col: &ColumnMetadata, db_type: &str) -> Option<Box<LogicalType>> {
+    let element = col.data_type.trim().strip_suffix("[]")?.trim();
+    let element = ColumnMetadata {
+        data_type: element.to_string(),
+        field_length: None,
+        enum_values: None,
+        numeric_precision: None,
+        numeric_scale: None,
+        ..col.clone()
+    };
+    Some(Box::new(LogicalType::from_column(&element, db_type)))
+}
+
+/// Splits `varchar(120)` into `("varchar", [120])`. Type names are lower-cased.
+fn split_type(data_type: &str) -> (String, Vec<i64>) {
+    let lower = data_type.trim().to_lowercase();
+    match lower.split_once('(') {
+        Some((base, rest)) => {
+            let params = rest
+                .split(')')
+                .next()
+                .unwrap_or("")
+                .split(',')
+                .filter_map(|p| p.trim().parse().ok())
+                .collect();
+            // Keep modifiers that follow the parameters, e.g. `timestamp(6) with time zone`
+            let suffix = rest.split_once(')').map(|(_, s)| s.trim()).unwrap_or("");
+            let base = if suffix.is_empty() { base.trim().to_string() } else { format!("{} {}", base.trim(), suffix) };
+            (base, params)
+        }
+        None => (lower, Vec::new()),
+    }
+}
@@ -0,0 +1,59 @@
+// ddl/dialect.rs
+// SQL dialects supported for DDL generation and their syntax differences
+
+use anyhow::{Result, anyhow};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl Dialect {
+    /// Accepts the same names as `--db-type`.
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "mysql" | "mariadb" => Ok(Self::MySql),
+            "sqlite" => Ok(Self::Sqlite),
+            _ => Err(anyhow!("Unsupported dialect: '{}'. Supported dialects: postgres, mysql, sqlite", name)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Postgres => "postgres",
+            Self::MySql => "mysql",
+            Self::Sqlite => "sqlite",
+        }
+    }
+
+    pub fn quote_identifier(&self, identifier: &str) -> String {
+        match self {
+            Self::MySql => format!("`{}`", identifier.replace('`', "``")),
+            _ => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        }
+    }
+
+    /// Quotes a `schema.table` key. Only PostgreSQL keeps the schema: a MySQL schema is the
+    /// database selected by the connection, and SQLite has no schemas.
+    pub fn quote_table(&self, table_key: &str) -> String {
+        match (self, table_key.split_once('.')) {
+            (Self::Postgres, Some((schema, table))) => {
+                format!("{}.{}", self.quote_identifier(schema), self.quote_identifier(table))
+            }
+            (_, Some((_, table))) => self.quote_identifier(table),
+            (_, None) => self.quote_identifier(table_key),
+        }
+    }
+
+    /// SQLite cannot add foreign keys to existing tables, so they always stay inline.
+    pub fn supports_alter_add_foreign_key(&self) -> bool {
+        !matches!(self, Self::Sqlite)
+    }
+}
+
+pub fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
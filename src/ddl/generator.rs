@@ -0,0 +1,424 @@
+// ddl/generator.rs
+// Generates CREATE TABLE / CREATE INDEX / foreign key DDL from a metadata snapshot
+
+use crate::db::models::{ColumnMetadata, DatabaseMetadata, IndexMetadata, TableMetadata};
+use super::dialect::{Dialect, quote_string};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct DdlGenerator {
+    pub dialect: Dialect,
+}
+
+/// A foreign key constraint. Snapshots record one target per column, so the columns of a table
+/// that reference the same composite key are grouped into one constraint.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ForeignKey<'a> {
+    pub columns: Vec<&'a str>,
+    pub ref_table: &'a str,
+    pub ref_columns: Vec<&'a str>,
+}
+
+/// Order in which tables can be created, plus the foreign keys (table, column) that have to
+/// be added after all tables exist because they close a reference cycle.
+pub struct CreationPlan<'a> {
+    pub order: Vec<&'a str>,
+    pub deferred: BTreeSet<(&'a str, &'a str)>,
+}
+
+impl DdlGenerator {
+    /// Generates the DDL of the whole snapshot; foreign keys that cannot be recreated are
+    /// reported in `warnings`.
+    pub fn generate(&self, metadata: &DatabaseMetadata, warnings: &mut Vec<String>) -> String {
+        let plan = creation_plan(metadata);
+        // Blocks are separated by blank lines; empty ones are skipped
+        let mut blocks = vec![format!("-- Generated by DBMetaExporter for {}\n", self.dialect.name())];
+
+        if self.dialect == Dialect::Postgres {
+            let mut preamble = String::new();
+            let schemas: BTreeSet<&str> = plan.order.iter().filter_map(|t| t.split_once('.').map(|(s, _)| s)).collect();
+            for schema in schemas {
+                preamble.push_str(&format!("CREATE SCHEMA IF NOT EXISTS {};\n", self.dialect.quote_identifier(schema)));
+            }
+            for (name, values) in enum_types(metadata) {
+                preamble.push_str(&format!(
+                    "CREATE TYPE {} AS ENUM ({});\n",
+                    self.dialect.quote_identifier(&name),
+                    values.iter().map(|v| quote_string(v)).collect::<Vec<_>>().join(", ")
+                ));
+            }
+            blocks.push(preamble);
+        }
+
+        let mut deferred_keys = Vec::new();
+        for name in &plan.order {
+            let (deferred, inline): (Vec<ForeignKey>, Vec<ForeignKey>) = foreign_keys(metadata, name, warnings)
+                .into_iter()
+                .partition(|fk| plan.deferred.contains(&(*name, fk.columns[0])) && self.dialect.supports_alter_add_foreign_key());
+            blocks.push(self.create_table(name, &metadata.tables[*name], &inline));
+            deferred_keys.extend(deferred.into_iter().map(|fk| (*name, fk)));
+        }
+
+        let mut indexes = String::new();
+        for name in &plan.order {
+            for index in &metadata.tables[*name].indexes {
+                indexes.push_str(&self.create_index(name, index));
+            }
+        }
+        blocks.push(indexes);
+
+        if self.dialect.supports_alter_add_foreign_key() {
+            let mut foreign_keys = String::new();
+            for (name, fk) in &deferred_keys {
+                foreign_keys.push_str(&format!(
+                    "ALTER TABLE {} ADD {};\n",
+                    self.dialect.quote_table(name),
+                    self.foreign_key_constraint(name, fk)
+                ));
+            }
+            blocks.push(foreign_keys);
+        }
+
+        if self.dialect == Dialect::Postgres {
+            blocks.push(self.postgres_comments(metadata, &plan.order));
+        }
+        blocks.retain(|b| !b.is_empty());
+        blocks.join("\n")
+    }
+
+    /// CREATE TABLE with the given foreign keys declared inline.
+    pub fn create_table(&self, name: &str, table: &TableMetadata, foreign_keys: &[ForeignKey]) -> String {
+        let mut lines: Vec<String> = table.columns.iter().map(|col| self.column_definition(name, col)).collect();
+        if !table.primary_keys.is_empty() {
+            lines.push(format!("PRIMARY KEY ({})", self.column_list(&table.primary_keys)));
+        }
+        for fk in foreign_keys {
+            lines.push(self.foreign_key_constraint(name, fk));
+        }
+
+        let mut sql = format!("CREATE TABLE {} (\n    {}\n)", self.dialect.quote_table(name), lines.join(",\n    "));
+        if let (Dialect::MySql, Some(description)) = (self.dialect, &table.description) {
+            sql.push_str(&format!(" COMMENT={}", quote_string(description)));
+        }
+        sql.push_str(";\n");
+        sql
+    }
+
+    pub fn column_definition(&self, table_name: &str, col: &ColumnMetadata) -> String {
+        let mut def = format!("{} {}", self.dialect.quote_identifier(&col.name), self.column_type(table_name, col));
+        let auto_increment = col.auto_increment == Some(true);
+        match (self.dialect, auto_increment) {
+            // Sequences are not part of the snapshot, so serial columns become identities
+            (Dialect::Postgres, true) => def.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+            (Dialect::MySql, true) => def.push_str(" AUTO_INCREMENT"),
+            // SQLite assigns INTEGER PRIMARY KEY values from the rowid without extra syntax
+            _ => {}
+        }
+        if !col.nullable {
+            def.push_str(" NOT NULL");
+        }
+        if let Some(default) = col.default_value.as_deref().filter(|_| !auto_increment) {
+            def.push_str(&format!(" DEFAULT {}", default));
+        }
+        if col.unique == Some(true) && !col.primary_key {
+            def.push_str(" UNIQUE");
+        }
+        if let (Dialect::Sqlite, Some(values)) = (self.dialect, &col.enum_values) {
+            def.push_str(&format!(
+                " CHECK ({} IN ({}))",
+                self.dialect.quote_identifier(&col.name),
+                values.iter().map(|v| quote_string(v)).collect::<Vec<_>>().join(", ")
+            ));
+        }
+        if let (Dialect::MySql, Some(description)) = (self.dialect, &col.description) {
+            def.push_str(&format!(" COMMENT {}", quote_string(description)));
+        }
+        def
+    }
+
+    pub fn column_type(&self, table_name: &str, col: &ColumnMetadata) -> String {
+        if let Some(values) = &col.enum_values {
+            return match self.dialect {
+                Dialect::Postgres => self.dialect.quote_identifier(&col.enum_type_name(table_name).unwrap_or_default()),
+                Dialect::MySql => format!("ENUM({})", values.iter().map(|v| quote_string(v)).collect::<Vec<_>>().join(", ")),
+                Dialect::Sqlite => "TEXT".to_string(),
+            };
+        }
+        let unsigned = if self.dialect == Dialect::MySql && col.unsigned == Some(true) { " unsigned" } else { "" };
+        if col.data_type.contains('(') {
+            return format!("{}{}", col.data_type, unsigned);
+        }
+        let base = col.data_type.to_lowercase();
+        let rendered = match (col.field_length, col.numeric_precision) {
+            // Text and blob lengths are the storage limits the server reports, not declared lengths
+            (Some(length), _) if !base.ends_with("text") && !base.ends_with("blob") => format!("{}({})", col.data_type, length),
+            (_, Some(precision)) if matches!(base.as_str(), "numeric" | "decimal") => {
+                format!("{}({},{})", col.data_type, precision, col.numeric_scale.unwrap_or(0))
+            }
+            // MySQL reports the width of bit columns as their precision
+            (_, Some(precision)) if base == "bit" && self.dialect == Dialect::MySql => format!("{}({})", col.data_type, precision),
+            _ => col.data_type.clone(),
+        };
+        format!("{}{}", rendered, unsigned)
+    }
+
+    pub fn create_index(&self, table_name: &str, index: &IndexMetadata) -> String {
+        format!(
+            "CREATE {}INDEX {} ON {} ({});\n",
+            if index.unique { "UNIQUE " } else { "" },
+            self.dialect.quote_identifier(&index_name(table_name, index)),
+            self.dialect.quote_table(table_name),
+            self.column_list(&index.columns)
+        )
+    }
+
+    pub fn foreign_key_constraint(&self, table_name: &str, fk: &ForeignKey) -> String {
+        format!(
+            "CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({})",
+            self.dialect.quote_identifier(&foreign_key_name(table_name, &fk.columns.join("_"))),
+            self.column_list(&fk.columns),
+            self.dialect.quote_table(fk.ref_table),
+            self.column_list(&fk.ref_columns)
+        )
+    }
+
+    fn column_list<S: AsRef<str>>(&self, columns: &[S]) -> String {
+        columns.iter().map(|c| self.dialect.quote_identifier(c.as_ref())).collect::<Vec<_>>().join(", ")
+    }
+
+    fn postgres_comments(&self, metadata: &DatabaseMetadata, order: &[&str]) -> String {
+        let mut sql = String::new();
+        for name in order {
+            let table = &metadata.tables[*name];
+            if let Some(description) = &table.description {
+                sql.push_str(&format!("COMMENT ON TABLE {} IS {};\n", self.dialect.quote_table(name), quote_string(description)));
+            }
+            for col in &table.columns {
+                if let Some(description) = &col.description {
+                    sql.push_str(&format!(
+                        "COMMENT ON COLUMN {}.{} IS {};\n",
+                        self.dialect.quote_table(name),
+                        self.dialect.quote_identifier(&col.name),
+                        quote_string(description)
+                    ));
+                }
+            }
+        }
+        sql
+    }
+}
+
+/// Orders tables so that referenced tables come first. Ties are broken alphabetically; when
+/// only tables in a reference cycle remain, the first one is created and its foreign keys to
+/// tables that do not exist yet are deferred. References to tables outside the snapshot are
+/// ignored, `foreign_keys` skips them.
+pub fn creation_plan(metadata: &DatabaseMetadata) -> CreationPlan<'_> {
+    let mut remaining: BTreeSet<&str> = metadata.tables.keys().map(|k| k.as_str()).collect();
+    let mut created: BTreeSet<&str> = BTreeSet::new();
+    let mut order = Vec::new();
+    let mut deferred = BTreeSet::new();
+
+    while let Some(&first) = remaining.iter().next() {
+        let ready = remaining.iter().copied().find(|name| {
+            foreign_key_parents(metadata, name).iter().all(|(_, parent)| parent == name || created.contains(parent))
+        });
+        let next = ready.unwrap_or(first);
+        for (column, parent) in foreign_key_parents(metadata, next) {
+            if parent != next && !created.contains(parent) {
+                deferred.insert((next, column));
+            }
+        }
+        remaining.remove(next);
+        created.insert(next);
+        order.push(next);
+    }
+    CreationPlan { order, deferred }
+}
+
+/// (column, referenced table) for each foreign key to a table in the snapshot.
+fn foreign_key_parents<'a>(metadata: &'a DatabaseMetadata, name: &str) -> Vec<(&'a str, &'a str)> {
+    metadata.tables[name].foreign_keys.iter()
+        .filter_map(|(column, target)| Some((column.as_str(), metadata.resolve_foreign_key(target)?.0)))
+        .collect()
+}
+
+/// Foreign key constraints of a table, sorted by column. Columns referencing a primary key or
+/// unique column get a constraint each. The others are grouped by referenced table into one
+/// constraint over the composite primary key or unique index they reference; groups that do not
+/// match such a key, and references to tables outside the snapshot, are skipped with a warning.
+pub fn foreign_keys<'a>(metadata: &'a DatabaseMetadata, name: &str, warnings: &mut Vec<String>) -> Vec<ForeignKey<'a>> {
+    let mut keys = Vec::new();
+    let mut composite: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+    for (column, target) in metadata.tables[name].sorted_foreign_keys() {
+        match metadata.resolve_foreign_key(target) {
+            Some((ref_table, ref_column)) if !metadata.tables[ref_table].is_unique_column(ref_column) => {
+                composite.entry(ref_table).or_default().push((column, ref_column));
+            }
+            Some((ref_table, ref_column)) => {
+                keys.push(ForeignKey { columns: vec![column], ref_table, ref_columns: vec![ref_column] });
+            }
+            // The referenced table would not exist when the constraint is created
+            None => warnings.push(format!("{}: foreign key ({}) skipped, {} is not in the snapshot", name, column, target)),
+        }
+    }
+
+    for (ref_table, mut pairs) in composite {
+        let target = &metadata.tables[ref_table];
+        let referenced: BTreeSet<&str> = pairs.iter().map(|(_, c)| *c).collect();
+        let key = std::iter::once(&target.primary_keys)
+            .chain(target.indexes.iter().filter(|i| i.unique).map(|i| &i.columns))
+            .find(|key| key.len() == pairs.len() && key.iter().map(String::as_str).collect::<BTreeSet<_>>() == referenced);
+        match key {
+            Some(key) => {
+                // Columns follow the order of the referenced key
+                pairs.sort_by_key(|(_, c)| key.iter().position(|k| k == c));
+                keys.push(ForeignKey {
+                    columns: pairs.iter().map(|(c, _)| *c).collect(),
+                    ref_table,
+                    ref_columns: pairs.iter().map(|(_, c)| *c).collect(),
+                });
+            }
+            None => warnings.push(format!(
+                "{}: foreign key ({}) skipped, {} ({}) is not a primary key or unique index of that table",
+                name,
+                pairs.iter().map(|(c, _)| *c).collect::<Vec<_>>().join(", "),
+                ref_table,
+                pairs.iter().map(|(_, c)| *c).collect::<Vec<_>>().join(", ")
+            )),
+        }
+    }
+    keys.sort_by(|a, b| a.columns.cmp(&b.columns));
+    keys
+}
+
+/// PostgreSQL enum types used by the columns, by type name.
+pub fn enum_types(metadata: &DatabaseMetadata) -> BTreeMap<String, &Vec<String>> {
+    let mut enums = BTreeMap::new();
+    for (name, table) in &metadata.tables {
+        for col in &table.columns {
+            if let (Some(enum_name), Some(values)) = (col.enum_type_name(name), &col.enum_values) {
+                enums.insert(enum_name, values);
+            }
+        }
+    }
+    enums
+}
+
+/// Constraint name used for foreign keys; snapshots do not record the original names.
+pub fn foreign_key_name(table_name: &str, column: &str) -> String {
+    format!("fk_{}_{}", bare_table_name(table_name), column)
+}
+
+/// SQLite reserves the `sqlite_` prefix for its automatic indexes, so those get a new name.
+pub fn index_name(table_name: &str, index: &IndexMetadata) -> String {
+    if index.name.starts_with("sqlite_") {
+        format!("{}_{}_{}", bare_table_name(table_name), index.columns.join("_"), if index.unique { "key" } else { "idx" })
+    } else {
+        index.name.clone()
+    }
+}
+
+pub fn bare_table_name(table_key: &str) -> &str {
+    table_key.rsplit_once('.').map_or(table_key, |(_, t)| t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata(value: serde_json::Value) -> DatabaseMetadata {
+        serde_json::from_value(json!({ "tables": value })).unwrap()
+    }
+
+    fn column(name: &str, data_type: &str) -> serde_json::Value {
+        json!({ "name": name, "type": data_type, "nullable": false, "primary_key": false, "field_length": null, "unique": null, "spec": null, "isChecked": null })
+    }
+
+    fn orders_with_items(items_foreign_keys: serde_json::Value) -> DatabaseMetadata {
+        metadata(json!({
+            "public.orders": {
+                "columns": [column("region", "text"), column("number", "integer"), column("note", "text")],
+                "primary_keys": ["region", "number"],
+                "foreign_keys": {},
+            },
+            "public.items": {
+                "columns": [column("order_number", "integer"), column("order_region", "text"), column("note", "text")],
+                "primary_keys": [],
+                "foreign_keys": items_foreign_keys,
+            },
+        }))
+    }
+
+    #[test]
+    fn columns_referencing_a_composite_key_become_one_constraint() {
+        let metadata = orders_with_items(json!({
+            "order_number": "public.orders.number",
+            "order_region": "public.orders.region",
+        }));
+        let mut warnings = Vec::new();
+        let keys = foreign_keys(&metadata, "public.items", &mut warnings);
+        assert_eq!(keys, vec![ForeignKey {
+            columns: vec!["order_region", "order_number"],
+            ref_table: "public.orders",
+            ref_columns: vec!["region", "number"],
+        }]);
+        assert!(warnings.is_empty());
+
+        let sql = DdlGenerator { dialect: Dialect::Postgres }.generate(&metadata, &mut warnings);
+        assert!(sql.contains(
+            "CONSTRAINT \"fk_items_order_region_order_number\" FOREIGN KEY (\"order_region\", \"order_number\") \
+             REFERENCES \"public\".\"orders\" (\"region\", \"number\")"
+        ), "{}", sql);
+    }
+
+    #[test]
+    fn columns_not_matching_a_key_are_skipped_with_a_warning() {
+        let metadata = orders_with_items(json!({
+            "order_number": "public.orders.number",
+            "note": "public.orders.note",
+        }));
+        let mut warnings = Vec::new();
+        let sql = DdlGenerator { dialect: Dialect::Postgres }.generate(&metadata, &mut warnings);
+        assert!(!sql.contains("FOREIGN KEY"), "{}", sql);
+        assert_eq!(warnings, vec![
+            "public.items: foreign key (note, order_number) skipped, public.orders (note, number) is not a primary key or unique index of that table"
+        ]);
+    }
+
+    #[test]
+    fn single_column_keys_stay_separate() {
+        let metadata = metadata(json!({
+            "users": {
+                "columns": [column("id", "INTEGER")],
+                "primary_keys": ["id"],
+                "foreign_keys": {},
+            },
+            "posts": {
+                "columns": [column("author", "INTEGER"), column("editor", "INTEGER")],
+                "primary_keys": [],
+                "foreign_keys": { "author": "users.id", "editor": "users.id", "tag": "tags.id" },
+            },
+        }));
+        let mut warnings = Vec::new();
+        let keys = foreign_keys(&metadata, "posts", &mut warnings);
+        let columns: Vec<_> = keys.iter().map(|fk| (fk.columns.clone(), fk.ref_table)).collect();
+        assert_eq!(columns, vec![(vec!["author"], "users"), (vec!["editor"], "users")]);
+        assert_eq!(warnings, ["posts: foreign key (tag) skipped, tags.id is not in the snapshot"]);
+    }
+
+    #[test]
+    fn references_outside_the_snapshot_are_not_deferred() {
+        let metadata = metadata(json!({
+            "posts": {
+                "columns": [column("id", "INTEGER"), column("tag", "INTEGER")],
+                "primary_keys": ["id"],
+                "foreign_keys": { "tag": "tags.id" },
+            },
+        }));
+        assert!(creation_plan(&metadata).deferred.is_empty());
+        let mut warnings = Vec::new();
+        let sql = DdlGenerator { dialect: Dialect::Postgres }.generate(&metadata, &mut warnings);
+        assert!(!sql.contains("FOREIGN KEY"), "{}", sql);
+        assert_eq!(warnings, ["posts: foreign key (tag) skipped, tags.id is not in the snapshot"]);
+    }
+}
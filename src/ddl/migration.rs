@@ -0,0 +1,453 @@
+// ddl/migration.rs
+// Generates ALTER-based migration scripts between two metadata snapshots
+
+use crate::db::models::{ColumnMetadata, DatabaseMetadata, IndexMetadata, TableMetadata};
+use crate::diff::schema_diff::{TableDiff, diff_metadata};
+use super::dialect::{Dialect, quote_string};
+use super::generator::{DdlGenerator, ForeignKey, bare_table_name, creation_plan, enum_types, foreign_key_name, foreign_keys, index_name};
+
+pub struct MigrationGenerator {
+    pub dialect: Dialect,
+}
+
+/// Migration script plus warnings about lost data and assumed constraint names.
+pub struct Migration {
+    pub sql: String,
+    pub warnings: Vec<String>,
+}
+
+impl MigrationGenerator {
+    /// Generates the statements turning `old` into `new`. Both snapshots must already be
+    /// translated to the target dialect. A backward migration is `generate(new, old)`.
+    pub fn generate(&self, old: &DatabaseMetadata, new: &DatabaseMetadata) -> Migration {
+        let diff = diff_metadata(old, new);
+        let ddl = DdlGenerator { dialect: self.dialect };
+        let mut warnings = Vec::new();
+        let mut blocks = vec![format!("-- Migration generated by DBMetaExporter for {}\n", self.dialect.name())];
+        if diff.is_empty() {
+            blocks.push("-- No changes\n".to_string());
+            return Migration { sql: blocks.join("\n"), warnings };
+        }
+
+        // SQLite can only add columns in place; any other table change rebuilds the table
+        let rebuilt: Vec<&TableDiff> = diff.changed_tables.iter()
+            .filter(|t| self.dialect == Dialect::Sqlite && needs_rebuild(t, &new.tables[&t.table]))
+            .collect();
+        let altered: Vec<&TableDiff> = diff.changed_tables.iter()
+            .filter(|t| !rebuilt.iter().any(|r| r.table == t.table))
+            .collect();
+
+        let mut drops = String::new();
+        for table in &altered {
+            let columns: Vec<&str> = table.removed_foreign_keys.iter().map(|fk| fk.column.as_str()).collect();
+            for fk in touched_foreign_keys(old, &table.table, &columns) {
+                let name = foreign_key_name(&table.table, &fk.columns.join("_"));
+                warnings.push(format!("{}.{}: snapshots do not record constraint names, assuming {}", table.table, fk.columns.join(", "), name));
+                let clause = match self.dialect {
+                    Dialect::MySql => "DROP FOREIGN KEY",
+                    _ => "DROP CONSTRAINT",
+                };
+                drops.push_str(&self.alter(&table.table, &format!("{} {}", clause, self.dialect.quote_identifier(&name))));
+            }
+            for index in table.removed_indexes.iter().chain(table.changed_indexes.iter().map(|c| &c.old)) {
+                drops.push_str(&self.drop_index(&table.table, index));
+            }
+        }
+        let old_plan = creation_plan(old);
+        for name in old_plan.order.iter().rev().filter(|n| diff.removed_tables.iter().any(|r| r == *n)) {
+            warnings.push(format!("{}: table dropped, its data is lost", name));
+            drops.push_str(&format!("DROP TABLE {};\n", self.dialect.quote_table(name)));
+        }
+        blocks.push(drops);
+
+        if self.dialect == Dialect::Postgres {
+            blocks.push(self.postgres_enum_changes(old, new, &mut warnings));
+        }
+
+        // New tables are created without foreign keys, which are added once all tables exist
+        let new_plan = creation_plan(new);
+        let added: Vec<&str> = new_plan.order.iter().copied().filter(|n| diff.added_tables.iter().any(|a| a == n)).collect();
+        let mut deferred = Vec::new();
+        let mut creates = String::new();
+        for name in &added {
+            let keys = foreign_keys(new, name, &mut warnings);
+            if self.dialect.supports_alter_add_foreign_key() {
+                creates.push_str(&ddl.create_table(name, &new.tables[*name], &[]));
+                deferred.extend(keys.into_iter().map(|fk| (*name, fk)));
+            } else {
+                creates.push_str(&ddl.create_table(name, &new.tables[*name], &keys));
+            }
+        }
+        blocks.push(creates);
+
+        for table in &altered {
+            blocks.push(self.alter_table(&ddl, table, &new.tables[&table.table], &mut warnings));
+        }
+        for table in &rebuilt {
+            blocks.push(self.rebuild_table(&ddl, table, &old.tables[&table.table], new, &mut warnings));
+        }
+
+        let mut indexes = String::new();
+        for name in &added {
+            for index in &new.tables[*name].indexes {
+                indexes.push_str(&ddl.create_index(name, index));
+            }
+        }
+        for table in &altered {
+            for index in table.added_indexes.iter().chain(table.changed_indexes.iter().map(|c| &c.new)) {
+                indexes.push_str(&ddl.create_index(&table.table, index));
+            }
+        }
+        blocks.push(indexes);
+
+        if self.dialect.supports_alter_add_foreign_key() {
+            let mut foreign_keys = String::new();
+            let added_fks = altered.iter().flat_map(|t| {
+                let columns: Vec<&str> = t.added_foreign_keys.iter().map(|fk| fk.column.as_str()).collect();
+                touched_foreign_keys(new, &t.table, &columns).into_iter().map(move |fk| (t.table.as_str(), fk))
+            });
+            for (name, fk) in deferred.into_iter().chain(added_fks) {
+                foreign_keys.push_str(&self.alter(name, &format!("ADD {}", ddl.foreign_key_constraint(name, &fk))));
+            }
+            blocks.push(foreign_keys);
+        }
+
+        if !rebuilt.is_empty() {
+            // Rebuilding drops tables that others may reference, so checks are suspended until the end
+            blocks.insert(1, "PRAGMA foreign_keys = OFF;\nBEGIN TRANSACTION;\n".to_string());
+            blocks.push("PRAGMA foreign_key_check;\nCOMMIT;\nPRAGMA foreign_keys = ON;\n".to_string());
+        }
+        blocks.retain(|b| !b.is_empty());
+        Migration { sql: blocks.join("\n"), warnings }
+    }
+
+    fn alter_table(&self, ddl: &DdlGenerator, diff: &TableDiff, new: &TableMetadata, warnings: &mut Vec<String>) -> String {
+        let name = diff.table.as_str();
+        let mut sql = String::new();
+
+        if let Some(change) = diff.primary_key.as_ref().filter(|c| !c.old.is_empty()) {
+            sql.push_str(&match self.dialect {
+                Dialect::MySql => self.alter(name, "DROP PRIMARY KEY"),
+                _ => {
+                    // PostgreSQL names unnamed primary keys <table>_pkey
+                    let constraint = format!("{}_pkey", bare_table_name(name));
+                    warnings.push(format!("{}: dropping primary key ({}) assumes constraint name {}", name, change.old.join(", "), constraint));
+                    self.alter(name, &format!("DROP CONSTRAINT {}", self.dialect.quote_identifier(&constraint)))
+                }
+            });
+        }
+        for column in &diff.added_columns {
+            let col = new.columns.iter().find(|c| &c.name == column).unwrap();
+            if !col.nullable && col.default_value.is_none() && col.auto_increment != Some(true) {
+                warnings.push(format!("{}.{}: NOT NULL column without a default cannot be added to a non-empty table", name, column));
+            }
+            sql.push_str(&self.alter(name, &format!("ADD COLUMN {}", ddl.column_definition(name, col))));
+        }
+        for column in &diff.changed_columns {
+            let col = new.columns.iter().find(|c| c.name == column.column).unwrap();
+            let changed = |attributes: &[&str]| column.changes.iter().any(|c| attributes.contains(&c.attribute.as_str()));
+            let quoted = self.dialect.quote_identifier(&col.name);
+            if self.dialect == Dialect::MySql {
+                if column.changes.iter().any(|c| c.attribute != "unique") {
+                    // MODIFY restates the whole definition, except UNIQUE which would add another index
+                    let restated = ColumnMetadata { unique: None, ..col.clone() };
+                    sql.push_str(&self.alter(name, &format!("MODIFY COLUMN {}", ddl.column_definition(name, &restated))));
+                }
+            } else {
+                if changed(&["type", "length", "precision", "scale"]) {
+                    let column_type = ddl.column_type(name, col);
+                    sql.push_str(&self.alter(name, &format!("ALTER COLUMN {} TYPE {} USING {}::{}", quoted, column_type, quoted, column_type)));
+                }
+                if changed(&["nullable"]) {
+                    let action = if col.nullable { "DROP NOT NULL" } else { "SET NOT NULL" };
+                    sql.push_str(&self.alter(name, &format!("ALTER COLUMN {} {}", quoted, action)));
+                }
+                if changed(&["default"]) {
+                    let action = match &col.default_value {
+                        Some(default) => format!("SET DEFAULT {}", default),
+                        None => "DROP DEFAULT".to_string(),
+                    };
+                    sql.push_str(&self.alter(name, &format!("ALTER COLUMN {} {}", quoted, action)));
+                }
+            }
+            if changed(&["unique"]) {
+                // Unnamed unique constraints are named <table>_<column>_key by PostgreSQL and after
+                // the column by MySQL
+                let constraint = match self.dialect {
+                    Dialect::MySql => col.name.clone(),
+                    _ => format!("{}_{}_key", bare_table_name(name), col.name),
+                };
+                let quoted_constraint = self.dialect.quote_identifier(&constraint);
+                let clause = match (self.dialect, col.unique == Some(true)) {
+                    (Dialect::MySql, true) => format!("ADD UNIQUE INDEX {} ({})", quoted_constraint, quoted),
+                    (_, true) => format!("ADD CONSTRAINT {} UNIQUE ({})", quoted_constraint, quoted),
+                    (Dialect::MySql, false) => format!("DROP INDEX {}", quoted_constraint),
+                    (_, false) => format!("DROP CONSTRAINT {}", quoted_constraint),
+                };
+                if col.unique != Some(true) {
+                    warnings.push(format!("{}.{}: dropping the unique constraint assumes constraint name {}", name, col.name, constraint));
+                }
+                sql.push_str(&self.alter(name, &clause));
+            }
+        }
+        for column in &diff.removed_columns {
+            warnings.push(format!("{}.{}: column dropped, its data is lost", name, column));
+            sql.push_str(&self.alter(name, &format!("DROP COLUMN {}", self.dialect.quote_identifier(column))));
+        }
+        if diff.primary_key.is_some() && !new.primary_keys.is_empty() {
+            let columns = new.primary_keys.iter().map(|c| self.dialect.quote_identifier(c)).collect::<Vec<_>>().join(", ");
+            sql.push_str(&self.alter(name, &format!("ADD PRIMARY KEY ({})", columns)));
+        }
+        sql
+    }
+
+    /// The SQLite rebuild: create the new definition under a temporary name, copy the shared
+    /// columns, drop the old table, rename and recreate the indexes.
+    fn rebuild_table(&self, ddl: &DdlGenerator, diff: &TableDiff, old: &TableMetadata, new_metadata: &DatabaseMetadata, warnings: &mut Vec<String>) -> String {
+        let name = diff.table.as_str();
+        let new = &new_metadata.tables[name];
+        let temporary = format!("{}_new", name);
+        let shared: Vec<String> = new.columns.iter()
+            .filter(|c| old.columns.iter().any(|o| o.name == c.name))
+            .map(|c| self.dialect.quote_identifier(&c.name))
+            .collect();
+        for column in &diff.removed_columns {
+            warnings.push(format!("{}.{}: column dropped, its data is lost", name, column));
+        }
+        for col in new.columns.iter().filter(|c| diff.added_columns.contains(&c.name)) {
+            if !col.nullable && col.default_value.is_none() && col.auto_increment != Some(true) {
+                warnings.push(format!("{}.{}: NOT NULL column without a default, copying existing rows will fail", name, col.name));
+            }
+        }
+
+        let mut sql = format!("-- Rebuild {}\n", name);
+        // Generated under the real name so that constraint names stay the same after the rename
+        let keys = foreign_keys(new_metadata, name, warnings);
+        let create = ddl.create_table(name, new, &keys).replacen(
+            &format!("CREATE TABLE {} (", self.dialect.quote_table(name)),
+            &format!("CREATE TABLE {} (", self.dialect.quote_table(&temporary)),
+            1,
+        );
+        sql.push_str(&create);
+        sql.push_str(&format!(
+            "INSERT INTO {} ({}) SELECT {} FROM {};\n",
+            self.dialect.quote_table(&temporary),
+            shared.join(", "),
+            shared.join(", "),
+            self.dialect.quote_table(name)
+        ));
+        sql.push_str(&format!("DROP TABLE {};\n", self.dialect.quote_table(name)));
+        sql.push_str(&format!("ALTER TABLE {} RENAME TO {};\n", self.dialect.quote_table(&temporary), self.dialect.quote_table(name)));
+        for index in &new.indexes {
+            sql.push_str(&ddl.create_index(name, index));
+        }
+        sql
+    }
+
+    /// Creates new enum types and adds new labels; PostgreSQL cannot remove labels.
+    fn postgres_enum_changes(&self, old: &DatabaseMetadata, new: &DatabaseMetadata, warnings: &mut Vec<String>) -> String {
+        let old_enums = enum_types(old);
+        let mut sql = String::new();
+        for (name, values) in enum_types(new) {
+            let type_name = self.dialect.quote_identifier(&name);
+            match old_enums.get(&name) {
+                None => sql.push_str(&format!(
+                    "CREATE TYPE {} AS ENUM ({});\n",
+                    type_name,
+                    values.iter().map(|v| quote_string(v)).collect::<Vec<_>>().join(", ")
+                )),
+                Some(old_values) => {
+                    for value in values.iter().filter(|v| !old_values.contains(v)) {
+                        sql.push_str(&format!("ALTER TYPE {} ADD VALUE IF NOT EXISTS {};\n", type_name, quote_string(value)));
+                    }
+                    for value in old_values.iter().filter(|v| !values.contains(v)) {
+                        warnings.push(format!("{}: enum label {} cannot be removed in place", name, value));
+                    }
+                }
+            }
+        }
+        sql
+    }
+
+    fn alter(&self, table: &str, clause: &str) -> String {
+        format!("ALTER TABLE {} {};\n", self.dialect.quote_table(table), clause)
+    }
+
+    fn drop_index(&self, table: &str, index: &IndexMetadata) -> String {
+        let name = self.dialect.quote_identifier(&index_name(table, index));
+        match (self.dialect, table.split_once('.')) {
+            (Dialect::MySql, _) => format!("DROP INDEX {} ON {};\n", name, self.dialect.quote_table(table)),
+            // PostgreSQL indexes live in the schema of their table
+            (Dialect::Postgres, Some((schema, _))) => format!("DROP INDEX {}.{};\n", self.dialect.quote_identifier(schema), name),
+            _ => format!("DROP INDEX {};\n", name),
+        }
+    }
+}
+
+/// Foreign key constraints of `table` that include any of `columns`. Constraints that cannot be
+/// recreated were reported when the table was created, so their warnings are not repeated.
+fn touched_foreign_keys<'a>(metadata: &'a DatabaseMetadata, table: &str, columns: &[&str]) -> Vec<ForeignKey<'a>> {
+    if columns.is_empty() {
+        return Vec::new();
+    }
+    foreign_keys(metadata, table, &mut Vec::new()).into_iter()
+        .filter(|fk| fk.columns.iter().any(|c| columns.contains(c)))
+        .collect()
+}
+
+/// Whether a SQLite table change needs a rebuild. `ALTER TABLE ADD COLUMN` only accepts
+/// columns that are not keys or unique and have a constant default when NOT NULL.
+fn needs_rebuild(diff: &TableDiff, new: &TableMetadata) -> bool {
+    let simple_add = new.columns.iter().filter(|c| diff.added_columns.contains(&c.name)).all(|c| {
+        !c.primary_key
+            && c.unique != Some(true)
+            && (c.nullable || c.default_value.as_deref().is_some_and(|d| !d.contains('(') && !d.to_uppercase().starts_with("CURRENT_")))
+    });
+    let automatic_index = diff.removed_indexes.iter().chain(diff.changed_indexes.iter().map(|c| &c.old))
+        .any(|i| i.name.starts_with("sqlite_"));
+    !simple_add
+        || automatic_index
+        || !diff.removed_columns.is_empty()
+        || !diff.changed_columns.is_empty()
+        || diff.primary_key.is_some()
+        || !diff.added_foreign_keys.is_empty()
+        || !diff.removed_foreign_keys.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> serde_json::Value {
+        json!({ "name": name, "type": data_type, "nullable": nullable, "primary_key": false, "field_length": null, "unique": null, "spec": null, "isChecked": true })
+    }
+
+    /// `users` before and after widening and constraining `email`, adding `name` and a new `posts` table.
+    fn snapshots(prefix: &str) -> (DatabaseMetadata, DatabaseMetadata) {
+        let users = format!("{}users", prefix);
+        let posts = format!("{}posts", prefix);
+        let mut id = column("id", "integer", false);
+        id["primary_key"] = json!(true);
+        let mut email = column("email", "varchar", true);
+        email["field_length"] = json!(120);
+        let old = json!({ "tables": {
+            users.clone(): { "columns": [id.clone(), email.clone()], "primary_keys": ["id"], "foreign_keys": {} },
+        }});
+
+        email["field_length"] = json!(200);
+        email["nullable"] = json!(false);
+        email["unique"] = json!(true);
+        let new = json!({ "tables": {
+            users.clone(): { "columns": [id.clone(), email, column("name", "text", true)], "primary_keys": ["id"], "foreign_keys": {} },
+            posts: {
+                "columns": [id, column("author_id", "integer", true)],
+                "primary_keys": ["id"],
+                "foreign_keys": { "author_id": format!("{}.id", users) },
+            },
+        }});
+        (serde_json::from_value(old).unwrap(), serde_json::from_value(new).unwrap())
+    }
+
+    fn statements(migration: &Migration) -> Vec<&str> {
+        migration.sql.lines().filter(|l| !l.is_empty() && !l.starts_with("--")).collect()
+    }
+
+    #[test]
+    fn postgres_forward_and_backward() {
+        let (old, new) = snapshots("public.");
+        let generator = MigrationGenerator { dialect: Dialect::Postgres };
+        let forward = generator.generate(&old, &new);
+        assert_eq!(statements(&forward), vec![
+            "CREATE TABLE \"public\".\"posts\" (",
+            "    \"id\" integer NOT NULL,",
+            "    \"author_id\" integer,",
+            "    PRIMARY KEY (\"id\")",
+            ");",
+            "ALTER TABLE \"public\".\"users\" ADD COLUMN \"name\" text;",
+            "ALTER TABLE \"public\".\"users\" ALTER COLUMN \"email\" TYPE varchar(200) USING \"email\"::varchar(200);",
+            "ALTER TABLE \"public\".\"users\" ALTER COLUMN \"email\" SET NOT NULL;",
+            "ALTER TABLE \"public\".\"users\" ADD CONSTRAINT \"users_email_key\" UNIQUE (\"email\");",
+            "ALTER TABLE \"public\".\"posts\" ADD CONSTRAINT \"fk_posts_author_id\" FOREIGN KEY (\"author_id\") REFERENCES \"public\".\"users\" (\"id\");",
+        ]);
+
+        let backward = generator.generate(&new, &old);
+        assert_eq!(statements(&backward), vec![
+            "DROP TABLE \"public\".\"posts\";",
+            "ALTER TABLE \"public\".\"users\" ALTER COLUMN \"email\" TYPE varchar(120) USING \"email\"::varchar(120);",
+            "ALTER TABLE \"public\".\"users\" ALTER COLUMN \"email\" DROP NOT NULL;",
+            "ALTER TABLE \"public\".\"users\" DROP CONSTRAINT \"users_email_key\";",
+            "ALTER TABLE \"public\".\"users\" DROP COLUMN \"name\";",
+        ]);
+        assert!(backward.warnings.contains(&"public.posts: table dropped, its data is lost".to_string()));
+    }
+
+    #[test]
+    fn mysql_modify_does_not_restate_unique() {
+        let (old, new) = snapshots("app.");
+        let generator = MigrationGenerator { dialect: Dialect::MySql };
+        let forward = generator.generate(&old, &new);
+        assert_eq!(statements(&forward), vec![
+            "CREATE TABLE `posts` (",
+            "    `id` integer NOT NULL,",
+            "    `author_id` integer,",
+            "    PRIMARY KEY (`id`)",
+            ");",
+            "ALTER TABLE `users` ADD COLUMN `name` text;",
+            "ALTER TABLE `users` MODIFY COLUMN `email` varchar(200) NOT NULL;",
+            "ALTER TABLE `users` ADD UNIQUE INDEX `email` (`email`);",
+            "ALTER TABLE `posts` ADD CONSTRAINT `fk_posts_author_id` FOREIGN KEY (`author_id`) REFERENCES `users` (`id`);",
+        ]);
+
+        let backward = generator.generate(&new, &old);
+        assert_eq!(statements(&backward), vec![
+            "DROP TABLE `posts`;",
+            "ALTER TABLE `users` MODIFY COLUMN `email` varchar(120);",
+            "ALTER TABLE `users` DROP INDEX `email`;",
+            "ALTER TABLE `users` DROP COLUMN `name`;",
+        ]);
+    }
+
+    #[test]
+    fn mysql_unique_only_change_skips_modify() {
+        let (_, new) = snapshots("app.");
+        let mut unique_dropped = new.clone();
+        unique_dropped.tables.get_mut("app.users").unwrap().columns[1].unique = None;
+        let migration = MigrationGenerator { dialect: Dialect::MySql }.generate(&new, &unique_dropped);
+        assert_eq!(statements(&migration), vec!["ALTER TABLE `users` DROP INDEX `email`;"]);
+        assert_eq!(migration.warnings, vec!["app.users.email: dropping the unique constraint assumes constraint name email"]);
+    }
+
+    #[test]
+    fn sqlite_rebuilds_changed_tables() {
+        let (old, new) = snapshots("");
+        let generator = MigrationGenerator { dialect: Dialect::Sqlite };
+        let forward = generator.generate(&old, &new);
+        assert_eq!(statements(&forward), vec![
+            "PRAGMA foreign_keys = OFF;",
+            "BEGIN TRANSACTION;",
+            "CREATE TABLE \"posts\" (",
+            "    \"id\" integer NOT NULL,",
+            "    \"author_id\" integer,",
+            "    PRIMARY KEY (\"id\"),",
+            "    CONSTRAINT \"fk_posts_author_id\" FOREIGN KEY (\"author_id\") REFERENCES \"users\" (\"id\")",
+            ");",
+            "CREATE TABLE \"users_new\" (",
+            "    \"id\" integer NOT NULL,",
+            "    \"email\" varchar(200) NOT NULL UNIQUE,",
+            "    \"name\" text,",
+            "    PRIMARY KEY (\"id\")",
+            ");",
+            "INSERT INTO \"users_new\" (\"id\", \"email\") SELECT \"id\", \"email\" FROM \"users\";",
+            "DROP TABLE \"users\";",
+            "ALTER TABLE \"users_new\" RENAME TO \"users\";",
+            "PRAGMA foreign_key_check;",
+            "COMMIT;",
+            "PRAGMA foreign_keys = ON;",
+        ]);
+
+        let backward = generator.generate(&new, &old);
+        assert!(backward.sql.contains("INSERT INTO \"users_new\" (\"id\", \"email\") SELECT \"id\", \"email\" FROM \"users\";"), "{}", backward.sql);
+        assert!(backward.warnings.contains(&"users.name: column dropped, its data is lost".to_string()));
+    }
+}
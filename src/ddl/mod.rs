@@ -0,0 +1,4 @@
+pub mod dialect;
+pub mod generator;
+pub mod migration;
+pub mod translate;
@@ -0,0 +1,457 @@
+// ddl/translate.rs
+// Translates column types, defaults and identities of a snapshot from one SQL dialect to another
+
+use crate::db::models::{ColumnMetadata, DatabaseMetadata, TableMetadata};
+use crate::db::types::LogicalType;
+use super::dialect::{Dialect, quote_string};
+
+/// Metadata rewritten for the target dialect, plus one warning per lossy conversion.
+pub struct Translation {
+    pub metadata: DatabaseMetadata,
+    pub warnings: Vec<String>,
+}
+
+/// Rewrites every column so that `DdlGenerator` for `target` produces valid DDL: types are
+/// re-rendered through `LogicalType`, lengths folded into the type, defaults converted and
+/// sequence defaults replaced by the `auto_increment` flag. Within one dialect the recorded
+/// types and defaults are kept as they are and only identities are normalized.
+pub fn translate(metadata: &DatabaseMetadata, source: Dialect, target: Dialect) -> Translation {
+    let mut translated = metadata.clone();
+    let mut warnings = Vec::new();
+
+    for name in metadata.sorted_table_names() {
+        let table = translated.tables.get_mut(name).unwrap();
+        let single_key = table.primary_keys.len() == 1;
+        for col in table.columns.iter_mut() {
+            let context = format!("{}.{}", name, col.name);
+            translate_column(col, single_key, source, target, &context, &mut warnings);
+        }
+        check_indexed_columns(name, table, source, target, &mut warnings);
+    }
+    Translation { metadata: translated, warnings }
+}
+
+fn translate_column(col: &mut ColumnMetadata, single_key: bool, source: Dialect, target: Dialect, context: &str, warnings: &mut Vec<String>) {
+    let logical = LogicalType::from_column(col, source.name());
+    let auto_increment = col.auto_increment == Some(true)
+        || col.default_value.as_deref().is_some_and(|d| d.starts_with("nextval("));
+
+    if source == target {
+        col.default_value = if auto_increment {
+            None
+        } else {
+            col.default_value.as_deref().map(|d| recorded_default(d, source))
+        };
+        col.auto_increment = Some(auto_increment);
+        return;
+    }
+
+    if let LogicalType::Enum(_) = logical {
+        // Enum columns keep their type name; DdlGenerator renders them per dialect
+        if target == Dialect::Sqlite {
+            warnings.push(format!("{}: enum type stored as TEXT with a CHECK constraint", context));
+        }
+    } else {
+        if let LogicalType::Other(raw) = &logical {
+            warnings.push(format!("{}: unrecognized type {} kept as-is", context, raw));
+        }
+        col.data_type = if auto_increment && target == Dialect::Sqlite {
+            // Only a column declared exactly INTEGER PRIMARY KEY aliases the rowid
+            if !single_key || !col.primary_key {
+                warnings.push(format!("{}: SQLite only auto-increments a single-column INTEGER PRIMARY KEY", context));
+            }
+            "INTEGER".to_string()
+        } else {
+            render_type(&logical, target, context, warnings)
+        };
+        col.field_length = None;
+        // The rendered type is wide enough for the unsigned range
+        col.unsigned = None;
+    }
+
+    col.default_value = if auto_increment {
+        None
+    } else {
+        col.default_value.as_deref().and_then(|d| translate_default(d, &logical, source, target, context, warnings))
+    };
+    col.auto_increment = Some(auto_increment);
+}
+
+/// Renders a logical type in the target dialect, warning when information is lost.
+pub fn render_type(logical: &LogicalType, target: Dialect, context: &str, warnings: &mut Vec<String>) -> String {
+    let with_length = |base: &str, length: &Option<i64>| match length {
+        Some(length) => format!("{}({})", base, length),
+        None => base.to_string(),
+    };
+    let decimal = |base: &str, precision: &Option<i64>, scale: &Option<i64>| match (precision, scale) {
+        (Some(p), Some(s)) => format!("{}({},{})", base, p, s),
+        (Some(p), None) => format!("{}({})", base, p),
+        _ => base.to_string(),
+    };
+    let mut lossy = |message: &str| warnings.push(format!("{}: {}", context, message));
+
+    match target {
+        Dialect::Postgres => match logical {
+            LogicalType::Boolean => "boolean".to_string(),
+            LogicalType::SmallInt => "smallint".to_string(),
+            LogicalType::Integer => "integer".to_string(),
+            LogicalType::BigInt => "bigint".to_string(),
+            LogicalType::Decimal { precision, scale } => decimal("numeric", precision, scale),
+            LogicalType::Float => "real".to_string(),
+            LogicalType::Double => "double precision".to_string(),
+            LogicalType::Char(length) => with_length("char", length),
+            LogicalType::Varchar(length) => with_length("varchar", length),
+            LogicalType::Text => "text".to_string(),
+            LogicalType::Date => "date".to_string(),
+            LogicalType::Time => "time".to_string(),
+            LogicalType::Timestamp => "timestamp".to_string(),
+            LogicalType::TimestampTz => "timestamptz".to_string(),
+            LogicalType::Interval => "interval".to_string(),
+            LogicalType::Uuid => "uuid".to_string(),
+            LogicalType::Json => "jsonb".to_string(),
+            LogicalType::Binary => "bytea".to_string(),
+            LogicalType::Enum(_) => "text".to_string(),
+            LogicalType::Array(Some(element)) => format!("{}[]", render_type(element, target, context, warnings)),
+            LogicalType::Array(None) => {
+                lossy("array element type is not part of the snapshot, using text[]");
+                "text[]".to_string()
+            }
+            LogicalType::Other(raw) => raw.clone(),
+        },
+        Dialect::MySql => match logical {
+            LogicalType::Boolean => "tinyint(1)".to_string(),
+            LogicalType::SmallInt => "smallint".to_string(),
+            LogicalType::Integer => "int".to_string(),
+            LogicalType::BigInt => "bigint".to_string(),
+            LogicalType::Decimal { precision, scale } => decimal("decimal", precision, scale),
+            LogicalType::Float => "float".to_string(),
+            LogicalType::Double => "double".to_string(),
+            LogicalType::Char(length) => with_length("char", length),
+            LogicalType::Varchar(Some(length)) => format!("varchar({})", length),
+            LogicalType::Varchar(None) => {
+                lossy("varchar without length, using varchar(255)");
+                "varchar(255)".to_string()
+            }
+            LogicalType::Text => "longtext".to_string(),
+            LogicalType::Date => "date".to_string(),
+            LogicalType::Time => "time".to_string(),
+            LogicalType::Timestamp => "datetime".to_string(),
+            LogicalType::TimestampTz => {
+                lossy("time zone is dropped, using datetime");
+                "datetime".to_string()
+            }
+            LogicalType::Interval => {
+                lossy("interval has no MySQL equivalent, using varchar(64)");
+                "varchar(64)".to_string()
+            }
+            LogicalType::Uuid => "char(36)".to_string(),
+            LogicalType::Json => "json".to_string(),
+            LogicalType::Binary => "longblob".to_string(),
+            LogicalType::Enum(_) => "text".to_string(),
+            LogicalType::Array(_) => {
+                lossy("array stored as json");
+                "json".to_string()
+            }
+            LogicalType::Other(raw) => raw.clone(),
+        },
+        Dialect::Sqlite => match logical {
+            LogicalType::Boolean => "BOOLEAN".to_string(),
+            LogicalType::SmallInt => "SMALLINT".to_string(),
+            LogicalType::Integer => "INTEGER".to_string(),
+            LogicalType::BigInt => "BIGINT".to_string(),
+            LogicalType::Decimal { precision, scale } => {
+                lossy("SQLite does not store exact decimals, values may be rounded");
+                decimal("NUMERIC", precision, scale)
+            }
+            LogicalType::Float | LogicalType::Double => "REAL".to_string(),
+            LogicalType::Char(length) => with_length("CHAR", length),
+            LogicalType::Varchar(length) => with_length("VARCHAR", length),
+            LogicalType::Text | LogicalType::Uuid | LogicalType::Json => "TEXT".to_string(),
+            LogicalType::Date => "DATE".to_string(),
+            LogicalType::Time => "TIME".to_string(),
+            LogicalType::Timestamp => "TIMESTAMP".to_string(),
+            LogicalType::TimestampTz => "TIMESTAMPTZ".to_string(),
+            LogicalType::Interval => {
+                lossy("interval stored as TEXT");
+                "TEXT".to_string()
+            }
+            LogicalType::Binary => "BLOB".to_string(),
+            LogicalType::Enum(_) => "TEXT".to_string(),
+            LogicalType::Array(_) => {
+                lossy("array stored as TEXT");
+                "TEXT".to_string()
+            }
+            LogicalType::Other(raw) => raw.clone(),
+        },
+    }
+}
+
+/// Converts a column default to the target dialect. Returns None when the default cannot be
+/// expressed there, which is reported as a warning.
+fn translate_default(default: &str, logical: &LogicalType, source: Dialect, target: Dialect, context: &str, warnings: &mut Vec<String>) -> Option<String> {
+    let value = default.trim();
+    let upper = value.to_uppercase();
+
+    if matches!(upper.as_str(), "NOW()" | "CURRENT_TIMESTAMP" | "CURRENT_TIMESTAMP()" | "LOCALTIMESTAMP" | "TRANSACTION_TIMESTAMP()")
+        || upper.starts_with("CURRENT_TIMESTAMP(")
+    {
+        return Some("CURRENT_TIMESTAMP".to_string());
+    }
+    if matches!(upper.as_str(), "CURRENT_DATE" | "CURRENT_TIME") {
+        return Some(upper);
+    }
+    if upper == "NULL" {
+        return Some(upper);
+    }
+
+    // PostgreSQL annotates literals with casts, e.g. 'new'::character varying
+    let literal = if source == Dialect::Postgres && target != Dialect::Postgres {
+        strip_postgres_cast(value)
+    } else {
+        value
+    };
+
+    if *logical == LogicalType::Boolean {
+        let lower = literal.to_lowercase();
+        // Bit literals b'1' and b'0' are accepted as well
+        let truthy = match lower.strip_prefix('b').unwrap_or(&lower).trim_matches('\'') {
+            "true" | "t" | "1" => Some(true),
+            "false" | "f" | "0" => Some(false),
+            _ => None,
+        };
+        if let Some(truthy) = truthy {
+            return Some(match (target, truthy) {
+                (Dialect::Postgres, true) => "true".to_string(),
+                (Dialect::Postgres, false) => "false".to_string(),
+                (_, true) => "1".to_string(),
+                (_, false) => "0".to_string(),
+            });
+        }
+    }
+
+    // MySQL bit literals such as b'101' set integer columns too
+    let bits = literal.strip_prefix("b'").or_else(|| literal.strip_prefix("B'")).and_then(|b| b.strip_suffix('\''));
+    if let (Some(bits), LogicalType::SmallInt | LogicalType::Integer | LogicalType::BigInt) = (bits, logical) {
+        if let Ok(number) = i64::from_str_radix(bits, 2) {
+            return Some(number.to_string());
+        }
+    }
+
+    if literal.starts_with('\'') || literal.parse::<f64>().is_ok() {
+        return Some(literal.to_string());
+    }
+
+    let is_expression = literal.contains('(');
+    if source == Dialect::MySql && !is_expression {
+        // MySQL reports string defaults without quotes
+        return Some(quote_string(literal));
+    }
+
+    warnings.push(format!("{}: default {} has no {} equivalent and was dropped", context, value, target.name()));
+    None
+}
+
+/// A default kept in its own dialect. MySQL reports string defaults without quotes, which
+/// DDL needs back; keywords, numbers, bit literals and expressions stay as recorded.
+fn recorded_default(default: &str, dialect: Dialect) -> String {
+    let value = default.trim();
+    let upper = value.to_uppercase();
+    let verbatim = dialect != Dialect::MySql
+        || value.starts_with('\'')
+        || value.contains('(')
+        || value.parse::<f64>().is_ok()
+        || upper == "NULL"
+        || upper.starts_with("CURRENT_")
+        || upper.starts_with("LOCALTIME")
+        || (upper.starts_with("B'") && upper.ends_with('\''));
+    if verbatim {
+        default.to_string()
+    } else {
+        quote_string(value)
+    }
+}
+
+/// `'a'::text` becomes `'a'`, `(0)::numeric` becomes `0`.
+fn strip_postgres_cast(value: &str) -> &str {
+    let mut literal = value;
+    if !literal.starts_with('\'') {
+        if let Some((head, _)) = literal.split_once("::") {
+            literal = head;
+        }
+    } else if let Some(end) = literal[1..].rfind('\'') {
+        literal = &literal[..end + 2];
+    }
+    literal.trim_start_matches('(').trim_end_matches(')')
+}
+
+/// MySQL can only index TEXT and BLOB columns with an explicit prefix length.
+fn check_indexed_columns(name: &str, table: &TableMetadata, source: Dialect, target: Dialect, warnings: &mut Vec<String>) {
+    if target != Dialect::MySql || source == Dialect::MySql {
+        return;
+    }
+    let mut indexed: Vec<&String> = table.primary_keys.iter().collect();
+    indexed.extend(table.indexes.iter().flat_map(|i| i.columns.iter()));
+    for col in table.columns.iter().filter(|c| indexed.contains(&&c.name)) {
+        if matches!(col.data_type.as_str(), "longtext" | "longblob" | "json") {
+            warnings.push(format!("{}.{}: MySQL cannot index {} columns without a prefix length", name, col.name, col.data_type));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn single_table(name: &str, columns: serde_json::Value) -> DatabaseMetadata {
+        serde_json::from_value(json!({
+            "tables": { name: { "columns": columns, "primary_keys": [], "foreign_keys": {} } }
+        }))
+        .unwrap()
+    }
+
+    fn column(name: &str, data_type: &str, default: Option<&str>) -> serde_json::Value {
+        json!({
+            "name": name, "type": data_type, "nullable": false, "primary_key": false, "field_length": null,
+            "unique": null, "spec": null, "isChecked": true, "default_value": default,
+        })
+    }
+
+    fn postgres_ddl(metadata: &DatabaseMetadata, source: Dialect) -> String {
+        let translation = translate(metadata, source, Dialect::Postgres);
+        assert!(translation.warnings.is_empty(), "{:?}", translation.warnings);
+        crate::ddl::generator::DdlGenerator { dialect: Dialect::Postgres }.generate(&translation.metadata, &mut Vec::new())
+    }
+
+    #[test]
+    fn mysql_tinyint_is_boolean_only_with_display_width_one() {
+        let mut flag = column("active", "tinyint", Some("1"));
+        flag["field_length"] = json!(1);
+        let metadata = single_table("shop.orders", json!([column("status", "tinyint", Some("3")), flag]));
+        let sql = postgres_ddl(&metadata, Dialect::MySql);
+        assert!(sql.contains("\"status\" smallint NOT NULL DEFAULT 3"), "{}", sql);
+        assert!(sql.contains("\"active\" boolean NOT NULL DEFAULT true"), "{}", sql);
+
+        // Snapshots can also carry the width in the type name
+        let metadata = single_table("shop.orders", json!([column("active", "tinyint(1)", Some("0"))]));
+        assert!(postgres_ddl(&metadata, Dialect::MySql).contains("\"active\" boolean NOT NULL DEFAULT false"));
+    }
+
+    #[test]
+    fn mysql_bit_literal_defaults() {
+        let mut bit = column("enabled", "bit", Some("b'1'"));
+        bit["numeric_precision"] = json!(1);
+        let metadata = single_table("shop.flags", json!([bit, column("mask", "tinyint", Some("b'101'"))]));
+        let sql = postgres_ddl(&metadata, Dialect::MySql);
+        assert!(sql.contains("\"enabled\" boolean NOT NULL DEFAULT true"), "{}", sql);
+        assert!(sql.contains("\"mask\" smallint NOT NULL DEFAULT 5"), "{}", sql);
+    }
+
+    #[test]
+    fn same_dialect_translation_is_silent() {
+        let metadata = single_table("prices", json!([column("amount", "NUMERIC(10,2)", None)]));
+        let translation = translate(&metadata, Dialect::Sqlite, Dialect::Sqlite);
+        assert!(translation.warnings.is_empty(), "{:?}", translation.warnings);
+        assert_eq!(translation.metadata.tables["prices"].columns[0].data_type, "NUMERIC(10,2)");
+
+        let translation = translate(&metadata, Dialect::Postgres, Dialect::Sqlite);
+        assert_eq!(translation.warnings, vec!["prices.amount: SQLite does not store exact decimals, values may be rounded"]);
+    }
+
+    #[test]
+    fn arrays_keep_their_element_type() {
+        let mut warnings = Vec::new();
+        let integers = LogicalType::Array(Some(Box::new(LogicalType::Integer)));
+        assert_eq!(render_type(&integers, Dialect::Postgres, "t.c", &mut warnings), "integer[]");
+        assert!(warnings.is_empty());
+        assert_eq!(render_type(&LogicalType::Array(None), Dialect::Postgres, "t.c", &mut warnings), "text[]");
+        assert_eq!(warnings, vec!["t.c: array element type is not part of the snapshot, using text[]"]);
+    }
+
+    /// Columns as the live accessors record them, with the attributes `column` leaves out.
+    fn recorded(name: &str, data_type: &str, default: Option<&str>, attributes: serde_json::Value) -> serde_json::Value {
+        let mut col = column(name, data_type, default);
+        col.as_object_mut().unwrap().extend(attributes.as_object().unwrap().clone());
+        col
+    }
+
+    fn assert_round_trip(metadata: &DatabaseMetadata, dialect: Dialect, table: &str) -> String {
+        let translation = translate(metadata, dialect, dialect);
+        assert!(translation.warnings.is_empty(), "{:?}", translation.warnings);
+        for (before, after) in metadata.tables[table].columns.iter().zip(&translation.metadata.tables[table].columns) {
+            assert_eq!(
+                (&before.data_type, before.field_length, before.numeric_precision, before.numeric_scale),
+                (&after.data_type, after.field_length, after.numeric_precision, after.numeric_scale),
+            );
+        }
+        crate::ddl::generator::DdlGenerator { dialect }.generate(&translation.metadata, &mut Vec::new())
+    }
+
+    #[test]
+    fn same_dialect_keeps_recorded_types() {
+        let metadata = single_table("public.docs", json!([
+            recorded("id", "integer", Some("nextval('docs_id_seq'::regclass)"), json!({ "numeric_precision": 32, "numeric_scale": 0 })),
+            recorded("body", "json", None, json!({})),
+            recorded("name", "citext", None, json!({})),
+            recorded("at", "time with time zone", None, json!({})),
+            recorded("email", "character varying", Some("'new'::character varying"), json!({ "field_length": 120 })),
+            recorded("balance", "numeric", None, json!({ "numeric_precision": 12, "numeric_scale": 2 })),
+            recorded("tags", "text[]", None, json!({})),
+        ]));
+        let sql = assert_round_trip(&metadata, Dialect::Postgres, "public.docs");
+        for line in [
+            "\"id\" integer GENERATED BY DEFAULT AS IDENTITY NOT NULL",
+            "\"body\" json NOT NULL",
+            "\"name\" citext NOT NULL",
+            "\"at\" time with time zone NOT NULL",
+            "\"email\" character varying(120) NOT NULL DEFAULT 'new'::character varying",
+            "\"balance\" numeric(12,2) NOT NULL",
+            "\"tags\" text[] NOT NULL",
+        ] {
+            assert!(sql.contains(line), "{} missing from\n{}", line, sql);
+        }
+
+        let metadata = single_table("shop.orders", json!([
+            recorded("id", "int", None, json!({ "auto_increment": true, "numeric_precision": 10, "numeric_scale": 0 })),
+            recorded("customer_id", "int", None, json!({ "unsigned": true, "numeric_precision": 10, "numeric_scale": 0 })),
+            recorded("notes", "text", None, json!({ "field_length": 65535 })),
+            recorded("body", "longtext", None, json!({ "field_length": 4294967295i64 })),
+            recorded("created", "timestamp", Some("CURRENT_TIMESTAMP"), json!({})),
+            recorded("level", "tinyint", Some("3"), json!({ "numeric_precision": 3, "numeric_scale": 0 })),
+            recorded("flag", "tinyint", Some("1"), json!({ "field_length": 1, "numeric_precision": 3, "numeric_scale": 0 })),
+            recorded("paid", "bit", Some("b'0'"), json!({ "numeric_precision": 1 })),
+            recorded("tags", "set('x','y')", Some("x"), json!({})),
+            recorded("status", "varchar", Some("open"), json!({ "field_length": 10 })),
+            recorded("total", "decimal", None, json!({ "numeric_precision": 10, "numeric_scale": 2 })),
+        ]));
+        let sql = assert_round_trip(&metadata, Dialect::MySql, "shop.orders");
+        for line in [
+            "`id` int AUTO_INCREMENT NOT NULL",
+            "`customer_id` int unsigned NOT NULL",
+            "`notes` text NOT NULL",
+            "`body` longtext NOT NULL",
+            "`created` timestamp NOT NULL DEFAULT CURRENT_TIMESTAMP",
+            "`level` tinyint NOT NULL DEFAULT 3",
+            "`flag` tinyint(1) NOT NULL DEFAULT 1",
+            "`paid` bit(1) NOT NULL DEFAULT b'0'",
+            "`tags` set('x','y') NOT NULL DEFAULT 'x'",
+            "`status` varchar(10) NOT NULL DEFAULT 'open'",
+            "`total` decimal(10,2) NOT NULL",
+        ] {
+            assert!(sql.contains(line), "{} missing from\n{}", line, sql);
+        }
+    }
+
+    #[test]
+    fn unsigned_integers_widen_across_dialects() {
+        let metadata = single_table("shop.orders", json!([
+            recorded("quantity", "smallint", None, json!({ "unsigned": true })),
+            recorded("customer_id", "int", None, json!({ "unsigned": true })),
+            recorded("total", "bigint", None, json!({ "unsigned": true })),
+        ]));
+        let translation = translate(&metadata, Dialect::MySql, Dialect::Postgres);
+        let columns = &translation.metadata.tables["shop.orders"].columns;
+        let types: Vec<(&str, Option<bool>)> = columns.iter().map(|c| (c.data_type.as_str(), c.unsigned)).collect();
+        assert_eq!(types, [("integer", None), ("bigint", None), ("numeric(20,0)", None)]);
+    }
+}
@@ -0,0 +1,375 @@
+// diff/breaking.rs
+// Classifies the changes of a SchemaDiff as breaking or non-breaking, for gating migrations in CI
+
+use super::schema_diff::{AttributeChange, SchemaDiff, TableDiff};
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema, TableMetadata};
+use crate::db::types::LogicalType;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+
+/// A single change with a stable id of the form `kind:object`, e.g. `drop_column:public.orders.note`.
+/// Ids are what the allowlist matches against.
+#[derive(Serialize, Debug)]
+pub struct ClassifiedChange {
+    pub id: String,
+    pub breaking: bool,
+    /// Breaking change accepted by the allowlist.
+    pub allowed: bool,
+    pub description: String,
+}
+
+/// Accepted breaking changes, one id pattern per line. `*` matches any run of characters,
+/// blank lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Allowlist {
+    patterns: Vec<String>,
+}
+
+impl Allowlist {
+    pub fn load(path: &str) -> Result<Self> {
+        let contents = fs::read_to_string(path).with_context(|| format!("Failed to read allowlist {}", path))?;
+        let patterns = contents
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(str::to_string)
+            .collect();
+        Ok(Self { patterns })
+    }
+
+    pub fn allows(&self, id: &str) -> bool {
+        self.patterns.iter().any(|p| wildcard_match(p, id))
+    }
+}
+
+/// Changes that fail the check: breaking and not allowlisted.
+pub fn blocking_changes(changes: &[ClassifiedChange]) -> Vec<&ClassifiedChange> {
+    changes.iter().filter(|c| c.breaking && !c.allowed).collect()
+}
+
+/// Classifies every change in `diff`. Snapshots carry no row counts, so constraints added to
+/// an existing table are treated as breaking: existing rows may violate them. Their
+/// descriptions say so, as the table may well be empty.
+pub fn classify(diff: &SchemaDiff, old: &DbMetaDataSchema, new: &DbMetaDataSchema, allowlist: &Allowlist) -> Vec<ClassifiedChange> {
+    let mut changes = Vec::new();
+    let mut push = |kind: &str, object: &str, breaking: bool, description: String| {
+        let id = format!("{}:{}", kind, object);
+        let allowed = breaking && allowlist.allows(&id);
+        changes.push(ClassifiedChange { id, breaking, allowed, description });
+    };
+
+    for name in &diff.added_tables {
+        push("add_table", name, false, "table added".to_string());
+    }
+    for name in &diff.removed_tables {
+        push("drop_table", name, true, "table dropped".to_string());
+    }
+
+    let old_db = old.source_database_type.as_deref().unwrap_or_default();
+    let new_db = new.source_database_type.as_deref().unwrap_or_default();
+    for table_diff in &diff.changed_tables {
+        let name = table_diff.table.as_str();
+        let old_table = &old.db_metadata.tables[name];
+        let new_table = &new.db_metadata.tables[name];
+        for (kind, object, breaking, description) in classify_table(table_diff, old_table, new_table, old_db, new_db) {
+            push(kind, &object, breaking, description);
+        }
+    }
+    changes
+}
+
+fn classify_table(
+    diff: &TableDiff,
+    old: &TableMetadata,
+    new: &TableMetadata,
+    old_db: &str,
+    new_db: &str,
+) -> Vec<(&'static str, String, bool, String)> {
+    let name = &diff.table;
+    let mut changes = Vec::new();
+
+    for column in &diff.added_columns {
+        let col = find_column(new, column);
+        let required = !col.nullable && col.default_value.is_none() && col.auto_increment != Some(true);
+        let description = if required {
+            "NOT NULL column added without a default".to_string()
+        } else {
+            "column added".to_string()
+        };
+        changes.push(("add_column", format!("{}.{}", name, column), required, description));
+    }
+    for column in &diff.removed_columns {
+        changes.push(("drop_column", format!("{}.{}", name, column), true, "column dropped".to_string()));
+    }
+    for column in &diff.changed_columns {
+        let old_col = find_column(old, &column.column);
+        let new_col = find_column(new, &column.column);
+        for change in &column.changes {
+            let (breaking, description) = classify_attribute(change, old_col, new_col, old_db, new_db);
+            let kind = match change.attribute.as_str() {
+                "type" => "change_type",
+                "nullable" => "change_nullable",
+                "unique" => "change_unique",
+                "length" => "change_length",
+                "precision" => "change_precision",
+                "scale" => "change_scale",
+                "default" => "change_default",
+                _ => "change_enum_values",
+            };
+            changes.push((kind, format!("{}.{}", name, column.column), breaking, description));
+        }
+    }
+    if let Some(change) = &diff.primary_key {
+        let description = format!("primary key changed from ({}) to ({})", change.old.join(", "), change.new.join(", "));
+        changes.push(("change_primary_key", name.clone(), true, description));
+    }
+    for fk in &diff.added_foreign_keys {
+        let description = format!("foreign key to {} added; row counts are unknown, existing rows may violate it", fk.target);
+        changes.push(("add_foreign_key", format!("{}.{}", name, fk.column), true, description));
+    }
+    for fk in &diff.removed_foreign_keys {
+        changes.push(("drop_foreign_key", format!("{}.{}", name, fk.column), false, format!("foreign key to {} dropped", fk.target)));
+    }
+    for index in &diff.added_indexes {
+        let description = if index.unique {
+            "unique index added; row counts are unknown, existing rows may contain duplicates"
+        } else {
+            "index added"
+        };
+        changes.push(("add_index", format!("{}.{}", name, index.name), index.unique, description.to_string()));
+    }
+    for index in &diff.removed_indexes {
+        changes.push(("drop_index", format!("{}.{}", name, index.name), false, "index dropped".to_string()));
+    }
+    for change in &diff.changed_indexes {
+        // A unique index only rejects more rows when it becomes unique or covers fewer columns
+        let breaking = change.new.unique
+            && (!change.old.unique || !change.new.columns.iter().all(|c| change.old.columns.contains(c))
+                || change.new.columns.len() < change.old.columns.len());
+        let description = format!(
+            "index changed from ({}){} to ({}){}",
+            change.old.columns.join(", "),
+            if change.old.unique { " unique" } else { "" },
+            change.new.columns.join(", "),
+            if change.new.unique { " unique" } else { "" }
+        );
+        changes.push(("change_index", format!("{}.{}", name, change.new.name), breaking, description));
+    }
+    changes
+}
+
+fn classify_attribute(change: &AttributeChange, old: &ColumnMetadata, new: &ColumnMetadata, old_db: &str, new_db: &str) -> (bool, String) {
+    let from_to = format!("from {} to {}", change.old.as_deref().unwrap_or("none"), change.new.as_deref().unwrap_or("none"));
+    match change.attribute.as_str() {
+        "type" => {
+            let old_type = LogicalType::from_column(old, old_db);
+            let new_type = LogicalType::from_column(new, new_db);
+            if old_type == new_type {
+                return (false, format!("type renamed {} (same type)", from_to));
+            }
+            match type_narrowing(&old_type, &new_type) {
+                Some(reason) => (true, format!("type {} ({})", from_to, reason)),
+                None => (false, format!("type widened {}", from_to)),
+            }
+        }
+        "nullable" if !new.nullable && new.default_value.is_none() => {
+            (true, "column made NOT NULL without a default; row counts are unknown, existing rows may hold NULLs".to_string())
+        }
+        "nullable" if !new.nullable => (false, "column made NOT NULL with a default".to_string()),
+        "nullable" => (false, "column made nullable".to_string()),
+        "unique" if new.unique == Some(true) => {
+            (true, "column made unique; row counts are unknown, existing rows may contain duplicates".to_string())
+        }
+        "unique" => (false, "unique constraint dropped".to_string()),
+        "length" | "precision" | "scale" => {
+            let narrowed = match (old_value(change), new_value(change)) {
+                (Some(old), Some(new)) => new < old,
+                // Unbounded becoming bounded
+                (None, Some(_)) => true,
+                _ => false,
+            };
+            let verb = if narrowed { "reduced" } else { "increased" };
+            (narrowed, format!("{} {} {}", change.attribute, verb, from_to))
+        }
+        "enum_values" => {
+            let new_values = new.enum_values.clone().unwrap_or_default();
+            let removed: Vec<&String> = old.enum_values.iter().flatten().filter(|v| !new_values.contains(v)).collect();
+            if removed.is_empty() {
+                (false, "enum values added".to_string())
+            } else {
+                (true, format!("enum values removed: {}", removed.iter().map(|v| v.as_str()).collect::<Vec<_>>().join(", ")))
+            }
+        }
+        _ => (false, format!("{} changed {}", change.attribute, from_to)),
+    }
+}
+
+/// Returns why a type change can lose data, or None when every old value fits the new type.
+fn type_narrowing(old: &LogicalType, new: &LogicalType) -> Option<String> {
+    let narrowed = |what: &str| Some(format!("{} narrowed", what));
+    match (old, new) {
+        (_, _) if integer_rank(old).is_some() && integer_rank(new).is_some() => {
+            (integer_rank(new) < integer_rank(old)).then(|| "integer range narrowed".to_string())
+        }
+        (_, LogicalType::Decimal { .. } | LogicalType::Float | LogicalType::Double) if integer_rank(old).is_some() => None,
+        (LogicalType::Float, LogicalType::Double) => None,
+        (LogicalType::Double, LogicalType::Float) => narrowed("floating point precision"),
+        (LogicalType::Decimal { precision: p1, scale: s1 }, LogicalType::Decimal { precision: p2, scale: s2 }) => {
+            let smaller = |old: &Option<i64>, new: &Option<i64>| match (old, new) {
+                (Some(old), Some(new)) => new < old,
+                (None, Some(_)) => true,
+                _ => false,
+            };
+            (smaller(p1, p2) || smaller(s1, s2)).then(|| "decimal precision narrowed".to_string())
+        }
+        (LogicalType::Enum(_), _) if text_capacity(new) == Some(None) => None,
+        (LogicalType::Date, LogicalType::Timestamp | LogicalType::TimestampTz) => None,
+        (LogicalType::Timestamp, LogicalType::TimestampTz) => None,
+        _ => match (text_capacity(old), text_capacity(new)) {
+            (Some(_), Some(None)) => None,
+            (Some(None), Some(Some(_))) => narrowed("text length"),
+            (Some(Some(old_len)), Some(Some(new_len))) if new_len < old_len => narrowed("text length"),
+            (Some(_), Some(_)) => None,
+            _ => Some("incompatible type".to_string()),
+        },
+    }
+}
+
+fn integer_rank(logical: &LogicalType) -> Option<u8> {
+    match logical {
+        LogicalType::Boolean => Some(0),
+        LogicalType::SmallInt => Some(1),
+        LogicalType::Integer => Some(2),
+        LogicalType::BigInt => Some(3),
+        _ => None,
+    }
+}
+
+/// Maximum length of a character type; `Some(None)` when unbounded.
+fn text_capacity(logical: &LogicalType) -> Option<Option<i64>> {
+    match logical {
+        LogicalType::Char(length) | LogicalType::Varchar(length) => Some(*length),
+        LogicalType::Text => Some(None),
+        _ => None,
+    }
+}
+
+fn old_value(change: &AttributeChange) -> Option<i64> {
+    change.old.as_deref().and_then(|v| v.parse().ok())
+}
+
+fn new_value(change: &AttributeChange) -> Option<i64> {
+    change.new.as_deref().and_then(|v| v.parse().ok())
+}
+
+fn find_column<'a>(table: &'a TableMetadata, name: &str) -> &'a ColumnMetadata {
+    table.columns.iter().find(|c| c.name == name).expect("diffed column exists in its snapshot")
+}
+
+/// Glob-style match supporting only `*`.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || !text[first.len()..].ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::schema_diff::diff_metadata;
+    use serde_json::json;
+
+    fn snapshot(columns: serde_json::Value, extra: serde_json::Value) -> DbMetaDataSchema {
+        let mut table = json!({ "columns": columns, "primary_keys": ["id"], "foreign_keys": {} });
+        for (key, value) in extra.as_object().unwrap() {
+            table[key] = value.clone();
+        }
+        DbMetaDataSchema {
+            db_metadata: serde_json::from_value(json!({ "tables": { "public.orders": table } })).unwrap(),
+            source_database_type: Some("postgres".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> serde_json::Value {
+        json!({ "name": name, "type": data_type, "nullable": nullable, "primary_key": false, "field_length": null, "unique": null, "spec": null, "isChecked": true })
+    }
+
+    fn classified(old: &DbMetaDataSchema, new: &DbMetaDataSchema, allowlist: &Allowlist) -> Vec<(String, bool, String)> {
+        let diff = diff_metadata(&old.db_metadata, &new.db_metadata);
+        classify(&diff, old, new, allowlist).into_iter()
+            .map(|c| (c.id, c.breaking, c.description))
+            .collect()
+    }
+
+    #[test]
+    fn type_changes_are_classified_by_logical_type() {
+        let old = snapshot(json!([column("id", "integer", false), column("qty", "integer", true), column("code", "int4", true)]), json!({}));
+        let new = snapshot(json!([column("id", "bigint", false), column("qty", "smallint", true), column("code", "integer", true)]), json!({}));
+        assert_eq!(classified(&old, &new, &Allowlist::default()), vec![
+            ("change_type:public.orders.id".to_string(), false, "type widened from integer to bigint".to_string()),
+            ("change_type:public.orders.qty".to_string(), true, "type from integer to smallint (integer range narrowed)".to_string()),
+            ("change_type:public.orders.code".to_string(), false, "type renamed from int4 to integer (same type)".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn added_constraints_are_breaking_and_say_row_counts_are_unknown() {
+        let columns = json!([column("id", "integer", false), column("customer_id", "integer", true)]);
+        let old = snapshot(columns.clone(), json!({}));
+        let new = snapshot(columns, json!({
+            "foreign_keys": { "customer_id": "public.customers.id" },
+            "indexes": [{ "name": "orders_customer_key", "columns": ["customer_id"], "unique": true }],
+        }));
+        let changes = classified(&old, &new, &Allowlist::default());
+        assert_eq!(changes, vec![
+            (
+                "add_foreign_key:public.orders.customer_id".to_string(),
+                true,
+                "foreign key to public.customers.id added; row counts are unknown, existing rows may violate it".to_string(),
+            ),
+            (
+                "add_index:public.orders.orders_customer_key".to_string(),
+                true,
+                "unique index added; row counts are unknown, existing rows may contain duplicates".to_string(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn column_changes_are_classified() {
+        let old = snapshot(json!([column("id", "integer", false), column("note", "text", true), column("gone", "text", true)]), json!({}));
+        let new = snapshot(json!([column("id", "integer", false), column("note", "text", false), column("added", "text", true)]), json!({}));
+        let changes: Vec<(String, bool)> = classified(&old, &new, &Allowlist::default()).into_iter().map(|(id, breaking, _)| (id, breaking)).collect();
+        assert_eq!(changes, vec![
+            ("add_column:public.orders.added".to_string(), false),
+            ("drop_column:public.orders.gone".to_string(), true),
+            ("change_nullable:public.orders.note".to_string(), true),
+        ]);
+    }
+
+    #[test]
+    fn allowlisted_changes_do_not_block() {
+        let old = snapshot(json!([column("id", "integer", false), column("gone", "text", true), column("other", "text", true)]), json!({}));
+        let new = snapshot(json!([column("id", "integer", false)]), json!({}));
+        let allowlist = Allowlist { patterns: vec!["drop_column:*.gone".to_string()] };
+        let diff = diff_metadata(&old.db_metadata, &new.db_metadata);
+        let changes = classify(&diff, &old, &new, &allowlist);
+        let blocking: Vec<&str> = blocking_changes(&changes).iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(blocking, vec!["drop_column:public.orders.other"]);
+    }
+}
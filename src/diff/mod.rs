@@ -0,0 +1,3 @@
+pub mod breaking;
+pub mod report;
+pub mod schema_diff;
@@ -0,0 +1,196 @@
+// diff/report.rs
+// Renders a SchemaDiff as plain text, Markdown or JSON
+
+use super::breaking::ClassifiedChange;
+use super::schema_diff::{ColumnDiff, SchemaDiff, TableDiff};
+use crate::db::models::IndexMetadata;
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Markdown,
+    Json,
+}
+
+impl ReportFormat {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.to_lowercase().as_str() {
+            "text" | "txt" => Ok(Self::Text),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow!("Unsupported report format: '{}'. Supported formats: text, markdown, json", name)),
+        }
+    }
+
+    pub fn render(&self, diff: &SchemaDiff, changes: &[ClassifiedChange]) -> Result<String> {
+        match self {
+            Self::Text => Ok(render_text(diff, changes)),
+            Self::Markdown => Ok(render_markdown(diff, changes)),
+            Self::Json => Ok(serde_json::to_string_pretty(&JsonReport { diff, changes })? + "\n"),
+        }
+    }
+}
+
+/// The diff as produced by `diff_metadata`, plus the classified list of changes.
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    #[serde(flatten)]
+    diff: &'a SchemaDiff,
+    changes: &'a [ClassifiedChange],
+}
+
+fn summary(diff: &SchemaDiff) -> String {
+    format!(
+        "{} table(s) added, {} removed, {} changed",
+        diff.added_tables.len(),
+        diff.removed_tables.len(),
+        diff.changed_tables.len()
+    )
+}
+
+pub fn render_text(diff: &SchemaDiff, changes: &[ClassifiedChange]) -> String {
+    if diff.is_empty() {
+        return "No differences\n".to_string();
+    }
+    let mut text = format!("{}\n\n", summary(diff));
+    for name in &diff.added_tables {
+        text.push_str(&format!("+ table {}\n", name));
+    }
+    for name in &diff.removed_tables {
+        text.push_str(&format!("- table {}\n", name));
+    }
+    for table in &diff.changed_tables {
+        text.push_str(&format!("~ table {}\n", table.table));
+        for (marker, line) in table_lines(table) {
+            text.push_str(&format!("    {} {}\n", marker, line));
+        }
+    }
+    let breaking: Vec<&ClassifiedChange> = changes.iter().filter(|c| c.breaking).collect();
+    if !breaking.is_empty() {
+        text.push_str(&format!("\nBreaking changes ({}):\n", breaking.len()));
+        for change in breaking {
+            text.push_str(&format!("  ! {}: {}{}\n", change.id, change.description, if change.allowed { " (allowed)" } else { "" }));
+        }
+    }
+    text
+}
+
+pub fn render_markdown(diff: &SchemaDiff, changes: &[ClassifiedChange]) -> String {
+    let mut md = String::from("# Schema Diff\n\n");
+    if diff.is_empty() {
+        md.push_str("No differences.\n");
+        return md;
+    }
+    md.push_str(&format!("{}.\n\n", summary(diff)));
+    if !diff.added_tables.is_empty() {
+        md.push_str("## Added tables\n\n");
+        for name in &diff.added_tables {
+            md.push_str(&format!("- `{}`\n", name));
+        }
+        md.push('\n');
+    }
+    if !diff.removed_tables.is_empty() {
+        md.push_str("## Removed tables\n\n");
+        for name in &diff.removed_tables {
+            md.push_str(&format!("- `{}`\n", name));
+        }
+        md.push('\n');
+    }
+    if !diff.changed_tables.is_empty() {
+        md.push_str("## Changed tables\n\n");
+        for table in &diff.changed_tables {
+            md.push_str(&format!("### `{}`\n\n", table.table));
+            for (marker, line) in table_lines(table) {
+                md.push_str(&format!("- `{}` {}\n", marker, line));
+            }
+            md.push('\n');
+        }
+    }
+    let breaking: Vec<&ClassifiedChange> = changes.iter().filter(|c| c.breaking).collect();
+    if !breaking.is_empty() {
+        md.push_str("## Breaking changes\n\n| Change | Description | Allowed |\n|---|---|---|\n");
+        for change in breaking {
+            md.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                escape_cell(&change.id),
+                escape_cell(&change.description),
+                if change.allowed { "yes" } else { "no" }
+            ));
+        }
+        md.push('\n');
+    }
+    md
+}
+
+/// Table cells end at a newline and at every `|`, also inside code spans.
+fn escape_cell(value: &str) -> String {
+    value.replace('\r', "").replace('\n', " ").replace('|', "\\|")
+}
+
+/// One line per change inside a table, with its `+`, `-` or `~` marker.
+fn table_lines(table: &TableDiff) -> Vec<(char, String)> {
+    let mut lines = Vec::new();
+    for column in &table.added_columns {
+        lines.push(('+', format!("column {}", column)));
+    }
+    for column in &table.removed_columns {
+        lines.push(('-', format!("column {}", column)));
+    }
+    for column in &table.changed_columns {
+        lines.push(('~', format!("column {}: {}", column.column, column_changes(column))));
+    }
+    if let Some(change) = &table.primary_key {
+        lines.push(('~', format!("primary key: ({}) -> ({})", change.old.join(", "), change.new.join(", "))));
+    }
+    for fk in &table.added_foreign_keys {
+        lines.push(('+', format!("foreign key {} -> {}", fk.column, fk.target)));
+    }
+    for fk in &table.removed_foreign_keys {
+        lines.push(('-', format!("foreign key {} -> {}", fk.column, fk.target)));
+    }
+    for index in &table.added_indexes {
+        lines.push(('+', format!("index {}", describe_index(index))));
+    }
+    for index in &table.removed_indexes {
+        lines.push(('-', format!("index {}", describe_index(index))));
+    }
+    for change in &table.changed_indexes {
+        lines.push(('~', format!("index {} -> {}", describe_index(&change.old), describe_index(&change.new))));
+    }
+    lines
+}
+
+fn column_changes(column: &ColumnDiff) -> String {
+    column.changes.iter()
+        .map(|c| format!("{} {} -> {}", c.attribute, c.old.as_deref().unwrap_or("none"), c.new.as_deref().unwrap_or("none")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn describe_index(index: &IndexMetadata) -> String {
+    format!("{} ({}){}", index.name, index.columns.join(", "), if index.unique { " unique" } else { "" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_cells_escape_pipes_and_newlines() {
+        let diff = SchemaDiff { added_tables: vec!["public.tags".to_string()], ..Default::default() };
+        let changes = [ClassifiedChange {
+            id: "column.default:public.users.note".to_string(),
+            breaking: true,
+            allowed: false,
+            description: "default of public.users.note changed from 'a|b' to 'line\nbreak'".to_string(),
+        }];
+        let md = render_markdown(&diff, &changes);
+        assert!(
+            md.contains("| `column.default:public.users.note` | default of public.users.note changed from 'a\\|b' to 'line break' | no |\n"),
+            "{}",
+            md
+        );
+    }
+}
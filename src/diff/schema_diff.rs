@@ -0,0 +1,270 @@
+// diff/schema_diff.rs
+// Structural comparison of two metadata snapshots
+
+use crate::db::models::{ColumnMetadata, DatabaseMetadata, IndexMetadata, TableMetadata};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Differences between two snapshots. Only `db_metadata` is compared, so ids, timestamps and
+/// the order of tables, columns and keys never show up as changes.
+#[derive(Serialize, Debug, Default)]
+pub struct SchemaDiff {
+    pub added_tables: Vec<String>,
+    pub removed_tables: Vec<String>,
+    pub changed_tables: Vec<TableDiff>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct TableDiff {
+    pub table: String,
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    pub changed_columns: Vec<ColumnDiff>,
+    pub primary_key: Option<ValueChange<Vec<String>>>,
+    pub added_foreign_keys: Vec<ForeignKeyRef>,
+    pub removed_foreign_keys: Vec<ForeignKeyRef>,
+    pub added_indexes: Vec<IndexMetadata>,
+    pub removed_indexes: Vec<IndexMetadata>,
+    pub changed_indexes: Vec<ValueChange<IndexMetadata>>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ColumnDiff {
+    pub column: String,
+    pub changes: Vec<AttributeChange>,
+}
+
+/// A changed column attribute (`type`, `nullable`, `length`, ...) with display values.
+#[derive(Serialize, Debug)]
+pub struct AttributeChange {
+    pub attribute: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValueChange<T> {
+    pub old: T,
+    pub new: T,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyRef {
+    pub column: String,
+    pub target: String,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty() && self.removed_tables.is_empty() && self.changed_tables.is_empty()
+    }
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.changed_columns.is_empty()
+            && self.primary_key.is_none()
+            && self.added_foreign_keys.is_empty()
+            && self.removed_foreign_keys.is_empty()
+            && self.added_indexes.is_empty()
+            && self.removed_indexes.is_empty()
+            && self.changed_indexes.is_empty()
+    }
+}
+
+pub fn diff_metadata(old: &DatabaseMetadata, new: &DatabaseMetadata) -> SchemaDiff {
+    let old_names: BTreeSet<&String> = old.tables.keys().collect();
+    let new_names: BTreeSet<&String> = new.tables.keys().collect();
+
+    SchemaDiff {
+        added_tables: new_names.difference(&old_names).map(|n| n.to_string()).collect(),
+        removed_tables: old_names.difference(&new_names).map(|n| n.to_string()).collect(),
+        changed_tables: old_names
+            .intersection(&new_names)
+            .map(|name| diff_table(name, &old.tables[*name], &new.tables[*name]))
+            .filter(|d| !d.is_empty())
+            .collect(),
+    }
+}
+
+pub fn diff_table(name: &str, old: &TableMetadata, new: &TableMetadata) -> TableDiff {
+    let old_columns: BTreeSet<&String> = old.columns.iter().map(|c| &c.name).collect();
+    let new_columns: BTreeSet<&String> = new.columns.iter().map(|c| &c.name).collect();
+
+    let changed_columns = old.columns.iter()
+        .filter_map(|old_col| {
+            let new_col = new.columns.iter().find(|c| c.name == old_col.name)?;
+            let changes = diff_column(old_col, new_col);
+            (!changes.is_empty()).then(|| ColumnDiff { column: old_col.name.clone(), changes })
+        })
+        .collect();
+
+    let old_fks = foreign_key_refs(old);
+    let new_fks = foreign_key_refs(new);
+
+    let mut added_indexes = Vec::new();
+    let mut changed_indexes = Vec::new();
+    for index in sorted_indexes(new) {
+        match old.indexes.iter().find(|i| i.name == index.name) {
+            None => added_indexes.push(index.clone()),
+            Some(old_index) if old_index != index => {
+                changed_indexes.push(ValueChange { old: old_index.clone(), new: index.clone() })
+            }
+            _ => {}
+        }
+    }
+
+    TableDiff {
+        table: name.to_string(),
+        added_columns: new_columns.difference(&old_columns).map(|c| c.to_string()).collect(),
+        removed_columns: old_columns.difference(&new_columns).map(|c| c.to_string()).collect(),
+        changed_columns,
+        primary_key: (old.primary_keys != new.primary_keys)
+            .then(|| ValueChange { old: old.primary_keys.clone(), new: new.primary_keys.clone() }),
+        added_foreign_keys: new_fks.iter().filter(|fk| !old_fks.contains(fk)).cloned().collect(),
+        removed_foreign_keys: old_fks.iter().filter(|fk| !new_fks.contains(fk)).cloned().collect(),
+        added_indexes,
+        removed_indexes: sorted_indexes(old).into_iter()
+            .filter(|i| !new.indexes.iter().any(|n| n.name == i.name))
+            .cloned()
+            .collect(),
+        changed_indexes,
+    }
+}
+
+/// Compares the attributes that matter for the schema; type names are compared case-insensitively.
+pub fn diff_column(old: &ColumnMetadata, new: &ColumnMetadata) -> Vec<AttributeChange> {
+    let mut changes = Vec::new();
+    let mut compare = |attribute: &str, old: Option<String>, new: Option<String>| {
+        if old != new {
+            changes.push(AttributeChange { attribute: attribute.to_string(), old, new });
+        }
+    };
+    if !old.data_type.eq_ignore_ascii_case(&new.data_type) {
+        compare("type", Some(old.data_type.clone()), Some(new.data_type.clone()));
+    }
+    compare("nullable", Some(old.nullable.to_string()), Some(new.nullable.to_string()));
+    compare("unique", Some((old.unique == Some(true)).to_string()), Some((new.unique == Some(true)).to_string()));
+    compare("length", old.field_length.map(|l| l.to_string()), new.field_length.map(|l| l.to_string()));
+    compare("precision", old.numeric_precision.map(|p| p.to_string()), new.numeric_precision.map(|p| p.to_string()));
+    compare("scale", old.numeric_scale.map(|s| s.to_string()), new.numeric_scale.map(|s| s.to_string()));
+    compare("default", old.default_value.clone(), new.default_value.clone());
+    compare("enum_values", old.enum_values.as_ref().map(|v| v.join(", ")), new.enum_values.as_ref().map(|v| v.join(", ")));
+    changes
+}
+
+fn foreign_key_refs(table: &TableMetadata) -> Vec<ForeignKeyRef> {
+    table.sorted_foreign_keys().into_iter()
+        .map(|(column, target)| ForeignKeyRef { column: column.clone(), target: target.clone() })
+        .collect()
+}
+
+fn sorted_indexes(table: &TableMetadata) -> Vec<&IndexMetadata> {
+    let mut indexes: Vec<&IndexMetadata> = table.indexes.iter().collect();
+    indexes.sort_by(|a, b| a.name.cmp(&b.name));
+    indexes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata(tables: serde_json::Value) -> DatabaseMetadata {
+        serde_json::from_value(json!({ "tables": tables })).unwrap()
+    }
+
+    fn column(name: &str, data_type: &str) -> serde_json::Value {
+        json!({ "name": name, "type": data_type, "nullable": true, "primary_key": false, "field_length": null, "unique": null, "spec": null, "isChecked": true })
+    }
+
+    fn orders(columns: serde_json::Value, primary_keys: serde_json::Value) -> serde_json::Value {
+        json!({ "columns": columns, "primary_keys": primary_keys, "foreign_keys": {} })
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_changes() {
+        let tables = json!({ "orders": orders(json!([column("id", "integer")]), json!(["id"])) });
+        assert!(diff_metadata(&metadata(tables.clone()), &metadata(tables)).is_empty());
+    }
+
+    #[test]
+    fn tables_and_columns_are_added_and_removed() {
+        let old = metadata(json!({
+            "orders": orders(json!([column("id", "integer"), column("note", "text")]), json!(["id"])),
+            "legacy": orders(json!([column("id", "integer")]), json!([])),
+        }));
+        let new = metadata(json!({
+            "orders": orders(json!([column("id", "integer"), column("total", "numeric")]), json!(["id"])),
+            "customers": orders(json!([column("id", "integer")]), json!([])),
+        }));
+        let diff = diff_metadata(&old, &new);
+        assert_eq!(diff.added_tables, vec!["customers"]);
+        assert_eq!(diff.removed_tables, vec!["legacy"]);
+        assert_eq!(diff.changed_tables.len(), 1);
+        assert_eq!(diff.changed_tables[0].added_columns, vec!["total"]);
+        assert_eq!(diff.changed_tables[0].removed_columns, vec!["note"]);
+    }
+
+    #[test]
+    fn column_attributes_are_compared() {
+        let mut email = column("email", "VARCHAR");
+        email["field_length"] = json!(120);
+        let old = metadata(json!({ "users": orders(json!([email.clone()]), json!([])) }));
+        email["type"] = json!("varchar");
+        email["field_length"] = json!(200);
+        email["nullable"] = json!(false);
+        email["unique"] = json!(true);
+        let new = metadata(json!({ "users": orders(json!([email]), json!([])) }));
+
+        let diff = diff_metadata(&old, &new);
+        let changes: Vec<(&str, Option<&str>, Option<&str>)> = diff.changed_tables[0].changed_columns[0].changes.iter()
+            .map(|c| (c.attribute.as_str(), c.old.as_deref(), c.new.as_deref()))
+            .collect();
+        // Type names differing only in case are not a change
+        assert_eq!(changes, vec![
+            ("nullable", Some("true"), Some("false")),
+            ("unique", Some("false"), Some("true")),
+            ("length", Some("120"), Some("200")),
+        ]);
+    }
+
+    #[test]
+    fn primary_key_column_order_is_a_change() {
+        let columns = json!([column("region", "text"), column("number", "integer")]);
+        let old = metadata(json!({ "orders": orders(columns.clone(), json!(["region", "number"])) }));
+        let new = metadata(json!({ "orders": orders(columns, json!(["number", "region"])) }));
+        let diff = diff_metadata(&old, &new);
+        assert_eq!(diff.changed_tables[0].primary_key, Some(ValueChange {
+            old: vec!["region".to_string(), "number".to_string()],
+            new: vec!["number".to_string(), "region".to_string()],
+        }));
+    }
+
+    #[test]
+    fn foreign_keys_and_indexes_are_compared() {
+        let mut old_table = orders(json!([column("id", "integer"), column("customer_id", "integer")]), json!(["id"]));
+        old_table["foreign_keys"] = json!({ "customer_id": "customers.id" });
+        old_table["indexes"] = json!([
+            { "name": "orders_customer", "columns": ["customer_id"], "unique": false },
+            { "name": "orders_old", "columns": ["id"], "unique": false },
+        ]);
+        let mut new_table = old_table.clone();
+        new_table["foreign_keys"] = json!({ "customer_id": "clients.id" });
+        new_table["indexes"] = json!([
+            { "name": "orders_customer", "columns": ["customer_id"], "unique": true },
+            { "name": "orders_new", "columns": ["id", "customer_id"], "unique": false },
+        ]);
+
+        let diff = diff_metadata(&metadata(json!({ "orders": old_table })), &metadata(json!({ "orders": new_table })));
+        let table = &diff.changed_tables[0];
+        assert_eq!(table.added_foreign_keys, vec![ForeignKeyRef { column: "customer_id".into(), target: "clients.id".into() }]);
+        assert_eq!(table.removed_foreign_keys, vec![ForeignKeyRef { column: "customer_id".into(), target: "customers.id".into() }]);
+        assert_eq!(table.added_indexes.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), vec!["orders_new"]);
+        assert_eq!(table.removed_indexes.iter().map(|i| i.name.as_str()).collect::<Vec<_>>(), vec!["orders_old"]);
+        assert_eq!(table.changed_indexes.len(), 1);
+        assert!(!table.changed_indexes[0].old.unique && table.changed_indexes[0].new.unique);
+    }
+}
@@ -0,0 +1,327 @@
+// export/avro.rs
+// Apache Avro exporter with one record schema per table
+
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema, TableMetadata};
+use crate::db::types::LogicalType;
+use crate::ddl::generator::bare_table_name;
+use super::naming::{identifier, namespace, pascal_case};
+use super::registry::Exporter;
+use super::writer::{SerializationFormat, create_output_dir, write_file_in};
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeSet;
+use std::io::Write;
+
+/// Writes a directory with one `.avsc` record schema per table, named `<namespace>.<Record>.avsc`.
+/// Written to a stream, the records are emitted as a single JSON array.
+pub struct AvroExporter;
+
+impl Exporter for AvroExporter {
+    fn name(&self) -> &'static str {
+        "avro"
+    }
+
+    fn description(&self) -> &'static str {
+        "Avro record schema of each table (directory), or a JSON array of records"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "avsc"
+    }
+
+    fn default_output_path(&self) -> String {
+        "output_avro".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        // Named types may only be defined once in a schema list
+        let mut defined = BTreeSet::new();
+        let records: Vec<Record> = schema.db_metadata.sorted_table_names().into_iter()
+            .map(|name| record(name, &schema.db_metadata.tables[name], db_type(schema), &mut defined))
+            .collect();
+        SerializationFormat::Json.write(&records, out)
+    }
+
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        let root = create_output_dir(output_path)?;
+        for name in schema.db_metadata.sorted_table_names() {
+            let record = record(name, &schema.db_metadata.tables[name], db_type(schema), &mut BTreeSet::new());
+            write_file_in(root, format!("{}.{}.avsc", record.namespace, record.name), |out| SerializationFormat::Json.write(&record, out))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct Record {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: String,
+    namespace: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doc: Option<String>,
+    fields: Vec<Field>,
+}
+
+#[derive(Serialize)]
+struct Field {
+    name: String,
+    #[serde(rename = "type")]
+    field_type: Value,
+    /// `Some(Value::Null)` for nullable fields, which must default to null.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doc: Option<String>,
+}
+
+fn db_type(schema: &DbMetaDataSchema) -> &str {
+    schema.source_database_type.as_deref().unwrap_or_default()
+}
+
+/// Record for a table, named after the table in PascalCase within the namespace of its schema.
+/// `defined` holds the full names of enum types already defined, which are referenced by name.
+fn record(name: &str, table: &TableMetadata, db_type: &str, defined: &mut BTreeSet<String>) -> Record {
+    let namespace = namespace(name);
+    let fields = table.columns.iter()
+        .map(|col| {
+            let base = field_type(name, col, db_type, &namespace, defined);
+            let (field_type, default) = if col.nullable {
+                (json!(["null", base]), Some(Value::Null))
+            } else {
+                (base, None)
+            };
+            Field { name: identifier(&col.name), field_type, default, doc: col.description.clone() }
+        })
+        .collect();
+    Record {
+        kind: "record",
+        name: pascal_case(bare_table_name(name)),
+        namespace,
+        doc: table.description.clone(),
+        fields,
+    }
+}
+
+fn field_type(table_name: &str, col: &ColumnMetadata, db_type: &str, namespace: &str, defined: &mut BTreeSet<String>) -> Value {
+    let logical = |base: &str, logical_type: &str| json!({ "type": base, "logicalType": logical_type });
+    match LogicalType::from_column(col, db_type) {
+        LogicalType::Boolean => json!("boolean"),
+        LogicalType::SmallInt | LogicalType::Integer => json!("int"),
+        LogicalType::BigInt => json!("long"),
+        LogicalType::Float => json!("float"),
+        LogicalType::Double => json!("double"),
+        LogicalType::Decimal { precision: Some(precision), scale } => {
+            json!({ "type": "bytes", "logicalType": "decimal", "precision": precision, "scale": scale.unwrap_or(0) })
+        }
+        LogicalType::Date => logical("int", "date"),
+        LogicalType::Time => logical("long", "time-micros"),
+        LogicalType::Timestamp | LogicalType::TimestampTz => logical("long", "timestamp-micros"),
+        LogicalType::Uuid => logical("string", "uuid"),
+        LogicalType::Binary => json!("bytes"),
+        LogicalType::Array(_) => json!({ "type": "array", "items": "string" }),
+        LogicalType::Enum(values) if values.iter().all(|v| identifier(v) == *v) => {
+            let name = pascal_case(&col.enum_type_name(table_name).unwrap_or_else(|| col.name.clone()));
+            let full_name = format!("{}.{}", namespace, name);
+            if defined.insert(full_name.clone()) {
+                json!({ "type": "enum", "name": name, "symbols": values })
+            } else {
+                json!(full_name)
+            }
+        }
+        // Unconstrained decimals have no fixed scale, so they keep their exact text. Enums with
+        // labels that are not valid Avro symbols, intervals and JSON are strings as well.
+        _ => json!("string"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+    use std::fs;
+
+    #[test]
+    fn records_of_a_postgres_snapshot() {
+        assert_eq!(
+            render(&AvroExporter, &postgres_blog()),
+            r#"[
+  {
+    "type": "record",
+    "name": "Posts",
+    "namespace": "public",
+    "fields": [
+      {
+        "name": "id",
+        "type": "long"
+      },
+      {
+        "name": "user_id",
+        "type": [
+          "null",
+          "int"
+        ],
+        "default": null
+      },
+      {
+        "name": "title",
+        "type": "string"
+      },
+      {
+        "name": "posted_at",
+        "type": [
+          "null",
+          {
+            "logicalType": "timestamp-micros",
+            "type": "long"
+          }
+        ],
+        "default": null
+      }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "Users",
+    "namespace": "public",
+    "doc": "Registered users",
+    "fields": [
+      {
+        "name": "id",
+        "type": "int"
+      },
+      {
+        "name": "email",
+        "type": "string",
+        "doc": "Login"
+      },
+      {
+        "name": "mood",
+        "type": [
+          "null",
+          {
+            "name": "Mood",
+            "symbols": [
+              "happy",
+              "sad"
+            ],
+            "type": "enum"
+          }
+        ],
+        "default": null
+      },
+      {
+        "name": "profile",
+        "type": [
+          "null",
+          "string"
+        ],
+        "default": null
+      },
+      {
+        "name": "balance",
+        "type": {
+          "logicalType": "decimal",
+          "precision": 12,
+          "scale": 2,
+          "type": "bytes"
+        }
+      }
+    ]
+  }
+]
+"#
+        );
+    }
+
+    #[test]
+    fn records_of_a_mysql_snapshot() {
+        assert_eq!(
+            render(&AvroExporter, &mysql_shop()),
+            r#"[
+  {
+    "type": "record",
+    "name": "Customers",
+    "namespace": "shop",
+    "fields": [
+      {
+        "name": "id",
+        "type": "int"
+      },
+      {
+        "name": "email",
+        "type": "string"
+      }
+    ]
+  },
+  {
+    "type": "record",
+    "name": "Orders",
+    "namespace": "shop",
+    "doc": "Orders placed in the shop",
+    "fields": [
+      {
+        "name": "id",
+        "type": "long"
+      },
+      {
+        "name": "customer_id",
+        "type": "long"
+      },
+      {
+        "name": "status",
+        "type": {
+          "name": "OrdersStatus",
+          "symbols": [
+            "open",
+            "paid"
+          ],
+          "type": "enum"
+        }
+      },
+      {
+        "name": "code",
+        "type": [
+          "null",
+          "string"
+        ],
+        "default": null
+      },
+      {
+        "name": "total",
+        "type": {
+          "logicalType": "decimal",
+          "precision": 10,
+          "scale": 2,
+          "type": "bytes"
+        }
+      },
+      {
+        "name": "created",
+        "type": {
+          "logicalType": "timestamp-micros",
+          "type": "long"
+        }
+      }
+    ]
+  }
+]
+"#
+        );
+    }
+
+    #[test]
+    fn directories_get_one_schema_per_table() {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-avro-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        AvroExporter.write_to_path(&postgres_blog(), dir.to_str().unwrap()).unwrap();
+        let mut files: Vec<String> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+        files.sort();
+        assert_eq!(files, ["public.Posts.avsc", "public.Users.avsc"]);
+        // Each file defines the enums it uses itself
+        let users: Value = serde_json::from_str(&fs::read_to_string(dir.join("public.Users.avsc")).unwrap()).unwrap();
+        assert_eq!(users["fields"][2]["type"][1]["type"], "enum");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
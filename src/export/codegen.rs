@@ -0,0 +1,364 @@
+// export/codegen.rs
+// Type mapping and naming shared by the code generators, so the languages stay consistent
+
+use crate::db::models::{ColumnMetadata, DatabaseMetadata, TableMetadata};
+use crate::db::types::LogicalType;
+use crate::ddl::dialect::Dialect;
+use crate::ddl::generator::bare_table_name;
+use super::naming::{namespace, pascal_case, snake_case};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A type in a target language and the `(module, item)` imports it needs.
+#[derive(Debug, Clone)]
+pub struct TypeRef {
+    pub name: String,
+    pub imports: Vec<(&'static str, &'static str)>,
+}
+
+impl TypeRef {
+    fn new(name: impl Into<String>, imports: &[(&'static str, &'static str)]) -> Self {
+        Self { name: name.into(), imports: imports.to_vec() }
+    }
+
+    fn plain(name: impl Into<String>) -> Self {
+        Self::new(name, &[])
+    }
+
+    fn with_name(self, name: &str) -> Self {
+        Self { name: name.to_string(), ..self }
+    }
+}
+
+/// How a column type is represented by each generator. Keeping every language in one row makes
+/// it hard to change one mapping and forget the others.
+pub struct TypeMapping {
+    pub rust: TypeRef,
+    pub typescript: TypeRef,
+    pub python: TypeRef,
+    /// SQLAlchemy column type expression.
+    pub sqlalchemy: TypeRef,
+    pub java: TypeRef,
+}
+
+pub fn type_mapping(col: &ColumnMetadata, db_type: &str) -> TypeMapping {
+    logical_type_mapping(&LogicalType::from_column(col, db_type), col, db_type)
+}
+
+fn logical_type_mapping(logical: &LogicalType, col: &ColumnMetadata, db_type: &str) -> TypeMapping {
+    let chrono = |item| TypeRef::new(item, &[("chrono", item)]);
+    let python = |module, item| TypeRef::new(item, &[(module, item)]);
+    let sqlalchemy = |item| TypeRef::new(item, &[("sqlalchemy", item)]);
+    let java = |package, item| TypeRef::new(item, &[(package, item)]);
+    let text = |length: Option<i64>, fixed: bool| TypeMapping {
+        rust: TypeRef::plain("String"),
+        typescript: TypeRef::plain("string"),
+        python: TypeRef::plain("str"),
+        sqlalchemy: match (length, fixed) {
+            (Some(length), true) => TypeRef::new(format!("CHAR({})", length), &[("sqlalchemy", "CHAR")]),
+            (Some(length), false) => TypeRef::new(format!("String({})", length), &[("sqlalchemy", "String")]),
+            (None, _) => sqlalchemy("Text"),
+        },
+        java: TypeRef::plain("String"),
+    };
+
+    let postgres = matches!(Dialect::parse(db_type), Ok(Dialect::Postgres));
+
+    match logical {
+        LogicalType::Boolean => TypeMapping {
+            rust: TypeRef::plain("bool"),
+            typescript: TypeRef::plain("boolean"),
+            python: TypeRef::plain("bool"),
+            sqlalchemy: sqlalchemy("Boolean"),
+            java: TypeRef::plain("Boolean"),
+        },
+        LogicalType::SmallInt => TypeMapping {
+            rust: TypeRef::plain("i16"),
+            typescript: TypeRef::plain("number"),
+            python: TypeRef::plain("int"),
+            sqlalchemy: sqlalchemy("SmallInteger"),
+            java: TypeRef::plain("Short"),
+        },
+        LogicalType::Integer => TypeMapping {
+            rust: TypeRef::plain("i32"),
+            typescript: TypeRef::plain("number"),
+            python: TypeRef::plain("int"),
+            sqlalchemy: sqlalchemy("Integer"),
+            java: TypeRef::plain("Integer"),
+        },
+        LogicalType::BigInt => TypeMapping {
+            rust: TypeRef::plain("i64"),
+            typescript: TypeRef::plain("number"),
+            python: TypeRef::plain("int"),
+            sqlalchemy: sqlalchemy("BigInteger"),
+            java: TypeRef::plain("Long"),
+        },
+        LogicalType::Decimal { precision, scale } => TypeMapping {
+            // sqlx cannot decode SQLite values into rust_decimal, which stores them as REAL or TEXT anyway
+            rust: if db_type.eq_ignore_ascii_case("sqlite") {
+                TypeRef::plain("f64")
+            } else {
+                TypeRef::new("Decimal", &[("rust_decimal", "Decimal")])
+            },
+            // Decimals travel as strings in JSON to keep their precision
+            typescript: TypeRef::plain("string"),
+            python: python("decimal", "Decimal"),
+            sqlalchemy: match precision {
+                Some(precision) => TypeRef::new(format!("Numeric({}, {})", precision, scale.unwrap_or(0)), &[("sqlalchemy", "Numeric")]),
+                None => sqlalchemy("Numeric"),
+            },
+            java: java("java.math", "BigDecimal"),
+        },
+        LogicalType::Float => TypeMapping {
+            rust: TypeRef::plain("f32"),
+            typescript: TypeRef::plain("number"),
+            python: TypeRef::plain("float"),
+            sqlalchemy: sqlalchemy("Float"),
+            java: TypeRef::plain("Float"),
+        },
+        LogicalType::Double => TypeMapping {
+            rust: TypeRef::plain("f64"),
+            typescript: TypeRef::plain("number"),
+            python: TypeRef::plain("float"),
+            sqlalchemy: sqlalchemy("Double"),
+            java: TypeRef::plain("Double"),
+        },
+        LogicalType::Char(length) => text(*length, true),
+        LogicalType::Varchar(length) => text(*length, false),
+        LogicalType::Text => text(None, false),
+        LogicalType::Date => TypeMapping {
+            rust: chrono("NaiveDate"),
+            typescript: TypeRef::plain("string"),
+            python: python("datetime", "date"),
+            sqlalchemy: sqlalchemy("Date"),
+            java: java("java.time", "LocalDate"),
+        },
+        LogicalType::Time => TypeMapping {
+            rust: chrono("NaiveTime"),
+            typescript: TypeRef::plain("string"),
+            python: python("datetime", "time"),
+            sqlalchemy: sqlalchemy("Time"),
+            java: java("java.time", "LocalTime"),
+        },
+        LogicalType::Timestamp => TypeMapping {
+            rust: chrono("NaiveDateTime"),
+            typescript: TypeRef::plain("string"),
+            python: python("datetime", "datetime"),
+            sqlalchemy: sqlalchemy("DateTime"),
+            java: java("java.time", "LocalDateTime"),
+        },
+        LogicalType::TimestampTz => TypeMapping {
+            rust: TypeRef::new("DateTime<Utc>", &[("chrono", "DateTime"), ("chrono", "Utc")]),
+            typescript: TypeRef::plain("string"),
+            python: python("datetime", "datetime"),
+            sqlalchemy: TypeRef::new("DateTime(timezone=True)", &[("sqlalchemy", "DateTime")]),
+            java: java("java.time", "OffsetDateTime"),
+        },
+        LogicalType::Interval => TypeMapping {
+            rust: if postgres {
+                TypeRef::new("PgInterval", &[("sqlx::postgres::types", "PgInterval")])
+            } else {
+                TypeRef::plain("String")
+            },
+            typescript: TypeRef::plain("string"),
+            python: python("datetime", "timedelta"),
+            sqlalchemy: sqlalchemy("Interval"),
+            java: java("java.time", "Duration"),
+        },
+        LogicalType::Uuid => TypeMapping {
+            rust: TypeRef::new("Uuid", &[("uuid", "Uuid")]),
+            typescript: TypeRef::plain("string"),
+            python: python("uuid", "UUID"),
+            sqlalchemy: sqlalchemy("Uuid"),
+            java: java("java.util", "UUID"),
+        },
+        LogicalType::Json => TypeMapping {
+            rust: TypeRef::plain("serde_json::Value"),
+            typescript: TypeRef::plain("unknown"),
+            python: python("typing", "Any"),
+            sqlalchemy: sqlalchemy("JSON"),
+            java: TypeRef::plain("String"),
+        },
+        LogicalType::Binary => TypeMapping {
+            rust: TypeRef::plain("Vec<u8>"),
+            // Base64 in JSON
+            typescript: TypeRef::plain("string"),
+            python: TypeRef::plain("bytes"),
+            sqlalchemy: sqlalchemy("LargeBinary"),
+            java: TypeRef::plain("byte[]"),
+        },
+        LogicalType::Enum(values) => {
+            let quoted: Vec<String> = values.iter().map(|v| python_string(v)).collect();
+            let mut sqlalchemy_args = quoted.clone();
+            // Named PostgreSQL types; MySQL enums are inline
+            if !col.data_type.eq_ignore_ascii_case("enum") {
+                sqlalchemy_args.push(format!("name={}", python_string(&col.data_type)));
+            }
+            TypeMapping {
+                // The Rust generator declares a named enum type for the column
+                rust: TypeRef::plain("String"),
+                typescript: TypeRef::plain(values.iter().map(|v| format!("'{}'", v.replace('\\', "\\\\").replace('\'', "\\'"))).collect::<Vec<_>>().join(" | ")),
+                python: TypeRef::new(format!("Literal[{}]", quoted.join(", ")), &[("typing", "Literal")]),
+                sqlalchemy: TypeRef::new(format!("Enum({})", sqlalchemy_args.join(", ")), &[("sqlalchemy", "Enum")]),
+                java: TypeRef::plain("String"),
+            }
+        }
+        LogicalType::Array(Some(element)) => {
+            let element = logical_type_mapping(element, col, db_type);
+            let with_imports = |type_ref: &TypeRef, name: String, imports: &[(&'static str, &'static str)]| TypeRef {
+                name,
+                imports: imports.iter().copied().chain(type_ref.imports.iter().copied()).collect(),
+            };
+            // Union types need parentheses: `('a' | 'b')[]`
+            let typescript_array = if element.typescript.name.contains(' ') {
+                format!("({})[]", element.typescript.name)
+            } else {
+                format!("{}[]", element.typescript.name)
+            };
+            TypeMapping {
+                rust: with_imports(&element.rust, format!("Vec<{}>", element.rust.name), &[]),
+                typescript: with_imports(&element.typescript, typescript_array, &[]),
+                python: with_imports(&element.python, format!("List[{}]", element.python.name), &[("typing", "List")]),
+                sqlalchemy: with_imports(&element.sqlalchemy, format!("ARRAY({})", element.sqlalchemy.name), &[("sqlalchemy", "ARRAY")]),
+                java: with_imports(&element.java, format!("{}[]", element.java.name), &[]),
+            }
+        }
+        // Without an element type the values are read as text
+        LogicalType::Array(None) => TypeMapping {
+            rust: TypeRef::plain("Vec<String>"),
+            typescript: TypeRef::plain("string[]"),
+            python: python("typing", "List").with_name("List[str]"),
+            sqlalchemy: TypeRef::new("ARRAY(Text)", &[("sqlalchemy", "ARRAY"), ("sqlalchemy", "Text")]),
+            java: TypeRef::plain("String[]"),
+        },
+        // Unrecognized types are read as text
+        LogicalType::Other(_) => text(None, false),
+    }
+}
+
+/// Imports collected while generating a file, grouped by module.
+#[derive(Debug, Default)]
+pub struct Imports(BTreeMap<&'static str, BTreeSet<&'static str>>);
+
+impl Imports {
+    pub fn add(&mut self, module: &'static str, item: &'static str) {
+        self.0.entry(module).or_default().insert(item);
+    }
+
+    pub fn add_type(&mut self, type_ref: &TypeRef) {
+        for (module, item) in &type_ref.imports {
+            self.add(module, item);
+        }
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = (&'static str, Vec<&'static str>)> + '_ {
+        self.0.iter().map(|(module, items)| (*module, items.iter().copied().collect()))
+    }
+}
+
+/// Columns identifying a row: the primary key, or every column when the table has none, since
+/// ORMs cannot map a table without an identity.
+pub fn identity_columns(table: &TableMetadata) -> Vec<&str> {
+    if table.primary_keys.is_empty() {
+        table.columns.iter().map(|c| c.name.as_str()).collect()
+    } else {
+        table.primary_keys.iter().map(String::as_str).collect()
+    }
+}
+
+/// Class (or interface, struct) name of each table: the table name in PascalCase, prefixed with
+/// its schema when several schemas have a table of that name.
+pub fn class_names(metadata: &DatabaseMetadata) -> BTreeMap<&str, String> {
+    let mut by_name: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for name in metadata.sorted_table_names() {
+        by_name.entry(pascal_case(bare_table_name(name))).or_default().push(name.as_str());
+    }
+    let mut names = BTreeMap::new();
+    for (class, tables) in by_name {
+        for table in &tables {
+            let class = if tables.len() > 1 { format!("{}{}", pascal_case(&namespace(table)), class) } else { class.clone() };
+            names.insert(*table, class);
+        }
+    }
+    names
+}
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+    "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+/// Modules imported by the Python generators that are not part of the standard library.
+const PYTHON_THIRD_PARTY: &[&str] = &["pydantic", "sqlalchemy", "sqlalchemy.orm"];
+
+/// snake_case attribute name for a column; keywords get a trailing underscore.
+pub fn python_identifier(name: &str) -> String {
+    let identifier = snake_case(name);
+    if PYTHON_KEYWORDS.contains(&identifier.as_str()) {
+        format!("{}_", identifier)
+    } else {
+        identifier
+    }
+}
+
+/// `from module import a, b` lines, standard library modules first.
+pub fn python_imports(imports: &Imports) -> Vec<String> {
+    let (third_party, stdlib): (Vec<_>, Vec<_>) = imports.modules().partition(|(module, _)| PYTHON_THIRD_PARTY.contains(module));
+    let render = |(module, items): &(&str, Vec<&str>)| format!("from {} import {}", module, items.join(", "));
+    let mut lines: Vec<String> = stdlib.iter().map(render).collect();
+    if !lines.is_empty() && !third_party.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(third_party.iter().map(render));
+    lines
+}
+
+pub fn docstring(indent: &str, text: &str) -> String {
+    let text = text.replace('\\', "\\\\").replace('"', "\\\"");
+    if text.contains('\n') {
+        format!("{}\"\"\"\n{}\n{}\"\"\"", indent, text.lines().map(|l| format!("{}{}", indent, l)).collect::<Vec<_>>().join("\n"), indent)
+    } else {
+        format!("{}\"\"\"{}\"\"\"", indent, text)
+    }
+}
+
+/// Double-quoted Python (and JSON-compatible) string literal.
+pub fn python_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn column(data_type: &str) -> ColumnMetadata {
+        serde_json::from_value(json!({
+            "name": "value", "type": data_type, "nullable": false, "primary_key": false, "field_length": null,
+            "unique": null, "spec": null, "isChecked": true,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn arrays_map_their_element_type() {
+        let mapping = type_mapping(&column("timestamp with time zone[]"), "postgres");
+        assert_eq!(mapping.rust.name, "Vec<DateTime<Utc>>");
+        assert_eq!(mapping.rust.imports, vec![("chrono", "DateTime"), ("chrono", "Utc")]);
+        assert_eq!(mapping.typescript.name, "string[]");
+        assert_eq!(mapping.python.name, "List[datetime]");
+        assert_eq!(mapping.sqlalchemy.name, "ARRAY(DateTime(timezone=True))");
+        assert_eq!(mapping.java.name, "OffsetDateTime[]");
+
+        // Snapshots taken before the element type was recorded
+        assert_eq!(type_mapping(&column("ARRAY"), "postgres").rust.name, "Vec<String>");
+    }
+
+    #[test]
+    fn postgres_intervals_are_pg_intervals() {
+        let mapping = type_mapping(&column("interval"), "postgres");
+        assert_eq!(mapping.rust.name, "PgInterval");
+        assert_eq!(mapping.rust.imports, vec![("sqlx::postgres::types", "PgInterval")]);
+        assert_eq!(type_mapping(&column("interval"), "sqlite").rust.name, "String");
+    }
+}
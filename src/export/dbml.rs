@@ -0,0 +1,224 @@
+// export/dbml.rs
+// DBML (dbdiagram.io) exporter
+
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema, TableMetadata};
+use crate::db::types::LogicalType;
+use super::diagram::{Cardinality, relationships};
+use super::registry::Exporter;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Renders tables, enums, indexes and references as DBML, ready to paste into dbdiagram.io.
+pub struct DbmlExporter;
+
+impl Exporter for DbmlExporter {
+    fn name(&self) -> &'static str {
+        "dbml"
+    }
+
+    fn description(&self) -> &'static str {
+        "DBML for dbdiagram.io with tables, enums, indexes and references"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "dbml"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let metadata = &schema.db_metadata;
+        let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+        let mut enums: BTreeMap<String, &Vec<String>> = BTreeMap::new();
+
+        for name in metadata.sorted_table_names() {
+            let table = &metadata.tables[name];
+            writeln!(out, "Table {} {{", qualified_name(name))?;
+            for col in &table.columns {
+                let data_type = match col.enum_type_name(name) {
+                    Some(enum_name) => {
+                        if let Some(values) = &col.enum_values {
+                            enums.insert(enum_name.clone(), values);
+                        }
+                        quote(&enum_name)
+                    }
+                    None => column_type(col),
+                };
+                let settings = column_settings(table, col, db_type);
+                if settings.is_empty() {
+                    writeln!(out, "  {} {}", quote(&col.name), data_type)?;
+                } else {
+                    writeln!(out, "  {} {} [{}]", quote(&col.name), data_type, settings.join(", "))?;
+                }
+            }
+            write_indexes(out, table)?;
+            if let Some(description) = &table.description {
+                writeln!(out, "  Note: {}", string_literal(description))?;
+            }
+            writeln!(out, "}}")?;
+            writeln!(out)?;
+        }
+
+        for (name, values) in &enums {
+            writeln!(out, "Enum {} {{", quote(name))?;
+            for value in *values {
+                writeln!(out, "  {}", quote(value))?;
+            }
+            writeln!(out, "}}")?;
+            writeln!(out)?;
+        }
+
+        for rel in relationships(metadata) {
+            let operator = match rel.child_cardinality {
+                Cardinality::ZeroOrMany => ">",
+                _ => "-",
+            };
+            writeln!(
+                out,
+                "Ref: {}.{} {} {}.{}",
+                qualified_name(rel.child),
+                quote(rel.column),
+                operator,
+                qualified_name(rel.parent),
+                quote(rel.parent_column)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn column_settings(table: &TableMetadata, col: &ColumnMetadata, db_type: &str) -> Vec<String> {
+    let mut settings = Vec::new();
+    // Composite primary keys are declared in the Indexes block instead
+    if table.primary_keys.len() == 1 && col.primary_key {
+        settings.push("pk".to_string());
+    }
+    if !col.nullable {
+        settings.push("not null".to_string());
+    }
+    if !col.primary_key && table.is_unique_column(&col.name) {
+        settings.push("unique".to_string());
+    }
+    if let Some(default) = &col.default_value {
+        settings.push(format!("default: {}", default_literal(col, default, db_type)));
+    }
+    if let Some(description) = &col.description {
+        settings.push(format!("note: {}", string_literal(description)));
+    }
+    settings
+}
+
+fn write_indexes(out: &mut dyn Write, table: &TableMetadata) -> Result<()> {
+    if table.primary_keys.len() < 2 && table.indexes.is_empty() {
+        return Ok(());
+    }
+    writeln!(out)?;
+    writeln!(out, "  Indexes {{")?;
+    if table.primary_keys.len() > 1 {
+        writeln!(out, "    {} [pk]", index_columns(&table.primary_keys))?;
+    }
+    for index in &table.indexes {
+        writeln!(
+            out,
+            "    {} [{}name: {}]",
+            index_columns(&index.columns),
+            if index.unique { "unique, " } else { "" },
+            string_literal(&index.name)
+        )?;
+    }
+    writeln!(out, "  }}")?;
+    Ok(())
+}
+
+fn index_columns(columns: &[String]) -> String {
+    if columns.len() == 1 {
+        quote(&columns[0])
+    } else {
+        format!("({})", columns.iter().map(|c| quote(c)).collect::<Vec<_>>().join(", "))
+    }
+}
+
+/// `schema.table` keys become `"schema"."table"`; SQLite table names stay unqualified.
+fn qualified_name(name: &str) -> String {
+    match name.split_once('.') {
+        Some((schema, table)) => format!("{}.{}", quote(schema), quote(table)),
+        None => quote(name),
+    }
+}
+
+/// DBML accepts multi-word types like `character varying` only when quoted.
+fn column_type(col: &ColumnMetadata) -> String {
+    let base = match col.field_length {
+        Some(length) if !col.data_type.contains('(') => format!("{}({})", col.data_type, length),
+        _ => col.data_type.clone(),
+    };
+    if base.contains(' ') {
+        quote(&base)
+    } else {
+        base
+    }
+}
+
+/// Numbers and booleans stay bare, quoted SQL strings become DBML strings and anything
+/// else (function calls, sequences) is kept as a backtick expression. MySQL reports string
+/// defaults without quotes, so defaults of its string and enum columns are strings unless they
+/// are parenthesized expressions.
+fn default_literal(col: &ColumnMetadata, default: &str, db_type: &str) -> String {
+    let mysql = db_type.eq_ignore_ascii_case("mysql") || db_type.eq_ignore_ascii_case("mariadb");
+    let textual = matches!(
+        LogicalType::from_column(col, db_type),
+        LogicalType::Char(_) | LogicalType::Varchar(_) | LogicalType::Text | LogicalType::Enum(_)
+    );
+    if mysql && textual && !default.starts_with('(') {
+        return string_literal(default);
+    }
+    let value = default.trim();
+    let lower = value.to_lowercase();
+    if value.parse::<f64>().is_ok() || matches!(lower.as_str(), "true" | "false" | "null") {
+        return lower;
+    }
+    // Strip PostgreSQL casts such as 'new'::order_status
+    let literal = value.split("::").next().unwrap_or(value);
+    if literal.len() >= 2 && literal.starts_with('\'') && literal.ends_with('\'') {
+        return string_literal(&literal[1..literal.len() - 1].replace("''", "'"));
+    }
+    format!("`{}`", value.replace('`', "'"))
+}
+
+fn string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'").replace('\n', "\\n"))
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+
+    #[test]
+    fn unquoted_mysql_string_defaults_become_strings() {
+        let dbml = render(&DbmlExporter, &mysql_shop());
+        for line in [
+            "  \"status\" \"orders_status\" [not null, default: 'open']",
+            "  \"code\" varchar(10) [default: '0']",
+            "  \"total\" decimal [not null, default: 0.00]",
+            "  \"created\" datetime [not null, default: `CURRENT_TIMESTAMP`]",
+        ] {
+            assert!(dbml.contains(line), "{} missing from\n{}", line, dbml);
+        }
+    }
+
+    #[test]
+    fn postgres_defaults_keep_their_quoting() {
+        let dbml = render(&DbmlExporter, &postgres_blog());
+        for line in [
+            "  \"mood\" \"mood\" [default: 'happy']",
+            "  \"title\" text [not null, default: 'untitled']",
+            "  \"posted_at\" \"timestamp with time zone\" [default: `now()`]",
+        ] {
+            assert!(dbml.contains(line), "{} missing from\n{}", line, dbml);
+        }
+    }
+}
@@ -0,0 +1,257 @@
+// export/dbt.rs
+// dbt exporter: sources.yml with generated tests, plus optional staging model stubs
+
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema, TableMetadata};
+use crate::ddl::generator::bare_table_name;
+use super::naming::{namespace, snake_case};
+use super::registry::Exporter;
+use super::writer::{create_output_dir, write_file_in};
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::io::Write;
+
+const SOURCES_FILE: &str = "sources.yml";
+
+/// Options for the dbt export, set via `--dbt-*` flags.
+#[derive(Debug, Clone, Default)]
+pub struct DbtOptions {
+    /// Also write a `staging/stg_<source>__<table>.sql` model per table.
+    pub staging: bool,
+}
+
+/// Writes a directory with `sources.yml`, declaring one dbt source per schema with column tests
+/// derived from the metadata: `not_null` for NOT NULL and primary key columns, `unique` for
+/// single-column keys, `relationships` for foreign keys and `accepted_values` for enums. Written
+/// to a stream, only `sources.yml` is produced.
+pub struct DbtExporter {
+    pub options: DbtOptions,
+}
+
+impl Exporter for DbtExporter {
+    fn name(&self) -> &'static str {
+        "dbt"
+    }
+
+    fn description(&self) -> &'static str {
+        "dbt sources.yml with tests from constraints (directory), optionally with staging models"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "yml"
+    }
+
+    fn default_output_path(&self) -> String {
+        "output_dbt".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        serde_yaml::to_writer(out, &sources(schema))?;
+        Ok(())
+    }
+
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        let root = create_output_dir(output_path)?;
+        write_file_in(root, SOURCES_FILE, |out| self.write(schema, out))?;
+
+        if self.options.staging {
+            let staging = root.join("staging");
+            fs::create_dir_all(&staging)?;
+            for name in schema.db_metadata.sorted_table_names() {
+                let file_name = format!("stg_{}__{}.sql", namespace(name).replace('.', "_"), snake_case(bare_table_name(name)));
+                let model = staging_model(name, &schema.db_metadata.tables[name]);
+                write_file_in(&staging, file_name, |out| Ok(out.write_all(model.as_bytes())?))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The `sources.yml` document; sources are named after the schema (`main` for SQLite).
+fn sources(schema: &DbMetaDataSchema) -> Value {
+    let metadata = &schema.db_metadata;
+    let mut sources: Vec<(String, Option<&str>, Vec<Value>)> = Vec::new();
+    for name in metadata.sorted_table_names() {
+        let source = namespace(name);
+        let table = &metadata.tables[name];
+        let mut entry = Mapping::new();
+        entry.insert("name".into(), bare_table_name(name).into());
+        if let Some(description) = &table.description {
+            entry.insert("description".into(), description.as_str().into());
+        }
+        let columns: Vec<Value> = table.columns.iter().map(|col| column(schema, table, col)).collect();
+        entry.insert("columns".into(), Value::Sequence(columns));
+
+        match sources.last_mut() {
+            Some((last, _, tables)) if *last == source => tables.push(Value::Mapping(entry)),
+            _ => sources.push((source, name.rsplit_once('.').map(|(s, _)| s), vec![Value::Mapping(entry)])),
+        }
+    }
+
+    let sources: Vec<Value> = sources.into_iter()
+        .map(|(source, schema_name, tables)| {
+            let mut map = Mapping::new();
+            map.insert("name".into(), source.into());
+            if let Some(schema_name) = schema_name {
+                map.insert("schema".into(), schema_name.into());
+            }
+            map.insert("tables".into(), Value::Sequence(tables));
+            Value::Mapping(map)
+        })
+        .collect();
+    let mut document = Mapping::new();
+    document.insert("version".into(), 2.into());
+    document.insert("sources".into(), Value::Sequence(sources));
+    Value::Mapping(document)
+}
+
+fn column(schema: &DbMetaDataSchema, table: &TableMetadata, col: &ColumnMetadata) -> Value {
+    let mut tests = Vec::new();
+    // SQLite reports primary key columns as nullable
+    if !col.nullable || table.primary_keys.contains(&col.name) {
+        tests.push(Value::from("not_null"));
+    }
+    if table.is_unique_column(&col.name) {
+        tests.push(Value::from("unique"));
+    }
+    if let Some((parent, parent_column)) = table.foreign_keys.get(&col.name).and_then(|t| schema.db_metadata.resolve_foreign_key(t)) {
+        let mut relationship = Mapping::new();
+        relationship.insert("to".into(), format!("source('{}', '{}')", namespace(parent), bare_table_name(parent)).into());
+        relationship.insert("field".into(), parent_column.into());
+        tests.push(single("relationships", Value::Mapping(relationship)));
+    }
+    if let Some(values) = &col.enum_values {
+        let values = values.iter().map(|v| Value::from(v.as_str())).collect();
+        tests.push(single("accepted_values", single("values", Value::Sequence(values))));
+    }
+
+    let mut entry = Mapping::new();
+    entry.insert("name".into(), col.name.as_str().into());
+    if let Some(description) = &col.description {
+        entry.insert("description".into(), description.as_str().into());
+    }
+    if !tests.is_empty() {
+        entry.insert("tests".into(), Value::Sequence(tests));
+    }
+    Value::Mapping(entry)
+}
+
+/// Staging model selecting every column from the source, renaming columns to snake_case.
+fn staging_model(name: &str, table: &TableMetadata) -> String {
+    let columns: Vec<String> = table.columns.iter()
+        .map(|col| {
+            let renamed = snake_case(&col.name);
+            if renamed == col.name {
+                format!("        {}", col.name)
+            } else {
+                format!("        {{{{ adapter.quote(\"{}\") }}}} as {}", col.name.replace('"', "\\\""), renamed)
+            }
+        })
+        .collect();
+    format!(
+        "with source as (\n\n    select * from {{{{ source('{}', '{}') }}}}\n\n),\n\nrenamed as (\n\n    select\n{}\n    from source\n\n)\n\nselect * from renamed\n",
+        namespace(name),
+        bare_table_name(name),
+        columns.join(",\n")
+    )
+}
+
+fn single(key: &str, value: Value) -> Value {
+    let mut map = Mapping::new();
+    map.insert(key.into(), value);
+    Value::Mapping(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+
+    #[test]
+    fn sources_with_tests_from_constraints() {
+        let exporter = DbtExporter { options: DbtOptions::default() };
+        assert_eq!(
+            render(&exporter, &postgres_blog()),
+            r#"version: 2
+sources:
+- name: public
+  schema: public
+  tables:
+  - name: posts
+    columns:
+    - name: id
+      tests:
+      - not_null
+      - unique
+    - name: user_id
+      tests:
+      - relationships:
+          to: source('public', 'users')
+          field: id
+    - name: title
+      tests:
+      - not_null
+    - name: posted_at
+  - name: users
+    description: Registered users
+    columns:
+    - name: id
+      tests:
+      - not_null
+      - unique
+    - name: email
+      description: Login
+      tests:
+      - not_null
+      - unique
+    - name: mood
+      tests:
+      - accepted_values:
+          values:
+          - happy
+          - sad
+    - name: profile
+    - name: balance
+      tests:
+      - not_null
+"#
+        );
+    }
+
+    #[test]
+    fn staging_models() {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-dbt-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let exporter = DbtExporter { options: DbtOptions { staging: true } };
+        exporter.write_to_path(&mysql_shop(), dir.to_str().unwrap()).unwrap();
+        assert_eq!(fs::read_to_string(dir.join(SOURCES_FILE)).unwrap(), render(&exporter, &mysql_shop()));
+        let mut models: Vec<String> = fs::read_dir(dir.join("staging")).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+        models.sort();
+        assert_eq!(models, ["stg_shop__customers.sql", "stg_shop__orders.sql"]);
+        assert_eq!(
+            fs::read_to_string(dir.join("staging").join("stg_shop__orders.sql")).unwrap(),
+            r#"with source as (
+
+    select * from {{ source('shop', 'orders') }}
+
+),
+
+renamed as (
+
+    select
+        id,
+        customer_id,
+        status,
+        code,
+        total,
+        created
+    from source
+
+)
+
+select * from renamed
+"#
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+// export/diagram.rs
+// Relationship graph shared by the diagram exporters (Mermaid, Graphviz DOT)
+
+use crate::db::models::DatabaseMetadata;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Options for diagram exports, set via `--diagram-*` flags.
+#[derive(Debug, Clone, Default)]
+pub struct DiagramOptions {
+    /// Tables to start from; empty means the whole schema.
+    pub tables: Vec<String>,
+    /// How many foreign key hops of neighbors around `tables` to include.
+    pub hops: usize,
+    /// Draw entity boxes without their columns.
+    pub hide_columns: bool,
+}
+
+/// How many rows on one side of a relationship match a row on the other side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cardinality {
+    ExactlyOne,
+    ZeroOrOne,
+    ZeroOrMany,
+}
+
+/// A foreign key edge from `child.column` to `parent.parent_column`.
+#[derive(Debug, Clone)]
+pub struct Relationship<'a> {
+    pub child: &'a str,
+    pub column: &'a str,
+    pub parent: &'a str,
+    pub parent_column: &'a str,
+    /// Parents per child row: exactly one for NOT NULL columns, zero or one otherwise.
+    pub parent_cardinality: Cardinality,
+    /// Children per parent row: zero or one when the column is unique, zero or many otherwise.
+    pub child_cardinality: Cardinality,
+}
+
+/// Tables to draw, sorted, after applying the table filter and neighbor hops.
+pub fn select_tables<'a>(metadata: &'a DatabaseMetadata, options: &DiagramOptions) -> Vec<&'a str> {
+    let all = metadata.sorted_table_names();
+    if options.tables.is_empty() {
+        return all.into_iter().map(|n| n.as_str()).collect();
+    }
+
+    let mut selected: BTreeSet<&str> = all.iter()
+        .map(|n| n.as_str())
+        .filter(|n| options.tables.iter().any(|t| table_matches(n, t)))
+        .collect();
+    let mut queue: VecDeque<(&str, usize)> = selected.iter().map(|n| (*n, 0)).collect();
+    let edges = relationships(metadata);
+    while let Some((table, depth)) = queue.pop_front() {
+        if depth >= options.hops {
+            continue;
+        }
+        for rel in &edges {
+            let neighbor = if rel.child == table {
+                rel.parent
+            } else if rel.parent == table {
+                rel.child
+            } else {
+                continue;
+            };
+            if selected.insert(neighbor) {
+                queue.push_back((neighbor, depth + 1));
+            }
+        }
+    }
+    selected.into_iter().collect()
+}
+
+/// All foreign keys between tables present in the metadata, sorted by child table and column.
+pub fn relationships(metadata: &DatabaseMetadata) -> Vec<Relationship<'_>> {
+    let mut rels = Vec::new();
+    for name in metadata.sorted_table_names() {
+        let table = &metadata.tables[name];
+        for (column, target) in table.sorted_foreign_keys() {
+            let Some((parent, parent_column)) = metadata.resolve_foreign_key(target) else {
+                continue;
+            };
+            let nullable = table.columns.iter().find(|c| &c.name == column).map_or(true, |c| c.nullable);
+            rels.push(Relationship {
+                child: name,
+                column,
+                parent,
+                parent_column,
+                parent_cardinality: if nullable { Cardinality::ZeroOrOne } else { Cardinality::ExactlyOne },
+                child_cardinality: if table.is_unique_column(column) { Cardinality::ZeroOrOne } else { Cardinality::ZeroOrMany },
+            });
+        }
+    }
+    rels
+}
+
+/// Accepts either the full table key (`schema.table`) or the bare table name.
+pub fn table_matches(name: &str, filter: &str) -> bool {
+    name == filter || name.rsplit_once('.').is_some_and(|(_, table)| table == filter)
+}
+
+/// Identifiers of the names drawn in one diagram. Characters that diagram languages do not accept
+/// become `_` and names starting with a digit get a `_` prefix; names that still collide, such as
+/// `public.a-b` and `public.a_b`, get a numeric suffix in the order they were added.
+#[derive(Debug, Default)]
+pub struct DiagramIdentifiers {
+    ids: HashMap<String, String>,
+    used: HashSet<String>,
+}
+
+impl DiagramIdentifiers {
+    pub fn new<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut identifiers = Self::default();
+        for name in names {
+            identifiers.add(name);
+        }
+        identifiers
+    }
+
+    pub fn add(&mut self, name: &str) {
+        if self.ids.contains_key(name) {
+            return;
+        }
+        let mut base: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect();
+        if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+            base.insert(0, '_');
+        }
+        let mut id = base.clone();
+        let mut suffix = 1;
+        while !self.used.insert(id.clone()) {
+            suffix += 1;
+            id = format!("{}_{}", base, suffix);
+        }
+        self.ids.insert(name.to_string(), id);
+    }
+
+    /// Identifier of a name passed to `new` or `add`.
+    pub fn get(&self, name: &str) -> &str {
+        &self.ids[name]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_are_unique_and_valid() {
+        let ids = DiagramIdentifiers::new(["public.a-b", "public.a_b", "public.a_b_2", "2024_sales", "users"]);
+        assert_eq!(ids.get("public.a-b"), "public_a_b");
+        assert_eq!(ids.get("public.a_b"), "public_a_b_2");
+        assert_eq!(ids.get("public.a_b_2"), "public_a_b_2_2");
+        assert_eq!(ids.get("2024_sales"), "_2024_sales");
+        assert_eq!(ids.get("users"), "users");
+    }
+}
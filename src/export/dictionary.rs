@@ -0,0 +1,291 @@
+// export/dictionary.rs
+// Data dictionary exporters for spreadsheets: CSV and Excel (xlsx)
+
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema, TableMetadata};
+use crate::db::types::LogicalType;
+use crate::ddl::generator::bare_table_name;
+use super::registry::Exporter;
+use anyhow::Result;
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+const COLUMN_HEADERS: [&str; 8] = ["Table", "Column", "Type", "Length", "Nullable", "Primary key", "Foreign key", "Description"];
+const OVERVIEW_HEADERS: [&str; 8] =
+    ["Schema", "Table", "Columns", "Primary key", "Foreign keys", "Referenced by", "Indexes", "Description"];
+const OVERVIEW_SHEET: &str = "Overview";
+
+/// Data dictionary with one row per column: its schema followed by the `COLUMN_HEADERS` fields.
+pub struct CsvExporter;
+
+impl Exporter for CsvExporter {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn description(&self) -> &'static str {
+        "Data dictionary with one row per column"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+        let mut writer = csv::Writer::from_writer(out);
+        writer.write_record(std::iter::once("Schema").chain(COLUMN_HEADERS))?;
+        for name in schema.db_metadata.sorted_table_names() {
+            let table = &schema.db_metadata.tables[name];
+            for col in &table.columns {
+                writer.write_record(std::iter::once(schema_of(name).to_string()).chain(column_row(name, table, col, db_type)))?;
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// An overview sheet with one row of statistics per table, followed by a sheet per schema with
+/// one row per column.
+pub struct XlsxExporter;
+
+impl Exporter for XlsxExporter {
+    fn name(&self) -> &'static str {
+        "xlsx"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["excel"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Excel data dictionary: overview sheet with table statistics plus one sheet per schema"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "xlsx"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let metadata = &schema.db_metadata;
+        let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+        let header = Format::new().set_bold();
+        let mut workbook = Workbook::new();
+
+        let overview = workbook.add_worksheet().set_name(OVERVIEW_SHEET)?;
+        let mut schemas: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+        let mut rows = Vec::new();
+        for name in metadata.sorted_table_names() {
+            let table = &metadata.tables[name];
+            schemas.entry(schema_of(name).to_string()).or_default().push(name);
+            rows.push(vec![
+                schema_of(name).to_string(),
+                bare_table_name(name).to_string(),
+                table.columns.len().to_string(),
+                table.primary_keys.join(", "),
+                table.foreign_keys.len().to_string(),
+                metadata.referenced_by(name).len().to_string(),
+                table.indexes.len().to_string(),
+                table.description.clone().unwrap_or_default(),
+            ]);
+        }
+        // Counts are written as numbers so they can be summed and sorted
+        write_sheet(overview, &OVERVIEW_HEADERS, &rows, &[2, 4, 5, 6], &header)?;
+
+        let mut used = BTreeSet::from([OVERVIEW_SHEET.to_lowercase()]);
+        for (schema_name, tables) in &schemas {
+            let rows: Vec<Vec<String>> = tables.iter()
+                .flat_map(|name| {
+                    let table = &metadata.tables[name.as_str()];
+                    table.columns.iter().map(move |col| column_row(name, table, col, db_type).to_vec())
+                })
+                .collect();
+            let sheet = workbook.add_worksheet().set_name(sheet_name(schema_name, &mut used))?;
+            write_sheet(sheet, &COLUMN_HEADERS, &rows, &[], &header)?;
+        }
+
+        out.write_all(&workbook.save_to_buffer()?)?;
+        Ok(())
+    }
+}
+
+/// Schema part of a table key; SQLite tables live in `main`.
+fn schema_of(table_name: &str) -> &str {
+    table_name.rsplit_once('.').map_or("main", |(schema, _)| schema)
+}
+
+/// Length of character types, `precision,scale` of decimals; SQLite only has them in the type name.
+fn column_row(table_name: &str, table: &TableMetadata, col: &ColumnMetadata, db_type: &str) -> [String; 8] {
+    let length = match LogicalType::from_column(col, db_type) {
+        LogicalType::Char(Some(length)) | LogicalType::Varchar(Some(length)) => length.to_string(),
+        LogicalType::Decimal { precision: Some(precision), scale: Some(scale) } => format!("{},{}", precision, scale),
+        LogicalType::Decimal { precision: Some(precision), scale: None } => precision.to_string(),
+        _ => String::new(),
+    };
+    let yes_no = |flag: bool| if flag { "yes" } else { "no" }.to_string();
+    [
+        bare_table_name(table_name).to_string(),
+        col.name.clone(),
+        col.data_type.clone(),
+        length,
+        yes_no(col.nullable),
+        yes_no(table.primary_keys.contains(&col.name)),
+        table.foreign_keys.get(&col.name).cloned().unwrap_or_default(),
+        col.description.clone().unwrap_or_default(),
+    ]
+}
+
+/// Writes a bold, frozen header row with filters, the rows, and fits the column widths.
+/// Columns listed in `numeric` are written as numbers.
+fn write_sheet(sheet: &mut Worksheet, headers: &[&str], rows: &[Vec<String>], numeric: &[u16], header: &Format) -> Result<()> {
+    for (col, title) in (0u16..).zip(headers) {
+        sheet.write_string_with_format(0, col, *title, header)?;
+    }
+    for (row, values) in (1u32..).zip(rows) {
+        for (col, value) in (0u16..).zip(values) {
+            match value.parse::<f64>() {
+                Ok(number) if numeric.contains(&col) => sheet.write_number(row, col, number)?,
+                _ => sheet.write_string(row, col, value)?,
+            };
+        }
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofilter(0, 0, rows.len() as u32, headers.len() as u16 - 1)?;
+    sheet.autofit();
+    Ok(())
+}
+
+/// Excel sheet names are at most 31 characters, cannot contain `[]:*?/\` and must be unique
+/// regardless of case.
+fn sheet_name(schema_name: &str, used: &mut BTreeSet<String>) -> String {
+    let base: String = schema_name
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+        .take(31)
+        .collect();
+    let base = base.trim_matches('\'').to_string();
+    let mut name = if base.is_empty() { "schema".to_string() } else { base.clone() };
+    let mut n = 2;
+    while !used.insert(name.to_lowercase()) {
+        let suffix = format!(" ({})", n);
+        name = format!("{}{}", base.chars().take(31 - suffix.len()).collect::<String>(), suffix);
+        n += 1;
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+    use std::io::{Cursor, Read};
+
+    /// Sheet names and their rows, strings quoted and numbers bare, read back from the workbook.
+    fn read_workbook(schema: &DbMetaDataSchema) -> Vec<(String, Vec<String>)> {
+        let mut out = Vec::new();
+        XlsxExporter.write(schema, &mut out).unwrap();
+        let mut zip = zip::ZipArchive::new(Cursor::new(out)).unwrap();
+        let mut part = |name: &str| {
+            let mut xml = String::new();
+            zip.by_name(name).unwrap().read_to_string(&mut xml).unwrap();
+            xml
+        };
+        let between = |xml: &str, start: &str, end: &str| -> Vec<String> {
+            xml.split(start).skip(1).map(|s| s[..s.find(end).unwrap()].to_string()).collect()
+        };
+        let strings: Vec<String> = between(&part("xl/sharedStrings.xml"), "<si><t", "</t>")
+            .iter()
+            .map(|s| s[s.find('>').unwrap() + 1..].replace("&lt;", "<").replace("&gt;", ">").replace("&amp;", "&"))
+            .collect();
+        let sheets = between(&part("xl/workbook.xml"), "<sheet name=\"", "\"");
+        (1..=sheets.len())
+            .map(|n| part(&format!("xl/worksheets/sheet{}.xml", n)))
+            .zip(sheets)
+            .map(|(xml, name)| {
+                let rows = between(&xml, "<row ", "</row>").into_iter().map(|row| {
+                    let mut cells = vec![String::new(); 8];
+                    for cell in between(&row, "<c r=\"", "</c>") {
+                        let column = (cell.as_bytes()[0] - b'A') as usize;
+                        let value = &between(&cell, "<v>", "</v>")[0];
+                        cells[column] = if cell.contains("t=\"s\"") {
+                            format!("{:?}", strings[value.parse::<usize>().unwrap()])
+                        } else {
+                            value.clone()
+                        };
+                    }
+                    cells.join(",")
+                });
+                (name, rows.collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn csv_has_a_row_per_column() {
+        assert_eq!(
+            render(&CsvExporter, &mysql_shop()),
+            r#"Schema,Table,Column,Type,Length,Nullable,Primary key,Foreign key,Description
+shop,customers,id,int,,no,yes,,
+shop,customers,email,varchar,120,no,no,,
+shop,orders,id,bigint,,no,yes,,
+shop,orders,customer_id,int,,no,no,shop.customers.id,
+shop,orders,status,enum,,no,no,,
+shop,orders,code,varchar,10,yes,no,,
+shop,orders,total,decimal,"10,2",no,no,,
+shop,orders,created,datetime,,no,no,,
+"#
+        );
+    }
+
+    #[test]
+    fn xlsx_has_an_overview_and_a_sheet_per_schema() {
+        let mut snapshot = postgres_blog();
+        let mut audit = snapshot.db_metadata.tables["public.posts"].clone();
+        audit.foreign_keys.clear();
+        audit.description = Some("Changes".to_string());
+        snapshot.db_metadata.tables.insert("audit.posts".to_string(), audit);
+        let workbook = read_workbook(&snapshot);
+        let sheets: Vec<&str> = workbook.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(sheets, ["Overview", "audit", "public"]);
+        assert_eq!(
+            workbook[0].1.join("\n"),
+            r#""Schema","Table","Columns","Primary key","Foreign keys","Referenced by","Indexes","Description"
+"audit","posts",4,"id",0,0,1,"Changes"
+"public","posts",4,"id",1,0,1,
+"public","users",5,"id",0,1,1,"Registered users""#
+        );
+        assert_eq!(
+            workbook[1].1.join("\n"),
+            r#""Table","Column","Type","Length","Nullable","Primary key","Foreign key","Description"
+"posts","id","bigint",,"no","yes",,
+"posts","user_id","integer",,"yes","no",,
+"posts","title","text",,"no","no",,
+"posts","posted_at","timestamp with time zone",,"yes","no",,"#
+        );
+        assert_eq!(
+            workbook[2].1.join("\n"),
+            r#""Table","Column","Type","Length","Nullable","Primary key","Foreign key","Description"
+"posts","id","bigint",,"no","yes",,
+"posts","user_id","integer",,"yes","no","public.users.id",
+"posts","title","text",,"no","no",,
+"posts","posted_at","timestamp with time zone",,"yes","no",,
+"users","id","integer",,"no","yes",,
+"users","email","character varying","120","no","no",,"Login"
+"users","mood","mood",,"yes","no",,
+"users","profile","json",,"yes","no",,
+"users","balance","numeric","12,2","no","no",,"#
+        );
+    }
+
+    #[test]
+    fn sheet_names_are_valid_and_unique() {
+        let mut used = BTreeSet::from([OVERVIEW_SHEET.to_lowercase()]);
+        let names: Vec<String> = ["overview", "a/b", "A_B", "'", &"x".repeat(40), &"x".repeat(35)]
+            .iter()
+            .map(|name| sheet_name(name, &mut used))
+            .collect();
+        let (truncated, suffixed) = ("x".repeat(31), format!("{} (2)", "x".repeat(27)));
+        assert_eq!(names, ["overview (2)", "a_b", "A_B (2)", "schema", &truncated, &suffixed]);
+    }
+}
@@ -0,0 +1,159 @@
+// export/dot.rs
+// Graphviz DOT exporter
+
+use crate::db::models::DbMetaDataSchema;
+use super::diagram::{Cardinality, DiagramIdentifiers, DiagramOptions, relationships, select_tables};
+use std::collections::HashMap;
+use super::registry::Exporter;
+use anyhow::Result;
+use std::io::Write;
+
+/// Renders tables as HTML-like record nodes and foreign keys as crow's foot edges,
+/// pointing from the referencing column to the referenced table.
+pub struct DotExporter {
+    pub options: DiagramOptions,
+}
+
+impl Exporter for DotExporter {
+    fn name(&self) -> &'static str {
+        "dot"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["graphviz"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Graphviz DOT graph of tables and foreign keys"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "dot"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let metadata = &schema.db_metadata;
+        let tables = select_tables(metadata, &self.options);
+        let ids = DiagramIdentifiers::new(tables.iter().copied());
+        let rels = relationships(metadata);
+        // Ports are the column identifiers within each table; edges may name columns the snapshot does not list
+        let mut ports: HashMap<&str, DiagramIdentifiers> = tables.iter()
+            .map(|name| (*name, DiagramIdentifiers::new(metadata.tables[*name].columns.iter().map(|c| c.name.as_str()))))
+            .collect();
+        for rel in &rels {
+            ports.entry(rel.child).or_default().add(rel.column);
+            ports.entry(rel.parent).or_default().add(rel.parent_column);
+        }
+
+        writeln!(out, "digraph schema {{")?;
+        writeln!(out, "    rankdir=LR;")?;
+        writeln!(out, "    node [shape=plaintext, fontname=\"Helvetica\", fontsize=10];")?;
+        writeln!(out, "    edge [dir=both, fontname=\"Helvetica\", fontsize=9];")?;
+        for name in &tables {
+            let table = &metadata.tables[*name];
+            writeln!(out, "    {} [label=<", ids.get(name))?;
+            writeln!(out, "        <table border=\"0\" cellborder=\"1\" cellspacing=\"0\">")?;
+            writeln!(out, "        <tr><td bgcolor=\"#dfe8f5\" colspan=\"2\"><b>{}</b></td></tr>", escape_label(name))?;
+            if !self.options.hide_columns {
+                for col in &table.columns {
+                    let mut markers = Vec::new();
+                    if col.primary_key {
+                        markers.push("PK");
+                    }
+                    if table.foreign_keys.contains_key(&col.name) {
+                        markers.push("FK");
+                    }
+                    let name_cell = if col.primary_key {
+                        format!("<u>{}</u>", escape_label(&col.name))
+                    } else {
+                        escape_label(&col.name)
+                    };
+                    writeln!(
+                        out,
+                        "        <tr><td port=\"{}\" align=\"left\">{}{}</td><td align=\"left\">{}{}</td></tr>",
+                        ports[*name].get(&col.name),
+                        if markers.is_empty() { String::new() } else { format!("{} ", markers.join(",")) },
+                        name_cell,
+                        escape_label(&col.data_type),
+                        if col.nullable { "" } else { " NOT NULL" }
+                    )?;
+                }
+            }
+            writeln!(out, "        </table>>];")?;
+        }
+
+        for rel in rels {
+            if !tables.contains(&rel.child) || !tables.contains(&rel.parent) {
+                continue;
+            }
+            let arrowhead = match rel.parent_cardinality {
+                Cardinality::ExactlyOne => "teetee",
+                _ => "teeodot",
+            };
+            let arrowtail = match rel.child_cardinality {
+                Cardinality::ZeroOrMany => "crowodot",
+                _ => "teeodot",
+            };
+            let (from, to) = if self.options.hide_columns {
+                (ids.get(rel.child).to_string(), ids.get(rel.parent).to_string())
+            } else {
+                (
+                    format!("{}:{}", ids.get(rel.child), ports[rel.child].get(rel.column)),
+                    format!("{}:{}", ids.get(rel.parent), ports[rel.parent].get(rel.parent_column)),
+                )
+            };
+            writeln!(
+                out,
+                "    {} -> {} [arrowhead={}, arrowtail={}, label=\"{}\"];",
+                from,
+                to,
+                arrowhead,
+                arrowtail,
+                rel.column.replace('"', "\\\"")
+            )?;
+        }
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{awkward_names, render};
+
+    #[test]
+    fn nodes_and_ports_get_unique_identifiers() {
+        let exporter = DotExporter { options: DiagramOptions::default() };
+        assert_eq!(
+            render(&exporter, &awkward_names()),
+            r##"digraph schema {
+    rankdir=LR;
+    node [shape=plaintext, fontname="Helvetica", fontsize=10];
+    edge [dir=both, fontname="Helvetica", fontsize=9];
+    _2024_sales [label=<
+        <table border="0" cellborder="1" cellspacing="0">
+        <tr><td bgcolor="#dfe8f5" colspan="2"><b>2024_sales</b></td></tr>
+        <tr><td port="id" align="left">PK <u>id</u></td><td align="left">integer NOT NULL</td></tr>
+        </table>>];
+    a_b [label=<
+        <table border="0" cellborder="1" cellspacing="0">
+        <tr><td bgcolor="#dfe8f5" colspan="2"><b>a-b</b></td></tr>
+        <tr><td port="id" align="left">PK <u>id</u></td><td align="left">integer NOT NULL</td></tr>
+        </table>>];
+    a_b_2 [label=<
+        <table border="0" cellborder="1" cellspacing="0">
+        <tr><td bgcolor="#dfe8f5" colspan="2"><b>a_b</b></td></tr>
+        <tr><td port="id" align="left">PK <u>id</u></td><td align="left">integer NOT NULL</td></tr>
+        <tr><td port="a_b_id" align="left">FK a-b id</td><td align="left">integer</td></tr>
+        </table>>];
+    a_b_2:a_b_id -> a_b:id [arrowhead=teeodot, arrowtail=crowodot, label="a-b id"];
+}
+"##
+        );
+    }
+}
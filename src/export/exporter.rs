@@ -2,21 +2,45 @@
 // Handles exporting database metadata to files
 
 use crate::db::models::DbMetaDataSchema;
-use anyhow::Result;
-use std::fs::File;
-use std::io::Write;
+use super::registry::{Exporter, ExporterRegistry};
+use super::split::write_split;
+use super::writer::{STDOUT, SerializationFormat, write_file};
+use anyhow::{Result, bail};
 
-pub struct MetadataExporter;
+#[derive(Default)]
+pub struct MetadataExporter {
+    registry: ExporterRegistry,
+}
 
 impl MetadataExporter {
+    pub fn new(registry: ExporterRegistry) -> Self {
+        Self { registry }
+    }
+
+    pub fn registry(&self) -> &ExporterRegistry {
+        &self.registry
+    }
+
     pub fn export_schema_to_file(&self, schema_data: &DbMetaDataSchema, output_file: &str, format: &str) -> Result<()> {
-        let serialized = match format {
-            "json" => serde_json::to_string_pretty(schema_data)?,
-            "yaml" => serde_yaml::to_string(schema_data)?,
-            _ => return Err(anyhow::anyhow!("Unsupported format")),
-        };
-        let mut file = File::create(output_file)?;
-        file.write_all(serialized.as_bytes())?;
-        Ok(())
+        export_to(self.registry.require(format)?, schema_data, output_file)
+    }
+
+    /// Writes the snapshot as a directory with a manifest plus one JSON or YAML file per table,
+    /// which `load_schema_from_file` reassembles.
+    pub fn export_schema_to_dir(&self, schema_data: &DbMetaDataSchema, output_dir: &str, format: &str) -> Result<()> {
+        if output_dir == STDOUT {
+            bail!("A split snapshot is a directory and cannot be written to stdout");
+        }
+        write_split(schema_data, output_dir, SerializationFormat::parse(format)?)
+    }
+}
+
+/// Writes with `exporter` to `output_file`. For `-` the single-stream form goes to stdout, also
+/// for formats that otherwise write a directory.
+pub fn export_to(exporter: &dyn Exporter, schema_data: &DbMetaDataSchema, output_file: &str) -> Result<()> {
+    if output_file == STDOUT {
+        write_file(STDOUT, |out| exporter.write(schema_data, out))
+    } else {
+        exporter.write_to_path(schema_data, output_file)
     }
 }
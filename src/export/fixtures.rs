@@ -0,0 +1,119 @@
+// export/fixtures.rs
+// Snapshots shared by the exporter tests
+
+use crate::db::models::DbMetaDataSchema;
+use super::registry::Exporter;
+use serde_json::{Value, json};
+
+fn column(name: &str, data_type: &str, nullable: bool, attributes: Value) -> Value {
+    let mut col = json!({
+        "name": name, "type": data_type, "nullable": nullable, "primary_key": false, "field_length": null,
+        "unique": null, "spec": null, "isChecked": true,
+    });
+    col.as_object_mut().unwrap().extend(attributes.as_object().unwrap().clone());
+    col
+}
+
+fn schema(db_type: &str, tables: Value) -> DbMetaDataSchema {
+    serde_json::from_value(json!({
+        "system_environment_id": 1,
+        "source_database_type": db_type,
+        "db_metadata": { "tables": tables },
+    }))
+    .unwrap()
+}
+
+/// Users with a serial key, a unique email, an enum, JSON and a decimal; posts referencing them.
+pub fn postgres_blog() -> DbMetaDataSchema {
+    schema("postgres", json!({
+        "public.users": {
+            "columns": [
+                column("id", "integer", false, json!({
+                    "primary_key": true, "default_value": "nextval('users_id_seq'::regclass)", "auto_increment": true,
+                    "numeric_precision": 32, "numeric_scale": 0,
+                })),
+                column("email", "character varying", false, json!({ "field_length": 120, "description": "Login" })),
+                column("mood", "mood", true, json!({ "default_value": "'happy'::mood", "enum_values": ["happy", "sad"] })),
+                column("profile", "json", true, json!({})),
+                column("balance", "numeric", false, json!({ "default_value": "0", "numeric_precision": 12, "numeric_scale": 2 })),
+            ],
+            "primary_keys": ["id"],
+            "foreign_keys": {},
+            "indexes": [{ "name": "users_email_key", "columns": ["email"], "unique": true }],
+            "checks": [{ "name": "users_balance_check", "expression": "(balance >= (0)::numeric)" }],
+            "description": "Registered users",
+        },
+        "public.posts": {
+            "columns": [
+                column("id", "bigint", false, json!({ "primary_key": true, "auto_increment": true })),
+                column("user_id", "integer", true, json!({})),
+                column("title", "text", false, json!({ "default_value": "'untitled'::text" })),
+                column("posted_at", "timestamp with time zone", true, json!({ "default_value": "now()" })),
+            ],
+            "primary_keys": ["id"],
+            "foreign_keys": { "user_id": "public.users.id" },
+            "indexes": [{ "name": "posts_user_id_idx", "columns": ["user_id"], "unique": false }],
+        },
+    }))
+}
+
+/// Customers and their orders as the live MySQL accessor reports them: string defaults unquoted.
+pub fn mysql_shop() -> DbMetaDataSchema {
+    schema("mysql", json!({
+        "shop.customers": {
+            "columns": [
+                column("id", "int", false, json!({ "primary_key": true, "auto_increment": true, "numeric_precision": 10, "numeric_scale": 0 })),
+                column("email", "varchar", false, json!({ "field_length": 120, "auto_increment": false })),
+            ],
+            "primary_keys": ["id"],
+            "foreign_keys": {},
+            "indexes": [{ "name": "email", "columns": ["email"], "unique": true }],
+        },
+        "shop.orders": {
+            "columns": [
+                column("id", "bigint", false, json!({ "primary_key": true, "auto_increment": true, "numeric_precision": 19, "numeric_scale": 0 })),
+                column("customer_id", "int", false, json!({ "numeric_precision": 10, "numeric_scale": 0, "unsigned": true })),
+                column("status", "enum", false, json!({ "default_value": "open", "enum_values": ["open", "paid"] })),
+                column("code", "varchar", true, json!({ "field_length": 10, "default_value": "0" })),
+                column("total", "decimal", false, json!({ "default_value": "0.00", "numeric_precision": 10, "numeric_scale": 2 })),
+                column("created", "datetime", false, json!({ "default_value": "CURRENT_TIMESTAMP" })),
+            ],
+            "primary_keys": ["id"],
+            "foreign_keys": { "customer_id": "shop.customers.id" },
+            "indexes": [{ "name": "customer_id", "columns": ["customer_id"], "unique": false }],
+            "checks": [{ "name": "orders_chk_1", "expression": "(`total` >= 0)" }, { "expression": "code <> ''" }],
+            "description": "Orders placed in the shop",
+        },
+    }))
+}
+
+/// Table names that collide or start with a digit once reduced to diagram identifiers.
+pub fn awkward_names() -> DbMetaDataSchema {
+    schema("sqlite", json!({
+        "a-b": {
+            "columns": [column("id", "integer", false, json!({ "primary_key": true }))],
+            "primary_keys": ["id"],
+            "foreign_keys": {},
+        },
+        "a_b": {
+            "columns": [
+                column("id", "integer", false, json!({ "primary_key": true })),
+                column("a-b id", "integer", true, json!({})),
+            ],
+            "primary_keys": ["id"],
+            "foreign_keys": { "a-b id": "a-b.id" },
+        },
+        "2024_sales": {
+            "columns": [column("id", "integer", false, json!({ "primary_key": true }))],
+            "primary_keys": ["id"],
+            "foreign_keys": {},
+        },
+    }))
+}
+
+/// Output of an exporter written to a stream.
+pub fn render(exporter: &dyn Exporter, schema: &DbMetaDataSchema) -> String {
+    let mut out = Vec::new();
+    exporter.write(schema, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
@@ -0,0 +1,88 @@
+// export/flyway.rs
+// Flyway baseline migration exporter
+
+use crate::db::models::DbMetaDataSchema;
+use crate::ddl::dialect::Dialect;
+use crate::ddl::generator::DdlGenerator;
+use crate::ddl::translate::translate;
+use super::registry::Exporter;
+use anyhow::{Result, anyhow};
+use std::io::Write;
+
+/// Writes the DDL of the current schema as `V1__baseline.sql`, in the dialect it was extracted
+/// from, so that Flyway can start managing an existing database from its current state.
+pub struct FlywayExporter;
+
+impl Exporter for FlywayExporter {
+    fn name(&self) -> &'static str {
+        "flyway"
+    }
+
+    fn description(&self) -> &'static str {
+        "Flyway V1__baseline.sql migration recreating the current schema"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "sql"
+    }
+
+    fn default_output_path(&self) -> String {
+        "V1__baseline.sql".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let source = schema.source_database_type.as_deref()
+            .ok_or_else(|| anyhow!("Snapshot does not record its source database type, cannot generate a Flyway baseline"))?;
+        let dialect = Dialect::parse(source)?;
+        let translation = translate(&schema.db_metadata, dialect, dialect);
+        let mut warnings = translation.warnings;
+        let sql = DdlGenerator { dialect }.generate(&translation.metadata, &mut warnings);
+        for warning in &warnings {
+            writeln!(out, "-- WARNING: {}", warning)?;
+        }
+        out.write_all(sql.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{postgres_blog, render};
+
+    #[test]
+    fn baseline_keeps_the_recorded_schema() {
+        assert_eq!(
+            render(&FlywayExporter, &postgres_blog()),
+            r#"-- Generated by DBMetaExporter for postgres
+
+CREATE SCHEMA IF NOT EXISTS "public";
+CREATE TYPE "mood" AS ENUM ('happy', 'sad');
+
+CREATE TABLE "public"."users" (
+    "id" integer GENERATED BY DEFAULT AS IDENTITY NOT NULL,
+    "email" character varying(120) NOT NULL,
+    "mood" "mood" DEFAULT 'happy'::mood,
+    "profile" json,
+    "balance" numeric(12,2) NOT NULL DEFAULT 0,
+    PRIMARY KEY ("id")
+);
+
+CREATE TABLE "public"."posts" (
+    "id" bigint GENERATED BY DEFAULT AS IDENTITY NOT NULL,
+    "user_id" integer,
+    "title" text NOT NULL DEFAULT 'untitled'::text,
+    "posted_at" timestamp with time zone DEFAULT now(),
+    PRIMARY KEY ("id"),
+    CONSTRAINT "fk_posts_user_id" FOREIGN KEY ("user_id") REFERENCES "public"."users" ("id")
+);
+
+CREATE UNIQUE INDEX "users_email_key" ON "public"."users" ("email");
+CREATE INDEX "posts_user_id_idx" ON "public"."posts" ("user_id");
+
+COMMENT ON TABLE "public"."users" IS 'Registered users';
+COMMENT ON COLUMN "public"."users"."email" IS 'Login';
+"#
+        );
+    }
+}
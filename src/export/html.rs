@@ -0,0 +1,338 @@
+// export/html.rs
+// Static, offline HTML documentation site exporter
+
+use crate::db::models::{DatabaseMetadata, DbMetaDataSchema, TableMetadata};
+use super::naming::FileStems;
+use super::registry::Exporter;
+use super::writer::{create_output_dir, write_file_in};
+use super::xml::escape_xml;
+use anyhow::{Context, Result, anyhow};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+
+/// Writes a self-contained documentation site into a directory: `index.html` with search and
+/// an ER diagram, plus one page per table under `tables/`. No external assets are referenced.
+pub struct HtmlExporter;
+
+impl Exporter for HtmlExporter {
+    fn name(&self) -> &'static str {
+        "html"
+    }
+
+    fn description(&self) -> &'static str {
+        "Offline documentation site (directory) with search, table pages and ER diagram"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "html"
+    }
+
+    fn default_output_path(&self) -> String {
+        "output_html".to_string()
+    }
+
+    fn write(&self, _schema: &DbMetaDataSchema, _out: &mut dyn Write) -> Result<()> {
+        Err(anyhow!("The html format writes a directory and cannot be written to a single stream"))
+    }
+
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        let root = create_output_dir(output_path)?;
+        let tables_dir = root.join("tables");
+        fs::create_dir_all(&tables_dir)?;
+
+        let metadata = &schema.db_metadata;
+        let files = FileStems::new(metadata.tables.keys(), &[]);
+        write_file_in(root, "index.html", |out| Ok(out.write_all(render_index(metadata, &files).as_bytes())?))?;
+        let mut pages = HashSet::new();
+        for name in metadata.sorted_table_names() {
+            let page = render_table_page(metadata, &files, name, &metadata.tables[name]);
+            write_file_in(&tables_dir, table_file(&files, name), |out| Ok(out.write_all(page.as_bytes())?))?;
+            pages.insert(table_file(&files, name));
+        }
+        // Pages of tables dropped since an earlier export into the same directory
+        for entry in fs::read_dir(&tables_dir)? {
+            let path = entry?.path();
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+            if path.extension().is_some_and(|e| e == "html") && !pages.contains(&file_name) {
+                fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2rem; color: #222; }
+a { color: #0b5cad; text-decoration: none; }
+a:hover { text-decoration: underline; }
+table { border-collapse: collapse; margin: 0.5rem 0 1.5rem; }
+th, td { border: 1px solid #ccc; padding: 0.3rem 0.6rem; text-align: left; vertical-align: top; }
+th { background: #f2f4f7; }
+code { background: #f2f4f7; padding: 0 0.2rem; }
+#search { width: 100%; max-width: 30rem; padding: 0.4rem; font-size: 1rem; margin-bottom: 1rem; }
+.muted { color: #777; }
+.diagram { overflow: auto; border: 1px solid #ccc; padding: 0.5rem; }
+";
+
+const SEARCH_SCRIPT: &str = "
+document.getElementById('search').addEventListener('input', function (e) {
+  var q = e.target.value.toLowerCase();
+  document.querySelectorAll('#table-list li').forEach(function (li) {
+    li.style.display = li.getAttribute('data-search').indexOf(q) >= 0 ? '' : 'none';
+  });
+});
+";
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_xml(title),
+        STYLE,
+        body
+    )
+}
+
+fn render_index(metadata: &DatabaseMetadata, files: &FileStems) -> String {
+    let names = metadata.sorted_table_names();
+    let mut body = String::from("<h1>Database Schema</h1>\n");
+    body.push_str(&format!("<p class=\"muted\">{} tables</p>\n", names.len()));
+    body.push_str("<input id=\"search\" type=\"search\" placeholder=\"Search tables and columns...\">\n");
+    body.push_str("<ul id=\"table-list\">\n");
+    for name in &names {
+        let table = &metadata.tables[*name];
+        // Search matches table names, column names and descriptions
+        let mut search = vec![name.to_lowercase()];
+        search.extend(table.columns.iter().map(|c| c.name.to_lowercase()));
+        search.extend(table.description.iter().map(|d| d.to_lowercase()));
+        body.push_str(&format!(
+            "<li data-search=\"{}\"><a href=\"tables/{}\">{}</a>{}</li>\n",
+            escape_xml(&search.join(" ")),
+            table_file(files, name),
+            escape_xml(name),
+            table.description.as_deref().map(|d| format!(" <span class=\"muted\">— {}</span>", escape_xml(d))).unwrap_or_default()
+        ));
+    }
+    body.push_str("</ul>\n<h2>Entity Relationship Diagram</h2>\n<div class=\"diagram\">\n");
+    body.push_str(&render_er_svg(metadata, files));
+    body.push_str("</div>\n");
+    body.push_str(&format!("<script>{}</script>\n", SEARCH_SCRIPT));
+    page("Database Schema", &body)
+}
+
+fn render_table_page(metadata: &DatabaseMetadata, files: &FileStems, name: &str, table: &TableMetadata) -> String {
+    let mut body = format!("<p><a href=\"../index.html\">&larr; All tables</a></p>\n<h1>{}</h1>\n", escape_xml(name));
+    if let Some(description) = &table.description {
+        body.push_str(&format!("<p>{}</p>\n", escape_xml(description)));
+    }
+
+    body.push_str("<h2>Columns</h2>\n<table>\n<tr><th>Name</th><th>Type</th><th>Length</th><th>Nullable</th><th>PK</th><th>References</th><th>Default</th><th>Description</th></tr>\n");
+    for col in &table.columns {
+        let fk = table.foreign_keys.get(&col.name).map(|t| foreign_key_link(metadata, files, t)).unwrap_or_default();
+        body.push_str(&format!(
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape_xml(&col.name),
+            escape_xml(&col.data_type),
+            col.field_length.map(|l| l.to_string()).unwrap_or_default(),
+            if col.nullable { "yes" } else { "no" },
+            if col.primary_key { "yes" } else { "" },
+            fk,
+            col.default_value.as_deref().map(|d| format!("<code>{}</code>", escape_xml(d))).unwrap_or_default(),
+            col.description.as_deref().map(escape_xml).unwrap_or_default(),
+        ));
+    }
+    body.push_str("</table>\n");
+
+    if !table.primary_keys.is_empty() {
+        body.push_str(&format!("<h2>Primary key</h2>\n<p>{}</p>\n", code_list(&table.primary_keys)));
+    }
+
+    if !table.indexes.is_empty() {
+        body.push_str("<h2>Indexes</h2>\n<ul>\n");
+        for index in &table.indexes {
+            body.push_str(&format!(
+                "<li><code>{}</code>{}: {}</li>\n",
+                escape_xml(&index.name),
+                if index.unique { " (unique)" } else { "" },
+                code_list(&index.columns)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    let foreign_keys = table.sorted_foreign_keys();
+    if !foreign_keys.is_empty() {
+        body.push_str("<h2>References</h2>\n<ul>\n");
+        for (col, target) in foreign_keys {
+            body.push_str(&format!("<li><code>{}</code> &rarr; {}</li>\n", escape_xml(col), foreign_key_link(metadata, files, target)));
+        }
+        body.push_str("</ul>\n");
+    }
+
+    let referenced_by = metadata.referenced_by(name);
+    if !referenced_by.is_empty() {
+        body.push_str("<h2>Referenced by</h2>\n<ul>\n");
+        for (ref_table, ref_col, col) in referenced_by {
+            body.push_str(&format!(
+                "<li><a href=\"{}\">{}</a>.<code>{}</code> &rarr; <code>{}</code></li>\n",
+                table_file(files, ref_table),
+                escape_xml(ref_table),
+                escape_xml(ref_col),
+                escape_xml(col)
+            ));
+        }
+        body.push_str("</ul>\n");
+    }
+    page(name, &body)
+}
+
+const BOX_WIDTH: usize = 220;
+const HEADER_HEIGHT: usize = 24;
+const ROW_HEIGHT: usize = 18;
+const GAP: usize = 60;
+
+/// Lays tables out on a grid and draws foreign keys as arrows between the boxes.
+/// Boxes link to the table pages, so the diagram doubles as navigation.
+fn render_er_svg(metadata: &DatabaseMetadata, files: &FileStems) -> String {
+    let names = metadata.sorted_table_names();
+    let per_row = (names.len() as f64).sqrt().ceil().max(1.0) as usize;
+
+    let heights: Vec<usize> = names.iter()
+        .map(|n| HEADER_HEIGHT + ROW_HEIGHT * metadata.tables[*n].columns.len() + 6)
+        .collect();
+    let mut positions = Vec::with_capacity(names.len());
+    let mut y = GAP / 2;
+    for chunk in heights.chunks(per_row) {
+        for col in 0..chunk.len() {
+            positions.push((GAP / 2 + col * (BOX_WIDTH + GAP), y));
+        }
+        y += chunk.iter().max().copied().unwrap_or(0) + GAP;
+    }
+    let width = per_row * (BOX_WIDTH + GAP);
+    let height = y;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"12\">\n",
+        width, height
+    );
+    svg.push_str("<defs><marker id=\"arrow\" markerWidth=\"10\" markerHeight=\"10\" refX=\"9\" refY=\"3\" orient=\"auto\"><path d=\"M0,0 L9,3 L0,6 z\" fill=\"#555\"/></marker></defs>\n");
+
+    let index_of = |table: &str| names.iter().position(|n| n.as_str() == table);
+    for (i, name) in names.iter().enumerate() {
+        for (_, target) in metadata.tables[*name].sorted_foreign_keys() {
+            let Some(j) = metadata.resolve_foreign_key(target).and_then(|(t, _)| index_of(t)) else {
+                continue;
+            };
+            let (x1, y1) = positions[i];
+            let (x2, y2) = positions[j];
+            if i == j {
+                svg.push_str(&format!(
+                    "<path d=\"M{} {} h20 v20 h-20\" fill=\"none\" stroke=\"#555\" marker-end=\"url(#arrow)\"/>\n",
+                    x1 + BOX_WIDTH, y1 + HEADER_HEIGHT / 2
+                ));
+            } else {
+                let (sx, tx) = if x1 <= x2 { (x1 + BOX_WIDTH, x2) } else { (x1, x2 + BOX_WIDTH) };
+                let (sx, tx) = if x1 == x2 { (x1 + BOX_WIDTH / 2, x2 + BOX_WIDTH / 2) } else { (sx, tx) };
+                let (sy, ty) = if x1 == x2 && y1 < y2 {
+                    (y1 + heights[i], y2)
+                } else if x1 == x2 {
+                    (y1, y2 + heights[j])
+                } else {
+                    (y1 + HEADER_HEIGHT / 2, y2 + HEADER_HEIGHT / 2)
+                };
+                svg.push_str(&format!(
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#555\" marker-end=\"url(#arrow)\"/>\n",
+                    sx, sy, tx, ty
+                ));
+            }
+        }
+    }
+
+    for (i, name) in names.iter().enumerate() {
+        let table = &metadata.tables[*name];
+        let (x, y) = positions[i];
+        svg.push_str(&format!("<a href=\"tables/{}\">\n", table_file(files, name)));
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#fff\" stroke=\"#333\"/>\n",
+            x, y, BOX_WIDTH, heights[i]
+        ));
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#dfe8f5\" stroke=\"#333\"/>\n",
+            x, y, BOX_WIDTH, HEADER_HEIGHT
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-weight=\"bold\">{}</text>\n",
+            x + 6, y + 16, escape_xml(name)
+        ));
+        for (row, col) in table.columns.iter().enumerate() {
+            let marker = if col.primary_key {
+                "PK "
+            } else if table.foreign_keys.contains_key(&col.name) {
+                "FK "
+            } else {
+                "   "
+            };
+            svg.push_str(&format!(
+                "<text x=\"{}\" y=\"{}\" xml:space=\"preserve\">{}{} : {}</text>\n",
+                x + 6,
+                y + HEADER_HEIGHT + ROW_HEIGHT * (row + 1) - 4,
+                marker,
+                escape_xml(&col.name),
+                escape_xml(&col.data_type)
+            ));
+        }
+        svg.push_str("</a>\n");
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Links to the page of the referenced table when it is part of the export.
+fn foreign_key_link(metadata: &DatabaseMetadata, files: &FileStems, target: &str) -> String {
+    match metadata.resolve_foreign_key(target) {
+        Some((table, column)) => format!(
+            "<a href=\"{}\">{}</a>.<code>{}</code>",
+            table_file(files, table),
+            escape_xml(table),
+            escape_xml(column)
+        ),
+        None => format!("<code>{}</code>", escape_xml(target)),
+    }
+}
+
+fn table_file(files: &FileStems, name: &str) -> String {
+    format!("{}.html", files.stem(name))
+}
+
+fn code_list(items: &[String]) -> String {
+    items.iter().map(|i| format!("<code>{}</code>", escape_xml(i))).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::postgres_blog;
+
+    #[test]
+    fn pages_escape_names_and_drop_stale_tables() {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-html-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let output = dir.to_str().unwrap();
+
+        let mut schema = postgres_blog();
+        schema.db_metadata.tables.get_mut("public.users").unwrap().description = Some("Users & <admins>".to_string());
+        HtmlExporter.write_to_path(&schema, output).unwrap();
+        let mut pages: Vec<_> = fs::read_dir(dir.join("tables")).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+        pages.sort();
+        assert_eq!(pages, ["public.posts.html", "public.users.html"]);
+        let users = fs::read_to_string(dir.join("tables").join("public.users.html")).unwrap();
+        assert!(users.contains("Users &amp; &lt;admins&gt;"), "{}", users);
+
+        schema.db_metadata.tables.remove("public.posts");
+        HtmlExporter.write_to_path(&schema, output).unwrap();
+        let pages: Vec<_> = fs::read_dir(dir.join("tables")).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+        assert_eq!(pages, ["public.users.html"]);
+        assert!(!fs::read_to_string(dir.join("index.html")).unwrap().contains("public.posts"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,552 @@
+// export/jpa.rs
+// JPA code generator: one Jakarta Persistence entity class per table
+
+use crate::db::models::{DbMetaDataSchema, TableMetadata};
+use crate::db::types::LogicalType;
+use crate::ddl::generator::bare_table_name;
+use super::codegen::{Imports, class_names, identity_columns, type_mapping};
+use super::naming::{camel_case, namespace, pascal_case};
+use super::registry::Exporter;
+use super::writer::{create_output_dir, write_file_in};
+use anyhow::Result;
+use std::fs;
+use std::io::Write;
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "assert", "boolean", "break", "byte", "case", "catch", "char", "class", "const", "continue", "default",
+    "do", "double", "else", "enum", "extends", "false", "final", "finally", "float", "for", "goto", "if", "implements",
+    "import", "instanceof", "int", "interface", "long", "native", "new", "null", "package", "private", "protected",
+    "public", "return", "short", "static", "strictfp", "super", "switch", "synchronized", "this", "throw", "throws",
+    "transient", "true", "try", "void", "volatile", "while", "_",
+];
+
+/// Writes a directory with one entity class per table, `<package>/<Class>.java`, where the
+/// package is derived from the schema. Tables with a composite (or no) primary key get a nested
+/// `Key` class for `@IdClass`. Written to a stream, the classes follow each other, each preceded
+/// by a comment with its path.
+pub struct JpaExporter;
+
+impl Exporter for JpaExporter {
+    fn name(&self) -> &'static str {
+        "jpa"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["java"]
+    }
+
+    fn description(&self) -> &'static str {
+        "JPA (Jakarta Persistence) entity class of each table (directory)"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "java"
+    }
+
+    fn default_output_path(&self) -> String {
+        "output_jpa".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        for (index, (path, source)) in entities(schema).iter().enumerate() {
+            if index > 0 {
+                writeln!(out)?;
+            }
+            writeln!(out, "// {}", path)?;
+            write!(out, "{}", source)?;
+        }
+        Ok(())
+    }
+
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        let root = create_output_dir(output_path)?;
+        for (path, source) in entities(schema) {
+            if let Some(parent) = root.join(&path).parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_file_in(root, path, |out| Ok(out.write_all(source.as_bytes())?))?;
+        }
+        Ok(())
+    }
+}
+
+/// Relative path and source of every entity.
+fn entities(schema: &DbMetaDataSchema) -> Vec<(String, String)> {
+    let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+    let class_names = class_names(&schema.db_metadata);
+    schema.db_metadata.sorted_table_names().into_iter()
+        .map(|name| {
+            let package: Vec<String> = namespace(name).split('.').map(java_identifier).collect();
+            let class = &class_names[name.as_str()];
+            let path = format!("{}/{}.java", package.join("/"), class);
+            (path, entity(name, &schema.db_metadata.tables[name], class, &package.join("."), db_type))
+        })
+        .collect()
+}
+
+fn entity(name: &str, table: &TableMetadata, class: &str, package: &str, db_type: &str) -> String {
+    let mut imports = Imports::default();
+    for item in ["Column", "Entity", "Id", "Table"] {
+        imports.add("jakarta.persistence", item);
+    }
+    let identity = identity_columns(table);
+    let composite = identity.len() > 1;
+
+    let mut body = Vec::new();
+    let mut accessors = Vec::new();
+    let mut key_fields = Vec::new();
+    for col in &table.columns {
+        let java_type = type_mapping(col, db_type).java;
+        imports.add_type(&java_type);
+        let field = java_identifier(&camel_case(&col.name));
+
+        let mut lines = Vec::new();
+        if let Some(description) = &col.description {
+            lines.push(javadoc("    ", description));
+        }
+        if identity.contains(&col.name.as_str()) {
+            lines.push("    @Id".to_string());
+            key_fields.push((java_type.name.clone(), field.clone()));
+        }
+        if col.auto_increment == Some(true) {
+            imports.add("jakarta.persistence", "GeneratedValue");
+            imports.add("jakarta.persistence", "GenerationType");
+            lines.push("    @GeneratedValue(strategy = GenerationType.IDENTITY)".to_string());
+        }
+        let mut attributes = vec![format!("name = {}", java_string(&col.name))];
+        if !col.nullable {
+            attributes.push("nullable = false".to_string());
+        }
+        if col.unique == Some(true) {
+            attributes.push("unique = true".to_string());
+        }
+        match LogicalType::from_column(col, db_type) {
+            LogicalType::Char(Some(length)) | LogicalType::Varchar(Some(length)) => attributes.push(format!("length = {}", length)),
+            LogicalType::Decimal { precision: Some(precision), scale } => {
+                attributes.push(format!("precision = {}", precision));
+                attributes.push(format!("scale = {}", scale.unwrap_or(0)));
+            }
+            _ => {}
+        }
+        lines.push(format!("    @Column({})", attributes.join(", ")));
+        lines.push(format!("    private {} {};", java_type.name, field));
+        body.push(lines.join("\n"));
+
+        let property = pascal_case(&camel_case(&col.name));
+        accessors.push(format!(
+            "    public {ty} get{property}() {{\n        return {field};\n    }}\n\n    public void set{property}({ty} {field}) {{\n        this.{field} = {field};\n    }}",
+            ty = java_type.name,
+            property = property,
+            field = field
+        ));
+    }
+
+    let mut annotations = vec!["@Entity".to_string()];
+    match name.rsplit_once('.') {
+        Some((schema_name, table_name)) => {
+            annotations.push(format!("@Table(name = {}, schema = {})", java_string(table_name), java_string(schema_name)))
+        }
+        None => annotations.push(format!("@Table(name = {})", java_string(bare_table_name(name)))),
+    }
+    if composite {
+        imports.add("jakarta.persistence", "IdClass");
+        imports.add("java.io", "Serializable");
+        imports.add("java.util", "Objects");
+        annotations.push(format!("@IdClass({}.Key.class)", class));
+    }
+
+    let mut source = format!("package {};\n\n", package);
+    // Jakarta imports first, then the JDK
+    let mut modules: Vec<(&str, Vec<&str>)> = imports.modules().collect();
+    modules.sort_by_key(|(module, _)| !module.starts_with("jakarta."));
+    let mut previous_group = None;
+    for (module, items) in modules {
+        let group = module.starts_with("jakarta.");
+        if previous_group.is_some_and(|g| g != group) {
+            source.push('\n');
+        }
+        previous_group = Some(group);
+        for item in items {
+            source.push_str(&format!("import {}.{};\n", module, item));
+        }
+    }
+    source.push('\n');
+    if let Some(description) = &table.description {
+        source.push_str(&javadoc("", description));
+        source.push('\n');
+    }
+    if table.primary_keys.is_empty() {
+        source.push_str("// The table has no primary key; rows are identified by all of their columns\n");
+    }
+    for annotation in annotations {
+        source.push_str(&annotation);
+        source.push('\n');
+    }
+    source.push_str(&format!("public class {} {{\n", class));
+    for section in body.iter().chain(accessors.iter()) {
+        source.push('\n');
+        source.push_str(section);
+        source.push('\n');
+    }
+    if composite {
+        source.push('\n');
+        source.push_str(&key_class(&key_fields));
+    }
+    source.push_str("}\n");
+    source
+}
+
+/// Nested primary key class for `@IdClass`, with the value semantics JPA requires.
+fn key_class(fields: &[(String, String)]) -> String {
+    let mut lines = vec!["    public static class Key implements Serializable {".to_string()];
+    for (java_type, field) in fields {
+        lines.push(format!("        private {} {};", java_type, field));
+    }
+    let names: Vec<&str> = fields.iter().map(|(_, f)| f.as_str()).collect();
+    let comparisons: Vec<String> = names.iter().map(|f| format!("Objects.equals({f}, other.{f})", f = f)).collect();
+    lines.push(String::new());
+    lines.push("        @Override".to_string());
+    lines.push("        public boolean equals(Object o) {".to_string());
+    lines.push("            if (this == o) {".to_string());
+    lines.push("                return true;".to_string());
+    lines.push("            }".to_string());
+    lines.push("            if (!(o instanceof Key)) {".to_string());
+    lines.push("                return false;".to_string());
+    lines.push("            }".to_string());
+    lines.push("            Key other = (Key) o;".to_string());
+    lines.push(format!("            return {};", comparisons.join(" && ")));
+    lines.push("        }".to_string());
+    lines.push(String::new());
+    lines.push("        @Override".to_string());
+    lines.push("        public int hashCode() {".to_string());
+    lines.push(format!("            return Objects.hash({});", names.join(", ")));
+    lines.push("        }".to_string());
+    lines.push("    }".to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Keywords get a trailing underscore.
+fn java_identifier(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn java_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn javadoc(indent: &str, text: &str) -> String {
+    let mut comment = format!("{}/**", indent);
+    for line in text.lines() {
+        comment.push_str(&format!("\n{} * {}", indent, line.replace("*/", "*&#47;")));
+    }
+    comment.push_str(&format!("\n{} */", indent));
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+
+    #[test]
+    fn postgres_snapshot() {
+        assert_eq!(
+            render(&JpaExporter, &postgres_blog()),
+            r#"// public_/Posts.java
+package public_;
+
+import jakarta.persistence.Column;
+import jakarta.persistence.Entity;
+import jakarta.persistence.GeneratedValue;
+import jakarta.persistence.GenerationType;
+import jakarta.persistence.Id;
+import jakarta.persistence.Table;
+
+import java.time.OffsetDateTime;
+
+@Entity
+@Table(name = "posts", schema = "public")
+public class Posts {
+
+    @Id
+    @GeneratedValue(strategy = GenerationType.IDENTITY)
+    @Column(name = "id", nullable = false)
+    private Long id;
+
+    @Column(name = "user_id")
+    private Integer userId;
+
+    @Column(name = "title", nullable = false)
+    private String title;
+
+    @Column(name = "posted_at")
+    private OffsetDateTime postedAt;
+
+    public Long getId() {
+        return id;
+    }
+
+    public void setId(Long id) {
+        this.id = id;
+    }
+
+    public Integer getUserId() {
+        return userId;
+    }
+
+    public void setUserId(Integer userId) {
+        this.userId = userId;
+    }
+
+    public String getTitle() {
+        return title;
+    }
+
+    public void setTitle(String title) {
+        this.title = title;
+    }
+
+    public OffsetDateTime getPostedAt() {
+        return postedAt;
+    }
+
+    public void setPostedAt(OffsetDateTime postedAt) {
+        this.postedAt = postedAt;
+    }
+}
+
+// public_/Users.java
+package public_;
+
+import jakarta.persistence.Column;
+import jakarta.persistence.Entity;
+import jakarta.persistence.GeneratedValue;
+import jakarta.persistence.GenerationType;
+import jakarta.persistence.Id;
+import jakarta.persistence.Table;
+
+import java.math.BigDecimal;
+
+/**
+ * Registered users
+ */
+@Entity
+@Table(name = "users", schema = "public")
+public class Users {
+
+    @Id
+    @GeneratedValue(strategy = GenerationType.IDENTITY)
+    @Column(name = "id", nullable = false)
+    private Integer id;
+
+    /**
+     * Login
+     */
+    @Column(name = "email", nullable = false, length = 120)
+    private String email;
+
+    @Column(name = "mood")
+    private String mood;
+
+    @Column(name = "profile")
+    private String profile;
+
+    @Column(name = "balance", nullable = false, precision = 12, scale = 2)
+    private BigDecimal balance;
+
+    public Integer getId() {
+        return id;
+    }
+
+    public void setId(Integer id) {
+        this.id = id;
+    }
+
+    public String getEmail() {
+        return email;
+    }
+
+    public void setEmail(String email) {
+        this.email = email;
+    }
+
+    public String getMood() {
+        return mood;
+    }
+
+    public void setMood(String mood) {
+        this.mood = mood;
+    }
+
+    public String getProfile() {
+        return profile;
+    }
+
+    public void setProfile(String profile) {
+        this.profile = profile;
+    }
+
+    public BigDecimal getBalance() {
+        return balance;
+    }
+
+    public void setBalance(BigDecimal balance) {
+        this.balance = balance;
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn mysql_snapshot() {
+        assert_eq!(
+            render(&JpaExporter, &mysql_shop()),
+            r#"// shop/Customers.java
+package shop;
+
+import jakarta.persistence.Column;
+import jakarta.persistence.Entity;
+import jakarta.persistence.GeneratedValue;
+import jakarta.persistence.GenerationType;
+import jakarta.persistence.Id;
+import jakarta.persistence.Table;
+
+@Entity
+@Table(name = "customers", schema = "shop")
+public class Customers {
+
+    @Id
+    @GeneratedValue(strategy = GenerationType.IDENTITY)
+    @Column(name = "id", nullable = false)
+    private Integer id;
+
+    @Column(name = "email", nullable = false, length = 120)
+    private String email;
+
+    public Integer getId() {
+        return id;
+    }
+
+    public void setId(Integer id) {
+        this.id = id;
+    }
+
+    public String getEmail() {
+        return email;
+    }
+
+    public void setEmail(String email) {
+        this.email = email;
+    }
+}
+
+// shop/Orders.java
+package shop;
+
+import jakarta.persistence.Column;
+import jakarta.persistence.Entity;
+import jakarta.persistence.GeneratedValue;
+import jakarta.persistence.GenerationType;
+import jakarta.persistence.Id;
+import jakarta.persistence.Table;
+
+import java.math.BigDecimal;
+import java.time.LocalDateTime;
+
+/**
+ * Orders placed in the shop
+ */
+@Entity
+@Table(name = "orders", schema = "shop")
+public class Orders {
+
+    @Id
+    @GeneratedValue(strategy = GenerationType.IDENTITY)
+    @Column(name = "id", nullable = false)
+    private Long id;
+
+    @Column(name = "customer_id", nullable = false)
+    private Long customerId;
+
+    @Column(name = "status", nullable = false)
+    private String status;
+
+    @Column(name = "code", length = 10)
+    private String code;
+
+    @Column(name = "total", nullable = false, precision = 10, scale = 2)
+    private BigDecimal total;
+
+    @Column(name = "created", nullable = false)
+    private LocalDateTime created;
+
+    public Long getId() {
+        return id;
+    }
+
+    public void setId(Long id) {
+        this.id = id;
+    }
+
+    public Long getCustomerId() {
+        return customerId;
+    }
+
+    public void setCustomerId(Long customerId) {
+        this.customerId = customerId;
+    }
+
+    public String getStatus() {
+        return status;
+    }
+
+    public void setStatus(String status) {
+        this.status = status;
+    }
+
+    public String getCode() {
+        return code;
+    }
+
+    public void setCode(String code) {
+        this.code = code;
+    }
+
+    public BigDecimal getTotal() {
+        return total;
+    }
+
+    public void setTotal(BigDecimal total) {
+        this.total = total;
+    }
+
+    public LocalDateTime getCreated() {
+        return created;
+    }
+
+    public void setCreated(LocalDateTime created) {
+        this.created = created;
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn directories_get_one_class_per_table() {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-jpa-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        JpaExporter.write_to_path(&mysql_shop(), dir.to_str().unwrap()).unwrap();
+        let mut files: Vec<String> = fs::read_dir(dir.join("shop")).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+        files.sort();
+        assert_eq!(files, ["Customers.java", "Orders.java"]);
+        let orders = fs::read_to_string(dir.join("shop").join("Orders.java")).unwrap();
+        assert!(orders.starts_with("package shop;\n"), "{}", orders);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
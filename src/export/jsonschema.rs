@@ -0,0 +1,183 @@
+// export/jsonschema.rs
+// JSON Schema exporter describing the row shape of every table
+
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema, TableMetadata};
+use crate::db::types::LogicalType;
+use super::naming::FileStems;
+use super::registry::Exporter;
+use super::writer::{SerializationFormat, create_output_dir, write_file_in};
+use anyhow::Result;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::{Value, json};
+use std::io::Write;
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+const BUNDLE_FILE: &str = "schema.json";
+
+/// Writes a directory with one JSON Schema (draft 2020-12) per table, named
+/// `<table>.schema.json`, plus `schema.json` bundling all tables under `$defs`. Written to a
+/// stream, only the bundle is produced.
+pub struct JsonSchemaExporter;
+
+impl Exporter for JsonSchemaExporter {
+    fn name(&self) -> &'static str {
+        "jsonschema"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["json-schema"]
+    }
+
+    fn description(&self) -> &'static str {
+        "JSON Schema of each table's rows (directory), plus a $defs bundle"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn default_output_path(&self) -> String {
+        "output_jsonschema".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        SerializationFormat::Json.write(&Bundle { schema }, out)
+    }
+
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        let root = create_output_dir(output_path)?;
+        let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+        let files = FileStems::new(schema.db_metadata.tables.keys(), &[]);
+        for name in schema.db_metadata.sorted_table_names() {
+            let file_name = format!("{}.schema.json", files.stem(name));
+            let table_schema = TableSchema {
+                draft: Some(DRAFT),
+                id: Some(file_name.clone()),
+                table: name,
+                metadata: &schema.db_metadata.tables[name],
+                db_type,
+            };
+            write_file_in(root, file_name, |out| SerializationFormat::Json.write(&table_schema, out))?;
+        }
+        write_file_in(root, BUNDLE_FILE, |out| self.write(schema, out))
+    }
+}
+
+/// Serialized with properties in column order, which `serde_json::Value` would sort.
+struct TableSchema<'a> {
+    draft: Option<&'static str>,
+    id: Option<String>,
+    table: &'a str,
+    metadata: &'a TableMetadata,
+    db_type: &'a str,
+}
+
+impl Serialize for TableSchema<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(draft) = self.draft {
+            map.serialize_entry("$schema", draft)?;
+        }
+        if let Some(id) = &self.id {
+            map.serialize_entry("$id", id)?;
+        }
+        map.serialize_entry("title", self.table)?;
+        if let Some(description) = &self.metadata.description {
+            map.serialize_entry("description", description)?;
+        }
+        map.serialize_entry("type", "object")?;
+        map.serialize_entry("properties", &Properties { columns: &self.metadata.columns, db_type: self.db_type })?;
+        let required: Vec<&String> = self.metadata.columns.iter().filter(|c| !c.nullable).map(|c| &c.name).collect();
+        if !required.is_empty() {
+            map.serialize_entry("required", &required)?;
+        }
+        map.serialize_entry("additionalProperties", &false)?;
+        map.end()
+    }
+}
+
+struct Properties<'a> {
+    columns: &'a [ColumnMetadata],
+    db_type: &'a str,
+}
+
+impl Serialize for Properties<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for col in self.columns {
+            map.serialize_entry(&col.name, &column_schema(col, self.db_type))?;
+        }
+        map.end()
+    }
+}
+
+/// The `$defs` bundle: every table schema without `$schema`/`$id`, keyed by table.
+struct Bundle<'a> {
+    schema: &'a DbMetaDataSchema,
+}
+
+impl Serialize for Bundle<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let metadata = &self.schema.db_metadata;
+        let db_type = self.schema.source_database_type.as_deref().unwrap_or_default();
+        let defs = Defs(
+            metadata.sorted_table_names().into_iter()
+                .map(|name| TableSchema { draft: None, id: None, table: name, metadata: &metadata.tables[name], db_type })
+                .collect(),
+        );
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("$schema", DRAFT)?;
+        map.serialize_entry("$id", BUNDLE_FILE)?;
+        map.serialize_entry("$defs", &defs)?;
+        map.end()
+    }
+}
+
+struct Defs<'a>(Vec<TableSchema<'a>>);
+
+impl Serialize for Defs<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for table in &self.0 {
+            map.serialize_entry(table.table, table)?;
+        }
+        map.end()
+    }
+}
+
+/// Schema of a single column value. Nullable columns also accept `null`.
+fn column_schema(col: &ColumnMetadata, db_type: &str) -> Value {
+    let string = |format: &str| json!({ "type": "string", "format": format });
+    let mut schema = match LogicalType::from_column(col, db_type) {
+        LogicalType::Boolean => json!({ "type": "boolean" }),
+        LogicalType::SmallInt => json!({ "type": "integer", "minimum": i16::MIN, "maximum": i16::MAX }),
+        LogicalType::Integer => json!({ "type": "integer", "minimum": i32::MIN, "maximum": i32::MAX }),
+        LogicalType::BigInt => json!({ "type": "integer" }),
+        LogicalType::Decimal { .. } | LogicalType::Float | LogicalType::Double => json!({ "type": "number" }),
+        LogicalType::Char(Some(length)) | LogicalType::Varchar(Some(length)) => json!({ "type": "string", "maxLength": length }),
+        LogicalType::Char(None) | LogicalType::Varchar(None) | LogicalType::Text => json!({ "type": "string" }),
+        LogicalType::Date => string("date"),
+        LogicalType::Time => string("time"),
+        LogicalType::Timestamp | LogicalType::TimestampTz => string("date-time"),
+        LogicalType::Interval => string("duration"),
+        LogicalType::Uuid => string("uuid"),
+        LogicalType::Binary => json!({ "type": "string", "contentEncoding": "base64" }),
+        LogicalType::Enum(values) => json!({ "type": "string", "enum": values }),
+        LogicalType::Array(_) => json!({ "type": "array" }),
+        // JSON columns and unknown types accept any value
+        LogicalType::Json | LogicalType::Other(_) => json!({}),
+    };
+
+    if col.nullable {
+        if let Some(Value::String(kind)) = schema.get("type").cloned() {
+            schema["type"] = json!([kind, "null"]);
+        }
+        if let Some(Value::Array(values)) = schema.get_mut("enum") {
+            values.push(Value::Null);
+        }
+    }
+    if let Some(description) = &col.description {
+        schema["description"] = json!(description);
+    }
+    schema
+}
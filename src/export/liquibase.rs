@@ -0,0 +1,490 @@
+// export/liquibase.rs
+// Liquibase changelog exporter (YAML and XML)
+
+use crate::db::models::{ColumnMetadata, DatabaseMetadata, DbMetaDataSchema};
+use crate::ddl::dialect::{Dialect, quote_string};
+use crate::ddl::generator::{DdlGenerator, bare_table_name, creation_plan, enum_types, foreign_key_name, foreign_keys, index_name};
+use crate::ddl::translate::translate;
+use super::registry::Exporter;
+use super::xml::escape_xml;
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+use std::io::Write;
+
+const AUTHOR: &str = "dbmetaexporter";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangelogFormat {
+    Yaml,
+    Xml,
+}
+
+/// Renders the current schema as a Liquibase changelog: one changeSet per createTable,
+/// addPrimaryKey, createIndex and addForeignKeyConstraint, so an existing database can be
+/// put under migration control with `changelogSync`.
+pub struct LiquibaseExporter {
+    pub format: ChangelogFormat,
+}
+
+/// A Liquibase change with its attributes, nested columns and, for `sql`, its statement.
+struct Change {
+    kind: &'static str,
+    attributes: Vec<(&'static str, Value)>,
+    columns: Vec<ChangeColumn>,
+    sql: Option<String>,
+}
+
+struct ChangeColumn {
+    attributes: Vec<(&'static str, Value)>,
+    constraints: Vec<(&'static str, Value)>,
+}
+
+impl Exporter for LiquibaseExporter {
+    fn name(&self) -> &'static str {
+        match self.format {
+            ChangelogFormat::Yaml => "liquibase",
+            ChangelogFormat::Xml => "liquibase-xml",
+        }
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        match self.format {
+            ChangelogFormat::Yaml => &["liquibase-yaml"],
+            ChangelogFormat::Xml => &[],
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.format {
+            ChangelogFormat::Yaml => "Liquibase YAML changelog recreating the current schema",
+            ChangelogFormat::Xml => "Liquibase XML changelog recreating the current schema",
+        }
+    }
+
+    fn file_extension(&self) -> &'static str {
+        match self.format {
+            ChangelogFormat::Yaml => "yaml",
+            ChangelogFormat::Xml => "xml",
+        }
+    }
+
+    fn default_output_path(&self) -> String {
+        format!("changelog.{}", self.file_extension())
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let dialect = schema.source_database_type.as_deref().and_then(|d| Dialect::parse(d).ok());
+        // Normalizes sequence defaults into auto-increment flags; types stay as recorded
+        let metadata = match dialect {
+            Some(dialect) => translate(&schema.db_metadata, dialect, dialect).metadata,
+            None => schema.db_metadata.clone(),
+        };
+        let change_sets = change_sets(&metadata, dialect);
+        match self.format {
+            ChangelogFormat::Yaml => write_yaml(out, &change_sets),
+            ChangelogFormat::Xml => write_xml(out, &change_sets),
+        }
+    }
+}
+
+fn change_sets(metadata: &DatabaseMetadata, dialect: Option<Dialect>) -> Vec<(String, Change)> {
+    let plan = creation_plan(metadata);
+    let mut change_sets = Vec::new();
+
+    if dialect == Some(Dialect::Postgres) {
+        for (name, values) in enum_types(metadata) {
+            let sql = format!(
+                "CREATE TYPE {} AS ENUM ({})",
+                Dialect::Postgres.quote_identifier(&name),
+                values.iter().map(|v| quote_string(v)).collect::<Vec<_>>().join(", ")
+            );
+            change_sets.push((format!("create-type-{}", name), Change { kind: "sql", attributes: Vec::new(), columns: Vec::new(), sql: Some(sql) }));
+        }
+    }
+
+    for name in &plan.order {
+        let table = &metadata.tables[*name];
+        // MySQL only accepts AUTO_INCREMENT on key columns, so such keys are declared inline
+        let inline_key = table.columns.iter().any(|c| c.auto_increment == Some(true));
+        let mut attributes = table_attributes(name, dialect);
+        if let Some(description) = &table.description {
+            attributes.push(("remarks", Value::from(description.as_str())));
+        }
+        let columns = table.columns.iter()
+            .map(|col| {
+                let mut constraints = Vec::new();
+                if inline_key && col.primary_key {
+                    constraints.push(("primaryKey", Value::Bool(true)));
+                    constraints.push(("primaryKeyName", Value::from(format!("{}_pkey", bare_table_name(name)))));
+                }
+                if !col.nullable {
+                    constraints.push(("nullable", Value::Bool(false)));
+                }
+                if col.unique == Some(true) && !col.primary_key {
+                    constraints.push(("unique", Value::Bool(true)));
+                }
+                ChangeColumn { attributes: column_attributes(name, col, dialect), constraints }
+            })
+            .collect();
+        change_sets.push((format!("create-table-{}", name), Change { kind: "createTable", attributes, columns, sql: None }));
+
+        if !table.primary_keys.is_empty() && !inline_key {
+            let mut attributes = table_attributes(name, dialect);
+            attributes.push(("columnNames", Value::from(table.primary_keys.join(", "))));
+            attributes.push(("constraintName", Value::from(format!("{}_pkey", bare_table_name(name)))));
+            change_sets.push((format!("primary-key-{}", name), Change { kind: "addPrimaryKey", attributes, columns: Vec::new(), sql: None }));
+        }
+    }
+
+    for name in &plan.order {
+        for index in &metadata.tables[*name].indexes {
+            let index_name = index_name(name, index);
+            let mut attributes = table_attributes(name, dialect);
+            attributes.push(("indexName", Value::from(index_name.as_str())));
+            attributes.push(("unique", Value::Bool(index.unique)));
+            let columns = index.columns.iter()
+                .map(|c| ChangeColumn { attributes: vec![("name", Value::from(c.as_str()))], constraints: Vec::new() })
+                .collect();
+            change_sets.push((format!("index-{}-{}", name, index_name), Change { kind: "createIndex", attributes, columns, sql: None }));
+        }
+    }
+
+    for name in &plan.order {
+        // Keys that cannot be recreated are left out; the ddl command reports them
+        for fk in foreign_keys(metadata, name, &mut Vec::new()) {
+            let constraint = foreign_key_name(name, &fk.columns.join("_"));
+            let mut attributes = Vec::new();
+            for (key, value) in table_attributes(name, dialect) {
+                attributes.push((if key == "schemaName" { "baseTableSchemaName" } else { "baseTableName" }, value));
+            }
+            attributes.push(("baseColumnNames", Value::from(fk.columns.join(", "))));
+            attributes.push(("constraintName", Value::from(constraint.as_str())));
+            for (key, value) in table_attributes(fk.ref_table, dialect) {
+                attributes.push((if key == "schemaName" { "referencedTableSchemaName" } else { "referencedTableName" }, value));
+            }
+            attributes.push(("referencedColumnNames", Value::from(fk.ref_columns.join(", "))));
+            change_sets.push((format!("foreign-key-{}", constraint), Change { kind: "addForeignKeyConstraint", attributes, columns: Vec::new(), sql: None }));
+        }
+    }
+    change_sets
+}
+
+/// `schemaName` (PostgreSQL, or when the dialect is unknown) and `tableName` of a table key.
+fn table_attributes(table_key: &str, dialect: Option<Dialect>) -> Vec<(&'static str, Value)> {
+    match table_key.split_once('.') {
+        Some((schema, table)) if dialect.is_none() || dialect == Some(Dialect::Postgres) => {
+            vec![("schemaName", Value::from(schema)), ("tableName", Value::from(table))]
+        }
+        _ => vec![("tableName", Value::from(bare_table_name(table_key)))],
+    }
+}
+
+fn column_attributes(table_name: &str, col: &ColumnMetadata, dialect: Option<Dialect>) -> Vec<(&'static str, Value)> {
+    let data_type = match dialect {
+        Some(dialect) => DdlGenerator { dialect }.column_type(table_name, col),
+        None => match col.field_length {
+            Some(length) if !col.data_type.contains('(') => format!("{}({})", col.data_type, length),
+            _ => col.data_type.clone(),
+        },
+    };
+    let mut attributes = vec![("name", Value::from(col.name.as_str())), ("type", Value::from(data_type))];
+    if col.auto_increment == Some(true) {
+        attributes.push(("autoIncrement", Value::Bool(true)));
+    } else if let Some(default) = &col.default_value {
+        attributes.push(default_attribute(default));
+    }
+    if let Some(description) = &col.description {
+        attributes.push(("remarks", Value::from(description.as_str())));
+    }
+    attributes
+}
+
+/// Picks the Liquibase default attribute matching the SQL default expression.
+fn default_attribute(default: &str) -> (&'static str, Value) {
+    let value = default.trim();
+    let lower = value.to_lowercase();
+    if value.parse::<f64>().is_ok() {
+        let number = serde_yaml::from_str(value).unwrap_or_else(|_| Value::from(value));
+        return ("defaultValueNumeric", number);
+    }
+    if lower == "true" || lower == "false" {
+        return ("defaultValueBoolean", Value::Bool(lower == "true"));
+    }
+    // Strip PostgreSQL casts such as 'new'::order_status
+    let literal = if value.starts_with('\'') { value.rsplit_once("::").map_or(value, |(l, _)| l) } else { value };
+    if literal.len() >= 2 && literal.starts_with('\'') && literal.ends_with('\'') {
+        return ("defaultValue", Value::from(literal[1..literal.len() - 1].replace("''", "'")));
+    }
+    ("defaultValueComputed", Value::from(value))
+}
+
+fn write_yaml(out: &mut dyn Write, change_sets: &[(String, Change)]) -> Result<()> {
+    let entries: Vec<Value> = change_sets.iter()
+        .map(|(id, change)| {
+            let mut body = mapping(&change.attributes);
+            if !change.columns.is_empty() {
+                let columns = change.columns.iter()
+                    .map(|c| {
+                        let mut column = mapping(&c.attributes);
+                        if !c.constraints.is_empty() {
+                            column.insert(Value::from("constraints"), Value::Mapping(mapping(&c.constraints)));
+                        }
+                        single("column", Value::Mapping(column))
+                    })
+                    .collect();
+                body.insert(Value::from("columns"), Value::Sequence(columns));
+            }
+            if let Some(sql) = &change.sql {
+                body.insert(Value::from("sql"), Value::from(sql.as_str()));
+            }
+            let mut change_set = Mapping::new();
+            change_set.insert(Value::from("id"), Value::from(id.as_str()));
+            change_set.insert(Value::from("author"), Value::from(AUTHOR));
+            change_set.insert(Value::from("changes"), Value::Sequence(vec![single(change.kind, Value::Mapping(body))]));
+            single("changeSet", Value::Mapping(change_set))
+        })
+        .collect();
+    serde_yaml::to_writer(out, &single("databaseChangeLog", Value::Sequence(entries)))?;
+    Ok(())
+}
+
+fn write_xml(out: &mut dyn Write, change_sets: &[(String, Change)]) -> Result<()> {
+    writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(out, "<databaseChangeLog")?;
+    writeln!(out, "    xmlns=\"http://www.liquibase.org/xml/ns/dbchangelog\"")?;
+    writeln!(out, "    xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"")?;
+    writeln!(out, "    xsi:schemaLocation=\"http://www.liquibase.org/xml/ns/dbchangelog http://www.liquibase.org/xml/ns/dbchangelog/dbchangelog-latest.xsd\">")?;
+    for (id, change) in change_sets {
+        writeln!(out)?;
+        writeln!(out, "    <changeSet id=\"{}\" author=\"{}\">", escape_xml(id), AUTHOR)?;
+        let attributes = xml_attributes(&change.attributes);
+        if let Some(sql) = &change.sql {
+            writeln!(out, "        <{}{}>{}</{}>", change.kind, attributes, escape_xml(sql), change.kind)?;
+        } else if change.columns.is_empty() {
+            writeln!(out, "        <{}{}/>", change.kind, attributes)?;
+        } else {
+            writeln!(out, "        <{}{}>", change.kind, attributes)?;
+            for column in &change.columns {
+                if column.constraints.is_empty() {
+                    writeln!(out, "            <column{}/>", xml_attributes(&column.attributes))?;
+                } else {
+                    writeln!(out, "            <column{}>", xml_attributes(&column.attributes))?;
+                    writeln!(out, "                <constraints{}/>", xml_attributes(&column.constraints))?;
+                    writeln!(out, "            </column>")?;
+                }
+            }
+            writeln!(out, "        </{}>", change.kind)?;
+        }
+        writeln!(out, "    </changeSet>")?;
+    }
+    writeln!(out, "</databaseChangeLog>")?;
+    Ok(())
+}
+
+fn mapping(attributes: &[(&'static str, Value)]) -> Mapping {
+    attributes.iter().map(|(k, v)| (Value::from(*k), v.clone())).collect()
+}
+
+fn single(key: &str, value: Value) -> Value {
+    let mut map = Mapping::new();
+    map.insert(Value::from(key), value);
+    Value::Mapping(map)
+}
+
+fn xml_attributes(attributes: &[(&'static str, Value)]) -> String {
+    attributes.iter()
+        .map(|(key, value)| {
+            let text = match value {
+                Value::String(s) => s.clone(),
+                Value::Bool(b) => b.to_string(),
+                other => serde_yaml::to_string(other).unwrap_or_default().trim().to_string(),
+            };
+            format!(" {}=\"{}\"", key, escape_xml(&text))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{postgres_blog, render};
+
+    #[test]
+    fn yaml_changelog() {
+        assert_eq!(
+            render(&LiquibaseExporter { format: ChangelogFormat::Yaml }, &postgres_blog()),
+            r#"databaseChangeLog:
+- changeSet:
+    id: create-type-mood
+    author: dbmetaexporter
+    changes:
+    - sql:
+        sql: CREATE TYPE "mood" AS ENUM ('happy', 'sad')
+- changeSet:
+    id: create-table-public.users
+    author: dbmetaexporter
+    changes:
+    - createTable:
+        schemaName: public
+        tableName: users
+        remarks: Registered users
+        columns:
+        - column:
+            name: id
+            type: integer
+            autoIncrement: true
+            constraints:
+              primaryKey: true
+              primaryKeyName: users_pkey
+              nullable: false
+        - column:
+            name: email
+            type: character varying(120)
+            remarks: Login
+            constraints:
+              nullable: false
+        - column:
+            name: mood
+            type: '"mood"'
+            defaultValue: happy
+        - column:
+            name: profile
+            type: json
+        - column:
+            name: balance
+            type: numeric(12,2)
+            defaultValueNumeric: 0
+            constraints:
+              nullable: false
+- changeSet:
+    id: create-table-public.posts
+    author: dbmetaexporter
+    changes:
+    - createTable:
+        schemaName: public
+        tableName: posts
+        columns:
+        - column:
+            name: id
+            type: bigint
+            autoIncrement: true
+            constraints:
+              primaryKey: true
+              primaryKeyName: posts_pkey
+              nullable: false
+        - column:
+            name: user_id
+            type: integer
+        - column:
+            name: title
+            type: text
+            defaultValue: untitled
+            constraints:
+              nullable: false
+        - column:
+            name: posted_at
+            type: timestamp with time zone
+            defaultValueComputed: now()
+- changeSet:
+    id: index-public.users-users_email_key
+    author: dbmetaexporter
+    changes:
+    - createIndex:
+        schemaName: public
+        tableName: users
+        indexName: users_email_key
+        unique: true
+        columns:
+        - column:
+            name: email
+- changeSet:
+    id: index-public.posts-posts_user_id_idx
+    author: dbmetaexporter
+    changes:
+    - createIndex:
+        schemaName: public
+        tableName: posts
+        indexName: posts_user_id_idx
+        unique: false
+        columns:
+        - column:
+            name: user_id
+- changeSet:
+    id: foreign-key-fk_posts_user_id
+    author: dbmetaexporter
+    changes:
+    - addForeignKeyConstraint:
+        baseTableSchemaName: public
+        baseTableName: posts
+        baseColumnNames: user_id
+        constraintName: fk_posts_user_id
+        referencedTableSchemaName: public
+        referencedTableName: users
+        referencedColumnNames: id
+"#
+        );
+    }
+
+    #[test]
+    fn xml_changelog() {
+        assert_eq!(
+            render(&LiquibaseExporter { format: ChangelogFormat::Xml }, &postgres_blog()),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<databaseChangeLog
+    xmlns="http://www.liquibase.org/xml/ns/dbchangelog"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+    xsi:schemaLocation="http://www.liquibase.org/xml/ns/dbchangelog http://www.liquibase.org/xml/ns/dbchangelog/dbchangelog-latest.xsd">
+
+    <changeSet id="create-type-mood" author="dbmetaexporter">
+        <sql>CREATE TYPE &quot;mood&quot; AS ENUM (&apos;happy&apos;, &apos;sad&apos;)</sql>
+    </changeSet>
+
+    <changeSet id="create-table-public.users" author="dbmetaexporter">
+        <createTable schemaName="public" tableName="users" remarks="Registered users">
+            <column name="id" type="integer" autoIncrement="true">
+                <constraints primaryKey="true" primaryKeyName="users_pkey" nullable="false"/>
+            </column>
+            <column name="email" type="character varying(120)" remarks="Login">
+                <constraints nullable="false"/>
+            </column>
+            <column name="mood" type="&quot;mood&quot;" defaultValue="happy"/>
+            <column name="profile" type="json"/>
+            <column name="balance" type="numeric(12,2)" defaultValueNumeric="0">
+                <constraints nullable="false"/>
+            </column>
+        </createTable>
+    </changeSet>
+
+    <changeSet id="create-table-public.posts" author="dbmetaexporter">
+        <createTable schemaName="public" tableName="posts">
+            <column name="id" type="bigint" autoIncrement="true">
+                <constraints primaryKey="true" primaryKeyName="posts_pkey" nullable="false"/>
+            </column>
+            <column name="user_id" type="integer"/>
+            <column name="title" type="text" defaultValue="untitled">
+                <constraints nullable="false"/>
+            </column>
+            <column name="posted_at" type="timestamp with time zone" defaultValueComputed="now()"/>
+        </createTable>
+    </changeSet>
+
+    <changeSet id="index-public.users-users_email_key" author="dbmetaexporter">
+        <createIndex schemaName="public" tableName="users" indexName="users_email_key" unique="true">
+            <column name="email"/>
+        </createIndex>
+    </changeSet>
+
+    <changeSet id="index-public.posts-posts_user_id_idx" author="dbmetaexporter">
+        <createIndex schemaName="public" tableName="posts" indexName="posts_user_id_idx" unique="false">
+            <column name="user_id"/>
+        </createIndex>
+    </changeSet>
+
+    <changeSet id="foreign-key-fk_posts_user_id" author="dbmetaexporter">
+        <addForeignKeyConstraint baseTableSchemaName="public" baseTableName="posts" baseColumnNames="user_id" constraintName="fk_posts_user_id" referencedTableSchemaName="public" referencedTableName="users" referencedColumnNames="id"/>
+    </changeSet>
+</databaseChangeLog>
+"#
+        );
+    }
+}
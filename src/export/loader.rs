@@ -0,0 +1,40 @@
+// export/loader.rs
+// Loads previously exported metadata snapshots back into memory
+
+use crate::db::models::DbMetaDataSchema;
+use super::split::{is_split, load_split};
+use super::writer::Compression;
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+/// Reads a `DbMetaDataSchema` snapshot; `.yaml`/`.yml` files are parsed as YAML, anything else as JSON.
+/// Snapshots compressed with gzip (`.gz`) or zstd (`.zst`) are decompressed first.
+/// A directory written with `--split` (or its manifest) is reassembled into one snapshot.
+pub fn load_schema_from_file(path: &str) -> Result<DbMetaDataSchema> {
+    if is_split(path) {
+        let value = load_split(path)?;
+        return serde_json::from_value(value).with_context(|| format!("Failed to parse split snapshot '{}'", path));
+    }
+    let contents = read_text(path)?;
+    let lower = Compression::strip(path).to_lowercase();
+    if lower.ends_with(".yaml") || lower.ends_with(".yml") {
+        serde_yaml::from_str(&contents).with_context(|| format!("Failed to parse YAML snapshot '{}'", path))
+    } else {
+        serde_json::from_str(&contents).with_context(|| format!("Failed to parse JSON snapshot '{}'", path))
+    }
+}
+
+/// Reads a text file, decompressing it according to its extension.
+pub fn read_text(path: &str) -> Result<String> {
+    let file = BufReader::new(File::open(path).with_context(|| format!("Failed to read {}", path))?);
+    let mut reader: Box<dyn Read> = match Compression::from_path(path) {
+        Compression::None => Box::new(file),
+        Compression::Gzip => Box::new(GzDecoder::new(file)),
+        Compression::Zstd => Box::new(zstd::Decoder::with_buffer(file)?),
+    };
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents).with_context(|| format!("Failed to read {}", path))?;
+    Ok(contents)
+}
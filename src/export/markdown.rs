@@ -0,0 +1,258 @@
+// export/markdown.rs
+// Markdown data dictionary exporter
+
+use crate::db::models::{DatabaseMetadata, DbMetaDataSchema, TableMetadata};
+use super::registry::Exporter;
+use anyhow::Result;
+use std::io::Write;
+
+/// Renders a data dictionary with a table of contents and one section per table.
+/// Output is sorted and contains no timestamps, so it can be committed and diffed.
+pub struct MarkdownExporter;
+
+impl Exporter for MarkdownExporter {
+    fn name(&self) -> &'static str {
+        "markdown"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["md"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Data dictionary with per-table column, index and constraint lists"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "md"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        out.write_all(render_markdown(&schema.db_metadata).as_bytes())?;
+        Ok(())
+    }
+}
+
+pub fn render_markdown(metadata: &DatabaseMetadata) -> String {
+    let mut md = String::from("# Data Dictionary\n\n");
+    let names = metadata.sorted_table_names();
+
+    md.push_str("## Tables\n\n");
+    for name in &names {
+        let table = &metadata.tables[*name];
+        md.push_str(&format!("- [{}](#{})", escape_text(name), table_anchor(name)));
+        if let Some(description) = &table.description {
+            md.push_str(&format!(" — {}", escape_text(description)));
+        }
+        md.push('\n');
+    }
+
+    for name in names {
+        md.push('\n');
+        render_table(&mut md, metadata, name, &metadata.tables[name]);
+    }
+    md
+}
+
+fn render_table(md: &mut String, metadata: &DatabaseMetadata, name: &str, table: &TableMetadata) {
+    md.push_str(&format!("<a id=\"{}\"></a>\n\n## {}\n\n", table_anchor(name), escape_text(name)));
+    if let Some(description) = &table.description {
+        md.push_str(&format!("{}\n\n", escape_text(description)));
+    }
+
+    md.push_str("### Columns\n\n");
+    md.push_str("| Name | Type | Length | Nullable | PK | FK target | Default | Description |\n");
+    md.push_str("|------|------|--------|----------|----|-----------|---------|-------------|\n");
+    for col in &table.columns {
+        let fk = table.foreign_keys.get(&col.name)
+            .map(|target| foreign_key_link(metadata, target))
+            .unwrap_or_default();
+        md.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            escape_cell(&col.name),
+            escape_cell(&col.data_type),
+            col.field_length.map(|l| l.to_string()).unwrap_or_default(),
+            if col.nullable { "yes" } else { "no" },
+            if col.primary_key { "yes" } else { "" },
+            fk,
+            col.default_value.as_deref().map(|d| format!("`{}`", escape_cell(d))).unwrap_or_default(),
+            col.description.as_deref().map(escape_cell).unwrap_or_default(),
+        ));
+    }
+
+    md.push_str("\n### Constraints\n\n");
+    let mut has_constraints = false;
+    if !table.primary_keys.is_empty() {
+        md.push_str(&format!("- Primary key: {}\n", code_list(&table.primary_keys)));
+        has_constraints = true;
+    }
+    for (col, target) in table.sorted_foreign_keys() {
+        md.push_str(&format!("- Foreign key: `{}` → {}\n", escape_text(col), foreign_key_link(metadata, target)));
+        has_constraints = true;
+    }
+    for index in table.indexes.iter().filter(|i| i.unique) {
+        md.push_str(&format!("- Unique `{}`: {}\n", escape_text(&index.name), code_list(&index.columns)));
+        has_constraints = true;
+    }
+    // Columns declared UNIQUE without a unique index of their own in the snapshot
+    for col in table.columns.iter().filter(|c| c.unique == Some(true)) {
+        if !table.indexes.iter().any(|i| i.unique && i.columns == [col.name.as_str()]) {
+            md.push_str(&format!("- Unique: `{}`\n", escape_text(&col.name)));
+            has_constraints = true;
+        }
+    }
+    for check in &table.checks {
+        match &check.name {
+            Some(check_name) => md.push_str(&format!("- Check `{}`: {}\n", escape_text(check_name), code_span(&check.expression))),
+            None => md.push_str(&format!("- Check: {}\n", code_span(&check.expression))),
+        }
+        has_constraints = true;
+    }
+    if !has_constraints {
+        md.push_str("_None_\n");
+    }
+
+    md.push_str("\n### Indexes\n\n");
+    if table.indexes.is_empty() {
+        md.push_str("_None_\n");
+    }
+    for index in &table.indexes {
+        md.push_str(&format!(
+            "- `{}`{}: {}\n",
+            escape_text(&index.name),
+            if index.unique { " (unique)" } else { "" },
+            code_list(&index.columns)
+        ));
+    }
+
+    let referenced_by = metadata.referenced_by(name);
+    if !referenced_by.is_empty() {
+        md.push_str("\n### Referenced by\n\n");
+        for (ref_table, ref_col, col) in referenced_by {
+            md.push_str(&format!(
+                "- [{}](#{}).`{}` → `{}`\n",
+                escape_text(ref_table),
+                table_anchor(ref_table),
+                escape_text(ref_col),
+                escape_text(col)
+            ));
+        }
+    }
+}
+
+/// Links the target table when it is part of the export, otherwise prints the raw target.
+fn foreign_key_link(metadata: &DatabaseMetadata, target: &str) -> String {
+    match metadata.resolve_foreign_key(target) {
+        Some((table, column)) => format!("[{}](#{}).`{}`", escape_cell(table), table_anchor(table), escape_cell(column)),
+        None => format!("`{}`", escape_cell(target)),
+    }
+}
+
+fn table_anchor(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("table-{}", slug)
+}
+
+fn code_list(items: &[String]) -> String {
+    items.iter().map(|i| format!("`{}`", escape_text(i))).collect::<Vec<_>>().join(", ")
+}
+
+/// Inline code for values that may contain backticks themselves, such as MySQL check expressions.
+fn code_span(value: &str) -> String {
+    let value = escape_text(value);
+    let longest_run = value.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    if longest_run > 0 {
+        format!("{} {} {}", fence, value, fence)
+    } else {
+        format!("{}{}{}", fence, value, fence)
+    }
+}
+
+fn escape_text(value: &str) -> String {
+    value.replace('\n', " ")
+}
+
+fn escape_cell(value: &str) -> String {
+    escape_text(value).replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, render};
+
+    #[test]
+    fn constraints_list_unique_columns_and_checks() {
+        let mut schema = mysql_shop();
+        let orders = schema.db_metadata.tables.get_mut("shop.orders").unwrap();
+        orders.columns.iter_mut().find(|c| c.name == "code").unwrap().unique = Some(true);
+        assert_eq!(
+            render(&MarkdownExporter, &schema),
+            r#"# Data Dictionary
+
+## Tables
+
+- [shop.customers](#table-shop-customers)
+- [shop.orders](#table-shop-orders) — Orders placed in the shop
+
+<a id="table-shop-customers"></a>
+
+## shop.customers
+
+### Columns
+
+| Name | Type | Length | Nullable | PK | FK target | Default | Description |
+|------|------|--------|----------|----|-----------|---------|-------------|
+| id | int |  | no | yes |  |  |  |
+| email | varchar | 120 | no |  |  |  |  |
+
+### Constraints
+
+- Primary key: `id`
+- Unique `email`: `email`
+
+### Indexes
+
+- `email` (unique): `email`
+
+### Referenced by
+
+- [shop.orders](#table-shop-orders).`customer_id` → `id`
+
+<a id="table-shop-orders"></a>
+
+## shop.orders
+
+Orders placed in the shop
+
+### Columns
+
+| Name | Type | Length | Nullable | PK | FK target | Default | Description |
+|------|------|--------|----------|----|-----------|---------|-------------|
+| id | bigint |  | no | yes |  |  |  |
+| customer_id | int |  | no |  | [shop.customers](#table-shop-customers).`id` |  |  |
+| status | enum |  | no |  |  | `open` |  |
+| code | varchar | 10 | yes |  |  | `0` |  |
+| total | decimal |  | no |  |  | `0.00` |  |
+| created | datetime |  | no |  |  | `CURRENT_TIMESTAMP` |  |
+
+### Constraints
+
+- Primary key: `id`
+- Foreign key: `customer_id` → [shop.customers](#table-shop-customers).`id`
+- Unique: `code`
+- Check `orders_chk_1`: `` (`total` >= 0) ``
+- Check: `code <> ''`
+
+### Indexes
+
+- `customer_id`: `customer_id`
+"#
+        );
+    }
+}
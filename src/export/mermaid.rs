@@ -0,0 +1,130 @@
+// export/mermaid.rs
+// Mermaid erDiagram exporter
+
+use crate::db::models::DbMetaDataSchema;
+use super::diagram::{Cardinality, DiagramIdentifiers, DiagramOptions, relationships, select_tables};
+use super::registry::Exporter;
+use anyhow::Result;
+use std::io::Write;
+
+pub struct MermaidExporter {
+    pub options: DiagramOptions,
+}
+
+impl Exporter for MermaidExporter {
+    fn name(&self) -> &'static str {
+        "mermaid"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["mmd"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Mermaid erDiagram of tables and foreign keys"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "mmd"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let metadata = &schema.db_metadata;
+        let tables = select_tables(metadata, &self.options);
+        let ids = DiagramIdentifiers::new(tables.iter().copied());
+
+        writeln!(out, "erDiagram")?;
+        for name in &tables {
+            let table = &metadata.tables[*name];
+            // Entities whose identifier differs from the table name are labeled with the name
+            let entity = if ids.get(name) == *name {
+                name.to_string()
+            } else {
+                format!("{}[\"{}\"]", ids.get(name), name.replace('"', "'"))
+            };
+            if self.options.hide_columns || table.columns.is_empty() {
+                writeln!(out, "    {} {{\n    }}", entity)?;
+                continue;
+            }
+            writeln!(out, "    {} {{", entity)?;
+            let column_ids = DiagramIdentifiers::new(table.columns.iter().map(|c| c.name.as_str()));
+            for col in &table.columns {
+                let mut keys = Vec::new();
+                if col.primary_key {
+                    keys.push("PK");
+                }
+                if table.foreign_keys.contains_key(&col.name) {
+                    keys.push("FK");
+                }
+                write!(out, "        {} {}", mermaid_type(&col.data_type), column_ids.get(&col.name))?;
+                if !keys.is_empty() {
+                    write!(out, " {}", keys.join(", "))?;
+                }
+                if col.nullable {
+                    write!(out, " \"nullable\"")?;
+                }
+                writeln!(out)?;
+            }
+            writeln!(out, "    }}")?;
+        }
+
+        for rel in relationships(metadata) {
+            if !tables.contains(&rel.child) || !tables.contains(&rel.parent) {
+                continue;
+            }
+            let parent_marker = match rel.parent_cardinality {
+                Cardinality::ExactlyOne => "||",
+                _ => "|o",
+            };
+            let child_marker = match rel.child_cardinality {
+                Cardinality::ZeroOrMany => "o{",
+                _ => "o|",
+            };
+            writeln!(
+                out,
+                "    {} {}--{} {} : \"{}\"",
+                ids.get(rel.parent),
+                parent_marker,
+                child_marker,
+                ids.get(rel.child),
+                rel.column.replace('"', "'")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Mermaid attribute types cannot contain spaces or commas, e.g. `character varying` or `numeric(10,2)`.
+fn mermaid_type(data_type: &str) -> String {
+    data_type
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || "_()[]-".contains(c) { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{awkward_names, render};
+
+    #[test]
+    fn entities_get_unique_identifiers_labeled_with_the_table_name() {
+        let exporter = MermaidExporter { options: DiagramOptions::default() };
+        assert_eq!(
+            render(&exporter, &awkward_names()),
+            r#"erDiagram
+    _2024_sales["2024_sales"] {
+        integer id PK
+    }
+    a_b["a-b"] {
+        integer id PK
+    }
+    a_b_2["a_b"] {
+        integer id PK
+        integer a_b_id FK "nullable"
+    }
+    a_b |o--o{ a_b_2 : "a-b id"
+"#
+        );
+    }
+}
@@ -1 +1,31 @@
+pub mod avro;
+pub mod codegen;
+pub mod dbml;
+pub mod dbt;
+pub mod diagram;
+pub mod dictionary;
+pub mod dot;
 pub mod exporter;
+#[cfg(test)]
+mod fixtures;
+pub mod flyway;
+pub mod html;
+pub mod jpa;
+pub mod jsonschema;
+pub mod liquibase;
+pub mod loader;
+pub mod markdown;
+pub mod mermaid;
+pub mod naming;
+pub mod plantuml;
+pub mod protobuf;
+pub mod pydantic;
+pub mod registry;
+pub mod rust;
+pub mod serialized;
+pub mod split;
+pub mod sqlalchemy;
+pub mod template;
+pub mod typescript;
+pub mod writer;
+pub mod xml;
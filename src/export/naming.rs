@@ -0,0 +1,149 @@
+// export/naming.rs
+// Identifier and file name conventions shared by the exporters
+
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+/// File name stems for the tables of a snapshot: the table key with characters outside
+/// `[A-Za-z0-9._-]` replaced by `_`. Keys whose stems would clash on a case-insensitive file
+/// system, like `a b` and `a_b` or `Users` and `users`, or with one of the reserved stems, get
+/// the first 8 hex digits of the key's SHA-256 appended. Whether a stem gets the suffix depends
+/// on the other tables: adding `a_b` to a snapshot renames the file of `a b`. The suffix itself
+/// depends only on the key.
+pub struct FileStems {
+    stems: HashMap<String, String>,
+}
+
+impl FileStems {
+    pub fn new<'a>(keys: impl IntoIterator<Item = &'a String>, reserved: &[&str]) -> Self {
+        let safe: Vec<(&String, String)> = keys.into_iter()
+            .map(|key| (key, key.chars().map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' }).collect()))
+            .collect();
+        let mut seen: HashSet<String> = reserved.iter().map(|r| r.to_lowercase()).collect();
+        let mut clashing: HashSet<String> = seen.clone();
+        for (_, stem) in &safe {
+            if !seen.insert(stem.to_lowercase()) {
+                clashing.insert(stem.to_lowercase());
+            }
+        }
+        let stems = safe.into_iter()
+            .map(|(key, stem)| {
+                let stem = if clashing.contains(&stem.to_lowercase()) {
+                    format!("{}-{}", stem, &format!("{:x}", Sha256::digest(key.as_bytes()))[..8])
+                } else {
+                    stem
+                };
+                (key.clone(), stem)
+            })
+            .collect();
+        Self { stems }
+    }
+
+    /// Stem of a table key passed to `new`.
+    pub fn stem(&self, key: &str) -> &str {
+        &self.stems[key]
+    }
+}
+
+/// Namespace of a table key: its schema (or MySQL database) lower-cased and made a valid
+/// identifier, `main` for SQLite tables which have no schema part.
+pub fn namespace(table_key: &str) -> String {
+    match table_key.rsplit_once('.') {
+        Some((schema, _)) => schema.split('.').map(|part| identifier(&part.to_lowercase())).collect::<Vec<_>>().join("."),
+        None => "main".to_string(),
+    }
+}
+
+/// `name` with every character outside `[A-Za-z0-9_]` replaced by `_`, prefixed with `_`
+/// when it would start with a digit.
+pub fn identifier(name: &str) -> String {
+    let safe: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' }).collect();
+    if safe.is_empty() || safe.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", safe)
+    } else {
+        safe
+    }
+}
+
+/// `order_items` and `order-items` become `OrderItems`; existing capitals are kept.
+pub fn pascal_case(name: &str) -> String {
+    let words: String = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().unwrap().to_ascii_uppercase();
+            std::iter::once(first).chain(chars).collect::<String>()
+        })
+        .collect();
+    identifier(&words)
+}
+
+/// `in progress` becomes `IN_PROGRESS`, `orderStatus` becomes `ORDER_STATUS`.
+pub fn upper_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !result.is_empty() && !result.ends_with('_') {
+                result.push('_');
+            }
+        } else {
+            if c.is_ascii_uppercase() && previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit()) {
+                result.push('_');
+            }
+            result.push(c.to_ascii_uppercase());
+        }
+        previous = Some(c);
+    }
+    identifier(result.trim_end_matches('_'))
+}
+
+/// `CustomerID` becomes `customer_id`, `first name` becomes `first_name`.
+pub fn snake_case(name: &str) -> String {
+    upper_snake_case(name).to_lowercase()
+}
+
+/// `user_name` and `userID` become `userName` and `userId`.
+pub fn camel_case(name: &str) -> String {
+    let pascal = pascal_case(&snake_case(name));
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => pascal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stems(keys: &[&str], reserved: &[&str]) -> Vec<String> {
+        let keys: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+        let stems = FileStems::new(&keys, reserved);
+        keys.iter().map(|k| stems.stem(k).to_string()).collect()
+    }
+
+    #[test]
+    fn safe_keys_are_kept() {
+        assert_eq!(stems(&["public.orders", "order-items"], &[]), vec!["public.orders", "order-items"]);
+        assert_eq!(stems(&["sales/q1 report"], &[]), vec!["sales_q1_report"]);
+    }
+
+    #[test]
+    fn clashing_keys_get_a_hash() {
+        let names = stems(&["a b", "a_b", "Users", "users", "orders"], &[]);
+        assert!(names[0].starts_with("a_b-") && names[1].starts_with("a_b-") && names[0] != names[1], "{:?}", names);
+        assert!(names[2].starts_with("Users-") && names[3].starts_with("users-"), "{:?}", names);
+        assert_eq!(names[4], "orders");
+        // The suffix depends only on the key
+        assert_eq!(stems(&["a b", "a_b", "zzz"], &[])[0], names[0]);
+    }
+
+    #[test]
+    fn reserved_stems_are_avoided() {
+        let names = stems(&["Manifest", "orders"], &["manifest"]);
+        assert!(names[0].starts_with("Manifest-"), "{:?}", names);
+        assert_eq!(names[1], "orders");
+    }
+}
@@ -0,0 +1,170 @@
+// export/plantuml.rs
+// PlantUML entity relationship diagram exporter
+
+use crate::db::models::DbMetaDataSchema;
+use super::diagram::{Cardinality, DiagramIdentifiers, DiagramOptions, relationships, select_tables};
+use super::registry::Exporter;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Renders tables as PlantUML entities (IE notation), with mandatory columns marked `*`,
+/// indexes listed below the columns, enum types as `enum` blocks and crow's foot relations.
+pub struct PlantUmlExporter {
+    pub options: DiagramOptions,
+}
+
+impl Exporter for PlantUmlExporter {
+    fn name(&self) -> &'static str {
+        "plantuml"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["puml"]
+    }
+
+    fn description(&self) -> &'static str {
+        "PlantUML entity relationship diagram"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "puml"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let metadata = &schema.db_metadata;
+        let tables = select_tables(metadata, &self.options);
+        let mut ids = DiagramIdentifiers::new(tables.iter().copied());
+
+        writeln!(out, "@startuml")?;
+        writeln!(out, "hide circle")?;
+        writeln!(out, "hide empty members")?;
+        writeln!(out, "skinparam linetype ortho")?;
+        writeln!(out)?;
+
+        let mut enums: BTreeMap<String, &Vec<String>> = BTreeMap::new();
+        for name in &tables {
+            let table = &metadata.tables[*name];
+            writeln!(out, "entity \"{}\" as {} {{", name, ids.get(name))?;
+            if !self.options.hide_columns {
+                let (keys, others): (Vec<_>, Vec<_>) = table.columns.iter().partition(|c| c.primary_key);
+                for col in &keys {
+                    writeln!(out, "  * {} : {} <<PK>>", col.name, col.data_type)?;
+                }
+                if !keys.is_empty() {
+                    writeln!(out, "  --")?;
+                }
+                for col in others {
+                    let mut stereotypes = Vec::new();
+                    if table.foreign_keys.contains_key(&col.name) {
+                        stereotypes.push("<<FK>>");
+                    }
+                    if table.is_unique_column(&col.name) {
+                        stereotypes.push("<<unique>>");
+                    }
+                    writeln!(
+                        out,
+                        "  {}{} : {}{}",
+                        if col.nullable { "" } else { "* " },
+                        col.name,
+                        col.data_type,
+                        stereotypes.iter().map(|s| format!(" {}", s)).collect::<String>()
+                    )?;
+                }
+                if !table.indexes.is_empty() {
+                    writeln!(out, "  ..")?;
+                    for index in &table.indexes {
+                        writeln!(
+                            out,
+                            "  {}{} ({})",
+                            if index.unique { "<<unique>> " } else { "<<index>> " },
+                            index.name,
+                            index.columns.join(", ")
+                        )?;
+                    }
+                }
+                for col in &table.columns {
+                    if let (Some(enum_name), Some(values)) = (col.enum_type_name(name), &col.enum_values) {
+                        enums.insert(enum_name, values);
+                    }
+                }
+            }
+            writeln!(out, "}}")?;
+            writeln!(out)?;
+        }
+
+        for (name, values) in &enums {
+            // Enum types share the identifiers of the tables
+            ids.add(name);
+            writeln!(out, "enum \"{}\" as {} {{", name, ids.get(name))?;
+            for value in *values {
+                writeln!(out, "  {}", value)?;
+            }
+            writeln!(out, "}}")?;
+            writeln!(out)?;
+        }
+
+        for rel in relationships(metadata) {
+            if !tables.contains(&rel.child) || !tables.contains(&rel.parent) {
+                continue;
+            }
+            let parent_marker = match rel.parent_cardinality {
+                Cardinality::ExactlyOne => "||",
+                _ => "|o",
+            };
+            let child_marker = match rel.child_cardinality {
+                Cardinality::ZeroOrMany => "o{",
+                _ => "o|",
+            };
+            writeln!(
+                out,
+                "{} {}--{} {} : {}",
+                ids.get(rel.parent),
+                parent_marker,
+                child_marker,
+                ids.get(rel.child),
+                rel.column
+            )?;
+        }
+        writeln!(out, "@enduml")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{awkward_names, render};
+
+    #[test]
+    fn entities_get_unique_aliases() {
+        let exporter = PlantUmlExporter { options: DiagramOptions::default() };
+        assert_eq!(
+            render(&exporter, &awkward_names()),
+            r#"@startuml
+hide circle
+hide empty members
+skinparam linetype ortho
+
+entity "2024_sales" as _2024_sales {
+  * id : integer <<PK>>
+  --
+}
+
+entity "a-b" as a_b {
+  * id : integer <<PK>>
+  --
+}
+
+entity "a_b" as a_b_2 {
+  * id : integer <<PK>>
+  --
+  a-b id : integer <<FK>>
+}
+
+a_b |o--o{ a_b_2 : a-b id
+@enduml
+"#
+        );
+    }
+}
@@ -0,0 +1,258 @@
+// export/protobuf.rs
+// Protocol Buffers (proto3) exporter with one message per table
+
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema};
+use crate::db::types::LogicalType;
+use crate::ddl::generator::bare_table_name;
+use super::naming::{identifier, namespace, pascal_case, upper_snake_case};
+use super::registry::Exporter;
+use super::writer::{create_output_dir, write_file_in};
+use anyhow::{Result, bail};
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Writes a directory with one `<package>.proto` per schema. Field numbers follow the column
+/// order, so they stay stable as long as columns are only appended.
+pub struct ProtobufExporter;
+
+impl Exporter for ProtobufExporter {
+    fn name(&self) -> &'static str {
+        "protobuf"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["proto"]
+    }
+
+    fn description(&self) -> &'static str {
+        "proto3 message of each table, one .proto file per schema (directory)"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "proto"
+    }
+
+    fn default_output_path(&self) -> String {
+        "output_protobuf".to_string()
+    }
+
+    /// A .proto file declares a single package, so only single-schema exports can be streamed.
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let packages = packages(schema);
+        if packages.len() > 1 {
+            bail!(
+                "Tables span several schemas ({}), write protobuf output to a directory instead",
+                packages.keys().cloned().collect::<Vec<_>>().join(", ")
+            );
+        }
+        for (package, tables) in &packages {
+            write_proto(out, schema, package, tables)?;
+        }
+        Ok(())
+    }
+
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        let root = create_output_dir(output_path)?;
+        for (package, tables) in &packages(schema) {
+            write_file_in(root, format!("{}.proto", package), |out| write_proto(out, schema, package, tables))?;
+        }
+        Ok(())
+    }
+}
+
+/// Table keys grouped by the package derived from their schema.
+fn packages(schema: &DbMetaDataSchema) -> BTreeMap<String, Vec<&String>> {
+    let mut packages: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for name in schema.db_metadata.sorted_table_names() {
+        packages.entry(namespace(name)).or_default().push(name);
+    }
+    packages
+}
+
+fn write_proto(out: &mut dyn Write, schema: &DbMetaDataSchema, package: &str, tables: &[&String]) -> Result<()> {
+    let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+    let mut messages = Vec::new();
+    // Enum types are declared at file level, so one named PostgreSQL type is shared by its columns
+    let mut enums: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut uses_timestamp = false;
+
+    for name in tables {
+        let table = &schema.db_metadata.tables[name.as_str()];
+        let mut lines = Vec::new();
+        if let Some(description) = &table.description {
+            lines.push(comment("", description));
+        }
+        lines.push(format!("message {} {{", pascal_case(bare_table_name(name))));
+        for (position, col) in table.columns.iter().enumerate() {
+            let (field_type, repeated) = match LogicalType::from_column(col, db_type) {
+                LogicalType::Enum(values) => {
+                    let enum_name = pascal_case(&col.enum_type_name(name).unwrap_or_else(|| col.name.clone()));
+                    enums.insert(enum_name.clone(), values);
+                    (enum_name, false)
+                }
+                LogicalType::Timestamp | LogicalType::TimestampTz => {
+                    uses_timestamp = true;
+                    ("google.protobuf.Timestamp".to_string(), false)
+                }
+                LogicalType::Array(_) => ("string".to_string(), true),
+                logical => (scalar_type(&logical).to_string(), false),
+            };
+            // Message fields already track presence; repeated fields cannot be optional
+            let label = if repeated {
+                "repeated "
+            } else if col.nullable && !field_type.contains('.') {
+                "optional "
+            } else {
+                ""
+            };
+            if let Some(description) = &col.description {
+                lines.push(comment("  ", description));
+            }
+            let note = type_note(col, db_type).map(|n| format!(" // {}", n)).unwrap_or_default();
+            lines.push(format!("  {}{} {} = {};{}", label, field_type, identifier(&col.name), position + 1, note));
+        }
+        lines.push("}".to_string());
+        messages.push(lines.join("\n"));
+    }
+
+    writeln!(out, "syntax = \"proto3\";")?;
+    writeln!(out)?;
+    writeln!(out, "package {};", package)?;
+    if uses_timestamp {
+        writeln!(out)?;
+        writeln!(out, "import \"google/protobuf/timestamp.proto\";")?;
+    }
+    for (name, values) in &enums {
+        // proto3 enums must start at zero, and value names share the enclosing scope
+        let prefix = upper_snake_case(name);
+        writeln!(out)?;
+        writeln!(out, "enum {} {{", name)?;
+        writeln!(out, "  {}_UNSPECIFIED = 0;", prefix)?;
+        for (number, value) in values.iter().enumerate() {
+            writeln!(out, "  {}_{} = {};", prefix, upper_snake_case(value), number + 1)?;
+        }
+        writeln!(out, "}}")?;
+    }
+    for message in messages {
+        writeln!(out)?;
+        writeln!(out, "{}", message)?;
+    }
+    Ok(())
+}
+
+fn scalar_type(logical: &LogicalType) -> &'static str {
+    match logical {
+        LogicalType::Boolean => "bool",
+        LogicalType::SmallInt | LogicalType::Integer => "int32",
+        LogicalType::BigInt => "int64",
+        LogicalType::Float => "float",
+        LogicalType::Double => "double",
+        LogicalType::Binary => "bytes",
+        _ => "string",
+    }
+}
+
+/// How values of types without a protobuf counterpart are encoded as strings.
+fn type_note(col: &ColumnMetadata, db_type: &str) -> Option<String> {
+    match LogicalType::from_column(col, db_type) {
+        LogicalType::Decimal { precision: Some(precision), scale } => {
+            Some(format!("decimal({}, {})", precision, scale.unwrap_or(0)))
+        }
+        LogicalType::Decimal { .. } => Some("decimal".to_string()),
+        LogicalType::Date => Some("ISO 8601 date".to_string()),
+        LogicalType::Time => Some("ISO 8601 time".to_string()),
+        LogicalType::Interval => Some("ISO 8601 duration".to_string()),
+        LogicalType::Uuid => Some("uuid".to_string()),
+        LogicalType::Json => Some("JSON".to_string()),
+        _ => None,
+    }
+}
+
+fn comment(indent: &str, text: &str) -> String {
+    text.lines().map(|line| format!("{}// {}", indent, line)).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+
+    #[test]
+    fn messages_of_a_postgres_snapshot() {
+        assert_eq!(
+            render(&ProtobufExporter, &postgres_blog()),
+            r#"syntax = "proto3";
+
+package public;
+
+import "google/protobuf/timestamp.proto";
+
+enum Mood {
+  MOOD_UNSPECIFIED = 0;
+  MOOD_HAPPY = 1;
+  MOOD_SAD = 2;
+}
+
+message Posts {
+  int64 id = 1;
+  optional int32 user_id = 2;
+  string title = 3;
+  google.protobuf.Timestamp posted_at = 4;
+}
+
+// Registered users
+message Users {
+  int32 id = 1;
+  // Login
+  string email = 2;
+  optional Mood mood = 3;
+  optional string profile = 4; // JSON
+  string balance = 5; // decimal(12, 2)
+}
+"#
+        );
+    }
+
+    #[test]
+    fn messages_of_a_mysql_snapshot() {
+        assert_eq!(
+            render(&ProtobufExporter, &mysql_shop()),
+            r#"syntax = "proto3";
+
+package shop;
+
+import "google/protobuf/timestamp.proto";
+
+enum OrdersStatus {
+  ORDERS_STATUS_UNSPECIFIED = 0;
+  ORDERS_STATUS_OPEN = 1;
+  ORDERS_STATUS_PAID = 2;
+}
+
+message Customers {
+  int32 id = 1;
+  string email = 2;
+}
+
+// Orders placed in the shop
+message Orders {
+  int64 id = 1;
+  int64 customer_id = 2;
+  OrdersStatus status = 3;
+  optional string code = 4;
+  string total = 5; // decimal(10, 2)
+  google.protobuf.Timestamp created = 6;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn several_schemas_cannot_be_streamed() {
+        let mut schema = postgres_blog();
+        let users = schema.db_metadata.tables["public.users"].clone();
+        schema.db_metadata.tables.insert("audit.users".to_string(), users);
+        let error = ProtobufExporter.write(&schema, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.to_string(), "Tables span several schemas (audit, public), write protobuf output to a directory instead");
+    }
+}
@@ -0,0 +1,163 @@
+// export/pydantic.rs
+// Pydantic code generator: one model per table
+
+use crate::db::models::DbMetaDataSchema;
+use super::codegen::{Imports, class_names, docstring, python_identifier, python_imports, python_string, type_mapping};
+use super::registry::Exporter;
+use anyhow::Result;
+use std::io::Write;
+
+/// Writes a Pydantic (v2) `BaseModel` per table. Fields are snake_case; columns named
+/// differently are kept as the field's alias.
+pub struct PydanticExporter;
+
+impl Exporter for PydanticExporter {
+    fn name(&self) -> &'static str {
+        "pydantic"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pydantic models of each table's rows"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn default_output_path(&self) -> String {
+        "models.py".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+        let class_names = class_names(&schema.db_metadata);
+        let mut imports = Imports::default();
+        imports.add("pydantic", "BaseModel");
+        let mut classes = Vec::new();
+
+        for name in schema.db_metadata.sorted_table_names() {
+            let table = &schema.db_metadata.tables[name];
+            let mut lines = vec![format!("class {}(BaseModel):", class_names[name.as_str()])];
+            if let Some(description) = &table.description {
+                lines.push(docstring("    ", description));
+            }
+            let mut aliased = false;
+            for col in &table.columns {
+                let python_type = type_mapping(col, db_type).python;
+                imports.add_type(&python_type);
+                let mut annotation = python_type.name;
+                let mut arguments = Vec::new();
+                if col.nullable {
+                    imports.add("typing", "Optional");
+                    annotation = format!("Optional[{}]", annotation);
+                    arguments.push("default=None".to_string());
+                }
+                let field = python_identifier(&col.name);
+                if field != col.name {
+                    aliased = true;
+                    arguments.push(format!("alias={}", python_string(&col.name)));
+                }
+                if let Some(description) = &col.description {
+                    arguments.push(format!("description={}", python_string(description)));
+                }
+                let value = match arguments.as_slice() {
+                    [] => String::new(),
+                    [default] if default == "default=None" => " = None".to_string(),
+                    _ => {
+                        imports.add("pydantic", "Field");
+                        format!(" = Field({})", arguments.join(", "))
+                    }
+                };
+                lines.push(format!("    {}: {}{}", field, annotation, value));
+            }
+            if aliased {
+                imports.add("pydantic", "ConfigDict");
+                lines.push(String::new());
+                lines.push("    model_config = ConfigDict(populate_by_name=True)".to_string());
+            }
+            if table.columns.is_empty() && table.description.is_none() {
+                lines.push("    pass".to_string());
+            }
+            classes.push(lines.join("\n"));
+        }
+
+        writeln!(out, "from __future__ import annotations")?;
+        writeln!(out)?;
+        for line in python_imports(&imports) {
+            writeln!(out, "{}", line)?;
+        }
+        for class in classes {
+            writeln!(out)?;
+            writeln!(out)?;
+            writeln!(out, "{}", class)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+
+    #[test]
+    fn postgres_snapshot() {
+        assert_eq!(
+            render(&PydanticExporter, &postgres_blog()),
+            r#"from __future__ import annotations
+
+from datetime import datetime
+from decimal import Decimal
+from typing import Any, Literal, Optional
+
+from pydantic import BaseModel, Field
+
+
+class Posts(BaseModel):
+    id: int
+    user_id: Optional[int] = None
+    title: str
+    posted_at: Optional[datetime] = None
+
+
+class Users(BaseModel):
+    """Registered users"""
+    id: int
+    email: str = Field(description="Login")
+    mood: Optional[Literal["happy", "sad"]] = None
+    profile: Optional[Any] = None
+    balance: Decimal
+"#
+        );
+    }
+
+    #[test]
+    fn mysql_snapshot() {
+        assert_eq!(
+            render(&PydanticExporter, &mysql_shop()),
+            r#"from __future__ import annotations
+
+from datetime import datetime
+from decimal import Decimal
+from typing import Literal, Optional
+
+from pydantic import BaseModel
+
+
+class Customers(BaseModel):
+    id: int
+    email: str
+
+
+class Orders(BaseModel):
+    """Orders placed in the shop"""
+    id: int
+    customer_id: int
+    status: Literal["open", "paid"]
+    code: Optional[str] = None
+    total: Decimal
+    created: datetime
+"#
+        );
+    }
+}
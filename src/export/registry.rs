@@ -0,0 +1,121 @@
+// export/registry.rs
+// Exporter trait and the registry of available output formats
+
+use crate::db::models::DbMetaDataSchema;
+use super::dbt::DbtOptions;
+use super::diagram::DiagramOptions;
+use anyhow::{Result, anyhow};
+use super::writer::write_file;
+use std::io::Write;
+
+/// An output format for `DbMetaDataSchema`. New formats implement this in their own module
+/// and are added to `ExporterRegistry::builtin`.
+pub trait Exporter {
+    /// Name used to select the format via `--format`.
+    fn name(&self) -> &'static str;
+    /// Alternative names accepted for `--format`.
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// One-line description shown by `--format help`.
+    fn description(&self) -> &'static str;
+    /// Extension used for the default output file name.
+    fn file_extension(&self) -> &'static str;
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()>;
+    /// Default output path when none is given.
+    fn default_output_path(&self) -> String {
+        format!("output.{}", self.file_extension())
+    }
+    /// Writes the export to `output_path`. Formats producing several files override this.
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        write_file(output_path, |out| self.write(schema, out))
+    }
+}
+
+/// Format-specific settings handed to the exporters when the registry is built.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub diagram: DiagramOptions,
+    pub dbt: DbtOptions,
+}
+
+pub struct ExporterRegistry {
+    exporters: Vec<Box<dyn Exporter + Send + Sync>>,
+}
+
+impl ExporterRegistry {
+    pub fn empty() -> Self {
+        Self { exporters: Vec::new() }
+    }
+
+    /// Registry with every output format shipped with the tool.
+    pub fn builtin(options: &ExportOptions) -> Self {
+        let mut registry = Self::empty();
+        registry.register(Box::new(super::serialized::JsonExporter));
+        registry.register(Box::new(super::serialized::YamlExporter));
+        registry.register(Box::new(super::markdown::MarkdownExporter));
+        registry.register(Box::new(super::html::HtmlExporter));
+        registry.register(Box::new(super::mermaid::MermaidExporter { options: options.diagram.clone() }));
+        registry.register(Box::new(super::dot::DotExporter { options: options.diagram.clone() }));
+        registry.register(Box::new(super::plantuml::PlantUmlExporter { options: options.diagram.clone() }));
+        registry.register(Box::new(super::dbml::DbmlExporter));
+        registry.register(Box::new(super::liquibase::LiquibaseExporter { format: super::liquibase::ChangelogFormat::Yaml }));
+        registry.register(Box::new(super::liquibase::LiquibaseExporter { format: super::liquibase::ChangelogFormat::Xml }));
+        registry.register(Box::new(super::flyway::FlywayExporter));
+        registry.register(Box::new(super::jsonschema::JsonSchemaExporter));
+        registry.register(Box::new(super::avro::AvroExporter));
+        registry.register(Box::new(super::protobuf::ProtobufExporter));
+        registry.register(Box::new(super::rust::RustExporter));
+        registry.register(Box::new(super::typescript::TypeScriptExporter));
+        registry.register(Box::new(super::pydantic::PydanticExporter));
+        registry.register(Box::new(super::sqlalchemy::SqlAlchemyExporter));
+        registry.register(Box::new(super::jpa::JpaExporter));
+        registry.register(Box::new(super::dictionary::CsvExporter));
+        registry.register(Box::new(super::dictionary::XlsxExporter));
+        registry.register(Box::new(super::dbt::DbtExporter { options: options.dbt.clone() }));
+        registry
+    }
+
+    pub fn register(&mut self, exporter: Box<dyn Exporter + Send + Sync>) {
+        self.exporters.push(exporter);
+    }
+
+    pub fn get(&self, format: &str) -> Option<&(dyn Exporter + Send + Sync)> {
+        let format = format.to_lowercase();
+        self.exporters
+            .iter()
+            .find(|e| e.name() == format || e.aliases().contains(&format.as_str()))
+            .map(|e| e.as_ref())
+    }
+
+    /// Like `get`, but fails with the list of supported formats.
+    pub fn require(&self, format: &str) -> Result<&(dyn Exporter + Send + Sync)> {
+        self.get(format).ok_or_else(|| {
+            anyhow!("Unsupported format: '{}'. Supported formats: {}", format, self.names().join(", "))
+        })
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.exporters.iter().map(|e| e.name()).collect()
+    }
+
+    /// Human-readable listing of all formats, printed for `--format help`.
+    pub fn help(&self) -> String {
+        let mut help = String::from("Available output formats:\n");
+        for exporter in &self.exporters {
+            help.push_str(&format!(
+                "  {:<12} .{:<6} {}\n",
+                exporter.name(),
+                exporter.file_extension(),
+                exporter.description()
+            ));
+        }
+        help
+    }
+}
+
+impl Default for ExporterRegistry {
+    fn default() -> Self {
+        Self::builtin(&ExportOptions::default())
+    }
+}
@@ -0,0 +1,224 @@
+// export/rust.rs
+// Rust code generator: serde and sqlx row structs for every table
+
+use crate::db::models::DbMetaDataSchema;
+use crate::ddl::dialect::Dialect;
+use crate::ddl::generator::bare_table_name;
+use super::codegen::{Imports, type_mapping};
+use super::naming::{namespace, pascal_case, snake_case};
+use super::registry::Exporter;
+use super::writer::{create_output_dir, write_file_in};
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+    "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move",
+    "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Writes a directory with one module per schema, `<schema>.rs`, and a `mod.rs` declaring them.
+/// Written to a stream, the modules are emitted inline as `pub mod <schema> { ... }`.
+pub struct RustExporter;
+
+impl Exporter for RustExporter {
+    fn name(&self) -> &'static str {
+        "rust"
+    }
+
+    fn description(&self) -> &'static str {
+        "Rust structs deriving Serialize, Deserialize and sqlx::FromRow, one module per schema (directory)"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "rs"
+    }
+
+    fn default_output_path(&self) -> String {
+        "output_rust".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        for (index, (module, tables)) in modules(schema).iter().enumerate() {
+            if index > 0 {
+                writeln!(out)?;
+            }
+            writeln!(out, "pub mod {} {{", module)?;
+            for line in module_source(schema, tables).lines() {
+                if line.is_empty() {
+                    writeln!(out)?;
+                } else {
+                    writeln!(out, "    {}", line)?;
+                }
+            }
+            writeln!(out, "}}")?;
+        }
+        Ok(())
+    }
+
+    fn write_to_path(&self, schema: &DbMetaDataSchema, output_path: &str) -> Result<()> {
+        let root = create_output_dir(output_path)?;
+        let modules = modules(schema);
+        for (module, tables) in &modules {
+            let source = format!("//! Row types of the `{}` schema.\n\n{}", module.trim_start_matches("r#"), module_source(schema, tables));
+            write_file_in(root, format!("{}.rs", module.trim_start_matches("r#")), |out| Ok(out.write_all(source.as_bytes())?))?;
+        }
+        write_file_in(root, "mod.rs", |out| {
+            for module in modules.keys() {
+                writeln!(out, "pub mod {};", module)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Table keys grouped by the module named after their schema.
+fn modules(schema: &DbMetaDataSchema) -> BTreeMap<String, Vec<&String>> {
+    let mut modules: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for name in schema.db_metadata.sorted_table_names() {
+        modules.entry(rust_identifier(&namespace(name).replace('.', "_"))).or_default().push(name);
+    }
+    modules
+}
+
+fn module_source(schema: &DbMetaDataSchema, tables: &[&String]) -> String {
+    let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+    let postgres = matches!(Dialect::parse(db_type), Ok(Dialect::Postgres));
+    let mut imports = Imports::default();
+    imports.add("serde", "Deserialize");
+    imports.add("serde", "Serialize");
+    let struct_names: BTreeSet<String> = tables.iter().map(|name| pascal_case(bare_table_name(name))).collect();
+    let mut enums: BTreeMap<String, String> = BTreeMap::new();
+    let mut structs = Vec::new();
+
+    for name in tables {
+        let table = &schema.db_metadata.tables[name.as_str()];
+        let mut lines = Vec::new();
+        if let Some(description) = &table.description {
+            lines.extend(doc_comment("", description));
+        }
+        lines.push("#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]".to_string());
+        lines.push(format!("pub struct {} {{", pascal_case(bare_table_name(name))));
+        for col in &table.columns {
+            let mut rust_type = type_mapping(col, db_type).rust;
+            if let (Some(type_name), Some(values)) = (col.enum_type_name(name), &col.enum_values) {
+                let mut enum_name = pascal_case(&type_name);
+                if struct_names.contains(&enum_name) {
+                    enum_name.push_str("Enum");
+                }
+                // Named PostgreSQL types are checked by name; MySQL enums are decoded from their labels
+                let sql_type_name = (postgres && !col.data_type.eq_ignore_ascii_case("enum")).then_some(type_name.as_str());
+                enums.entry(enum_name.clone()).or_insert_with(|| enum_source(&enum_name, sql_type_name, values));
+                rust_type.name = enum_name;
+            }
+            imports.add_type(&rust_type);
+            if let Some(description) = &col.description {
+                lines.extend(doc_comment("    ", description));
+            }
+            let field = rust_identifier(&snake_case(&col.name));
+            if field.trim_start_matches("r#") != col.name {
+                lines.push(format!("    #[sqlx(rename = \"{}\")]", rust_string(&col.name)));
+            }
+            // PgInterval does not implement serde's traits
+            if rust_type.name.contains("PgInterval") {
+                lines.push("    #[serde(skip)]".to_string());
+            }
+            let field_type = if col.nullable { format!("Option<{}>", rust_type.name) } else { rust_type.name };
+            lines.push(format!("    pub {}: {},", field, field_type));
+        }
+        lines.push("}".to_string());
+        structs.push(lines.join("\n"));
+    }
+
+    let imports: Vec<String> = imports
+        .modules()
+        .map(|(krate, items)| match items.as_slice() {
+            [item] => format!("use {}::{};", krate, item),
+            _ => format!("use {}::{{{}}};", krate, items.join(", ")),
+        })
+        .collect();
+    let items: Vec<String> = enums.into_values().chain(structs).collect();
+    format!("{}\n\n{}\n", imports.join("\n"), items.join("\n\n"))
+}
+
+/// A `sqlx::Type` enum with one variant per label. Variants are renamed to their label when
+/// the Rust name differs, for both sqlx and serde.
+fn enum_source(name: &str, sql_type_name: Option<&str>, labels: &[String]) -> String {
+    let mut lines = vec!["#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]".to_string()];
+    if let Some(sql_type_name) = sql_type_name {
+        lines.push(format!("#[sqlx(type_name = \"{}\")]", rust_string(sql_type_name)));
+    }
+    lines.push(format!("pub enum {} {{", name));
+    let mut variants = BTreeSet::new();
+    for (index, label) in labels.iter().enumerate() {
+        let mut variant = rust_identifier(&pascal_case(label));
+        // Labels like `a b` and `a_b` would share a variant name
+        if !variants.insert(variant.clone()) {
+            variant = format!("{}{}", variant, index);
+            variants.insert(variant.clone());
+        }
+        if variant != *label {
+            lines.push(format!("    #[sqlx(rename = \"{0}\")]\n    #[serde(rename = \"{0}\")]", rust_string(label)));
+        }
+        lines.push(format!("    {},", variant));
+    }
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+fn rust_string(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Keywords become raw identifiers; the few that cannot be raw get a trailing underscore.
+fn rust_identifier(name: &str) -> String {
+    match name {
+        "self" | "Self" | "super" | "crate" => format!("{}_", name),
+        _ if KEYWORDS.contains(&name) => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
+
+fn doc_comment(indent: &str, text: &str) -> Vec<String> {
+    text.lines().map(|line| format!("{}/// {}", indent, line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn enum_columns_get_sqlx_types() {
+        let column = |name: &str, data_type: &str, values: serde_json::Value| json!({
+            "name": name, "type": data_type, "nullable": false, "primary_key": false, "field_length": null,
+            "unique": null, "spec": null, "isChecked": true, "enum_values": values,
+        });
+        let schema = |db_type: &str, table: &str, data_type: &str| -> DbMetaDataSchema {
+            serde_json::from_value(json!({
+                "system_environment_id": 1,
+                "source_database_type": db_type,
+                "db_metadata": { "tables": { table: {
+                    "columns": [column("mood", data_type, json!(["happy", "so-so"]))],
+                    "primary_keys": [], "foreign_keys": {},
+                } } },
+            }))
+            .unwrap()
+        };
+
+        let postgres = schema("postgres", "public.people", "mood");
+        let source = module_source(&postgres, &postgres.db_metadata.sorted_table_names());
+        assert!(source.contains("#[sqlx(type_name = \"mood\")]\npub enum Mood {"), "{}", source);
+        assert!(source.contains("    #[sqlx(rename = \"so-so\")]\n    #[serde(rename = \"so-so\")]\n    SoSo,"), "{}", source);
+        assert!(source.contains("    pub mood: Mood,"), "{}", source);
+
+        // Inline MySQL enums are named after their column and matched by label only
+        let mysql = schema("mysql", "shop.people", "enum");
+        let source = module_source(&mysql, &mysql.db_metadata.sorted_table_names());
+        assert!(source.contains("sqlx::Type)]\npub enum PeopleMood {"), "{}", source);
+        assert!(!source.contains("type_name"), "{}", source);
+        assert!(source.contains("    pub mood: PeopleMood,"), "{}", source);
+    }
+}
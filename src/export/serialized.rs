@@ -0,0 +1,52 @@
+// export/serialized.rs
+// JSON and YAML exporters, writing the schema exactly as serde models it
+
+use crate::db::models::DbMetaDataSchema;
+use super::registry::Exporter;
+use super::writer::SerializationFormat;
+use anyhow::Result;
+use std::io::Write;
+
+pub struct JsonExporter;
+
+impl Exporter for JsonExporter {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Full metadata snapshot as pretty-printed JSON"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        SerializationFormat::Json.write(schema, out)
+    }
+}
+
+pub struct YamlExporter;
+
+impl Exporter for YamlExporter {
+    fn name(&self) -> &'static str {
+        "yaml"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["yml"]
+    }
+
+    fn description(&self) -> &'static str {
+        "Full metadata snapshot as YAML"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "yaml"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        SerializationFormat::Yaml.write(schema, out)
+    }
+}
@@ -0,0 +1,176 @@
+// export/split.rs
+// Split snapshot layout: a directory with a manifest plus one file per table
+
+use crate::db::models::DbMetaDataSchema;
+use super::loader::read_text;
+use super::naming::FileStems;
+use super::writer::{SerializationFormat, create_output_dir, write_file_in};
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const MANIFEST_STEM: &str = "manifest";
+const MANIFEST_EXTENSIONS: [&str; 3] = ["json", "yaml", "yml"];
+
+/// Writes `schema` to `dir` as `manifest.<ext>`, holding every snapshot field except the tables
+/// plus a `tables` map from table name to file, and one `<schema>.<table>.<ext>` per table.
+/// Keys are written sorted so that re-exports of an unchanged table produce identical files.
+/// Table files listed by a previous manifest that are no longer part of the schema are removed.
+pub fn write_split(schema: &DbMetaDataSchema, dir: &str, format: SerializationFormat) -> Result<()> {
+    let root = create_output_dir(dir)?;
+    let previous: BTreeSet<String> = match find_manifest(root) {
+        Some(path) => table_files(&read_value(&path)?)?.into_iter().map(|(_, file)| file).collect(),
+        None => BTreeSet::new(),
+    };
+
+    let mut manifest = match serde_json::to_value(schema)? {
+        Value::Object(map) => map,
+        _ => unreachable!("snapshots serialize to objects"),
+    };
+    manifest.remove("db_metadata");
+    let mut tables = Map::new();
+    let files = FileStems::new(schema.db_metadata.tables.keys(), &[MANIFEST_STEM]);
+    for name in schema.db_metadata.sorted_table_names() {
+        let file_name = format!("{}.{}", files.stem(name), format.extension());
+        let table = serde_json::to_value(&schema.db_metadata.tables[name])?;
+        write_file_in(root, &file_name, |out| format.write(&table, out))?;
+        tables.insert(name.clone(), Value::String(file_name));
+    }
+
+    for stale in previous.iter().filter(|f| !tables.values().any(|v| v == f.as_str())) {
+        // Only plain file names are ours to delete
+        if Path::new(stale).file_name().is_some_and(|n| n == stale.as_str()) {
+            let _ = fs::remove_file(root.join(stale));
+        }
+    }
+    for extension in MANIFEST_EXTENSIONS {
+        let _ = fs::remove_file(root.join(format!("{}.{}", MANIFEST_STEM, extension)));
+    }
+    manifest.insert("tables".to_string(), Value::Object(tables));
+    write_file_in(root, format!("{}.{}", MANIFEST_STEM, format.extension()), |out| format.write(&Value::Object(manifest), out))
+}
+
+/// True for a split snapshot directory or its manifest file.
+pub fn is_split(path: &str) -> bool {
+    let path = Path::new(path);
+    path.is_dir() || path.file_stem().is_some_and(|stem| stem == MANIFEST_STEM)
+}
+
+/// Reassembles the snapshot of a split directory (or its manifest) as it would appear in a
+/// single file, so it can be deserialized or validated against the JSON Schema.
+pub fn load_split(path: &str) -> Result<Value> {
+    let path = Path::new(path);
+    let (root, manifest_path) = if path.is_dir() {
+        let manifest = find_manifest(path).ok_or_else(|| anyhow!("{} contains no manifest.json or manifest.yaml", path.display()))?;
+        (path.to_path_buf(), manifest)
+    } else {
+        (path.parent().unwrap_or(Path::new(".")).to_path_buf(), path.to_path_buf())
+    };
+
+    let mut manifest = match read_value(&manifest_path)? {
+        Value::Object(map) => map,
+        _ => bail!("{} is not a snapshot manifest", manifest_path.display()),
+    };
+    let mut tables = Map::new();
+    for (name, file) in table_files(&Value::Object(manifest.clone()))? {
+        let table = read_value(&root.join(&file)).with_context(|| format!("Failed to load table {}", name))?;
+        tables.insert(name, table);
+    }
+    manifest.remove("tables");
+    manifest.insert("db_metadata".to_string(), serde_json::json!({ "tables": tables }));
+    Ok(Value::Object(manifest))
+}
+
+fn find_manifest(root: &Path) -> Option<std::path::PathBuf> {
+    MANIFEST_EXTENSIONS.iter()
+        .map(|extension| root.join(format!("{}.{}", MANIFEST_STEM, extension)))
+        .find(|path| path.is_file())
+}
+
+/// (table name, file name) pairs listed by a manifest.
+fn table_files(manifest: &Value) -> Result<Vec<(String, String)>> {
+    let tables = manifest.get("tables").and_then(Value::as_object).ok_or_else(|| anyhow!("Manifest has no tables map"))?;
+    tables.iter()
+        .map(|(name, file)| match file.as_str() {
+            Some(file) => Ok((name.clone(), file.to_string())),
+            None => Err(anyhow!("Manifest entry for table {} is not a file name", name)),
+        })
+        .collect()
+}
+
+/// Parses a JSON or YAML file, chosen by extension.
+fn read_value(path: &Path) -> Result<Value> {
+    let contents = read_text(&path.to_string_lossy())?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents).with_context(|| format!("{} is not valid YAML", path.display())),
+        _ => serde_json::from_str(&contents).with_context(|| format!("{} is not valid JSON", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::TableMetadata;
+    use serde_json::json;
+
+    fn schema(tables: &[&str]) -> DbMetaDataSchema {
+        let mut schema = DbMetaDataSchema { source_database_type: Some("sqlite".to_string()), ..Default::default() };
+        for name in tables {
+            let table: TableMetadata = serde_json::from_value(json!({
+                "columns": [{ "name": "id", "type": "INTEGER", "nullable": false, "primary_key": true, "field_length": null, "unique": null, "spec": null, "isChecked": true }],
+                "primary_keys": ["id"],
+                "foreign_keys": {},
+                "description": name,
+            }))
+            .unwrap();
+            schema.db_metadata.tables.insert(name.to_string(), table);
+        }
+        schema
+    }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-split-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn clashing_table_names_round_trip() {
+        let dir = temp_dir("round-trip");
+        let tables = ["a b", "a_b", "Users", "users", "manifest", "orders"];
+        let original = schema(&tables);
+        for format in [SerializationFormat::Json, SerializationFormat::Yaml] {
+            write_split(&original, &dir.to_string_lossy(), format).unwrap();
+            let files: BTreeSet<String> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().to_lowercase()).collect();
+            assert_eq!(files.len(), tables.len() + 1, "{:?}", files);
+
+            let loaded: DbMetaDataSchema = serde_json::from_value(load_split(&dir.to_string_lossy()).unwrap()).unwrap();
+            for name in tables {
+                assert_eq!(loaded.db_metadata.tables[name].description.as_deref(), Some(name));
+            }
+            assert_eq!(loaded.db_metadata.tables.len(), tables.len());
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dropped_tables_lose_their_files() {
+        let dir = temp_dir("stale");
+        write_split(&schema(&["orders", "customers"]), &dir.to_string_lossy(), SerializationFormat::Json).unwrap();
+        write_split(&schema(&["orders"]), &dir.to_string_lossy(), SerializationFormat::Json).unwrap();
+        let mut files: Vec<String> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name().to_string_lossy().into_owned()).collect();
+        files.sort();
+        assert_eq!(files, vec!["manifest.json", "orders.json"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn compressed_paths_are_rejected() {
+        let dir = temp_dir("compressed").with_extension("gz");
+        let error = write_split(&schema(&["users"]), &dir.to_string_lossy(), SerializationFormat::Json).unwrap_err();
+        assert!(error.to_string().contains("this format writes a directory"), "{}", error);
+        assert!(!dir.exists());
+    }
+}
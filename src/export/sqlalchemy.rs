@@ -0,0 +1,288 @@
+// export/sqlalchemy.rs
+// SQLAlchemy code generator: declarative models with relationships from foreign keys
+
+use crate::db::models::DbMetaDataSchema;
+use crate::ddl::generator::bare_table_name;
+use super::codegen::{Imports, class_names, docstring, identity_columns, python_identifier, python_imports, python_string, type_mapping};
+use super::diagram::{Cardinality, relationships};
+use super::registry::Exporter;
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+/// Writes SQLAlchemy 2.0 declarative models. Every foreign key becomes a pair of relationships:
+/// a scalar one on the referencing class and a collection (or scalar, for unique columns) on
+/// the referenced class, linked with `back_populates`.
+pub struct SqlAlchemyExporter;
+
+impl Exporter for SqlAlchemyExporter {
+    fn name(&self) -> &'static str {
+        "sqlalchemy"
+    }
+
+    fn description(&self) -> &'static str {
+        "SQLAlchemy declarative models with relationships from foreign keys"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "py"
+    }
+
+    fn default_output_path(&self) -> String {
+        "models.py".to_string()
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let metadata = &schema.db_metadata;
+        let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+        let class_names = class_names(metadata);
+        let mut imports = Imports::default();
+        imports.add("sqlalchemy.orm", "DeclarativeBase");
+        imports.add("sqlalchemy.orm", "Mapped");
+        imports.add("sqlalchemy.orm", "mapped_column");
+
+        // Attribute names taken per table, so relationship names never shadow a column
+        let mut used: BTreeMap<&str, BTreeSet<String>> = metadata.tables.iter()
+            .map(|(name, table)| (name.as_str(), table.columns.iter().map(|c| python_identifier(&c.name)).collect()))
+            .collect();
+        let mut relationship_lines: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        let relationships = relationships(metadata);
+        if !relationships.is_empty() {
+            imports.add("sqlalchemy.orm", "relationship");
+        }
+        for rel in &relationships {
+            let child_class = &class_names[rel.child];
+            let parent_class = &class_names[rel.parent];
+            let column_attr = python_identifier(rel.column);
+            let child_base = match column_attr.strip_suffix("_id") {
+                Some(stem) if !stem.is_empty() => stem.to_string(),
+                _ => python_identifier(bare_table_name(rel.parent)),
+            };
+            let child_attr = unique_name(used.get_mut(rel.child).unwrap(), child_base);
+            let siblings = relationships.iter().filter(|r| r.child == rel.child && r.parent == rel.parent).count();
+            let parent_base = if siblings == 1 && rel.child != rel.parent {
+                python_identifier(bare_table_name(rel.child))
+            } else {
+                format!("{}_by_{}", python_identifier(bare_table_name(rel.child)), child_attr)
+            };
+            let parent_attr = unique_name(used.get_mut(rel.parent).unwrap(), parent_base);
+
+            let mut child_args = vec![format!("foreign_keys=[{}]", column_attr)];
+            if rel.child == rel.parent {
+                child_args.push(format!("remote_side=[{}]", python_identifier(rel.parent_column)));
+            }
+            child_args.push(format!("back_populates={}", python_string(&parent_attr)));
+            let child_type = if rel.parent_cardinality == Cardinality::ExactlyOne {
+                parent_class.clone()
+            } else {
+                imports.add("typing", "Optional");
+                format!("Optional[{}]", parent_class)
+            };
+            relationship_lines.entry(rel.child).or_default().push(format!(
+                "    {}: Mapped[{}] = relationship({})",
+                child_attr, child_type, child_args.join(", ")
+            ));
+
+            let parent_type = if rel.child_cardinality == Cardinality::ZeroOrMany {
+                imports.add("typing", "List");
+                format!("List[{}]", child_class)
+            } else {
+                imports.add("typing", "Optional");
+                format!("Optional[{}]", child_class)
+            };
+            relationship_lines.entry(rel.parent).or_default().push(format!(
+                "    {}: Mapped[{}] = relationship(foreign_keys={}, back_populates={})",
+                parent_attr,
+                parent_type,
+                python_string(&format!("[{}.{}]", child_class, column_attr)),
+                python_string(&child_attr)
+            ));
+        }
+
+        let mut classes = Vec::new();
+        for name in metadata.sorted_table_names() {
+            let table = &metadata.tables[name];
+            let mut lines = vec![format!("class {}(Base):", class_names[name.as_str()])];
+            if let Some(description) = &table.description {
+                lines.push(docstring("    ", description));
+                lines.push(String::new());
+            }
+            lines.push(format!("    __tablename__ = {}", python_string(bare_table_name(name))));
+            if let Some((schema_name, _)) = name.rsplit_once('.') {
+                lines.push(format!("    __table_args__ = {{\"schema\": {}}}", python_string(schema_name)));
+            }
+            if table.primary_keys.is_empty() {
+                lines.push("    # The table has no primary key; the ORM identifies rows by all of their columns".to_string());
+            }
+            lines.push(String::new());
+
+            let identity = identity_columns(table);
+            for col in &table.columns {
+                let mapping = type_mapping(col, db_type);
+                imports.add_type(&mapping.python);
+                imports.add_type(&mapping.sqlalchemy);
+                let annotation = if col.nullable {
+                    imports.add("typing", "Optional");
+                    format!("Optional[{}]", mapping.python.name)
+                } else {
+                    mapping.python.name
+                };
+                let attr = python_identifier(&col.name);
+                let mut args = Vec::new();
+                if attr != col.name {
+                    args.push(python_string(&col.name));
+                }
+                args.push(mapping.sqlalchemy.name);
+                if let Some(target) = table.foreign_keys.get(&col.name) {
+                    imports.add("sqlalchemy", "ForeignKey");
+                    args.push(format!("ForeignKey({})", python_string(target)));
+                }
+                if identity.contains(&col.name.as_str()) {
+                    args.push("primary_key=True".to_string());
+                }
+                if col.auto_increment == Some(true) {
+                    args.push("autoincrement=True".to_string());
+                }
+                if col.unique == Some(true) {
+                    args.push("unique=True".to_string());
+                }
+                if let Some(description) = &col.description {
+                    args.push(format!("comment={}", python_string(description)));
+                }
+                lines.push(format!("    {}: Mapped[{}] = mapped_column({})", attr, annotation, args.join(", ")));
+            }
+            if let Some(rel_lines) = relationship_lines.remove(name.as_str()) {
+                lines.push(String::new());
+                lines.extend(rel_lines);
+            }
+            classes.push(lines.join("\n"));
+        }
+
+        writeln!(out, "from __future__ import annotations")?;
+        writeln!(out)?;
+        for line in python_imports(&imports) {
+            writeln!(out, "{}", line)?;
+        }
+        writeln!(out)?;
+        writeln!(out)?;
+        writeln!(out, "class Base(DeclarativeBase):")?;
+        writeln!(out, "    pass")?;
+        for class in classes {
+            writeln!(out)?;
+            writeln!(out)?;
+            writeln!(out, "{}", class)?;
+        }
+        Ok(())
+    }
+}
+
+/// `base`, or `base_2`, `base_3`, ... when taken; the result is marked as taken.
+fn unique_name(used: &mut BTreeSet<String>, base: String) -> String {
+    let mut name = base.clone();
+    let mut n = 2;
+    while used.contains(&name) {
+        name = format!("{}_{}", base, n);
+        n += 1;
+    }
+    used.insert(name.clone());
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+
+    #[test]
+    fn postgres_snapshot() {
+        assert_eq!(
+            render(&SqlAlchemyExporter, &postgres_blog()),
+            r#"from __future__ import annotations
+
+from datetime import datetime
+from decimal import Decimal
+from typing import Any, List, Literal, Optional
+
+from sqlalchemy import BigInteger, DateTime, Enum, ForeignKey, Integer, JSON, Numeric, String, Text
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column, relationship
+
+
+class Base(DeclarativeBase):
+    pass
+
+
+class Posts(Base):
+    __tablename__ = "posts"
+    __table_args__ = {"schema": "public"}
+
+    id: Mapped[int] = mapped_column(BigInteger, primary_key=True, autoincrement=True)
+    user_id: Mapped[Optional[int]] = mapped_column(Integer, ForeignKey("public.users.id"))
+    title: Mapped[str] = mapped_column(Text)
+    posted_at: Mapped[Optional[datetime]] = mapped_column(DateTime(timezone=True))
+
+    user: Mapped[Optional[Users]] = relationship(foreign_keys=[user_id], back_populates="posts")
+
+
+class Users(Base):
+    """Registered users"""
+
+    __tablename__ = "users"
+    __table_args__ = {"schema": "public"}
+
+    id: Mapped[int] = mapped_column(Integer, primary_key=True, autoincrement=True)
+    email: Mapped[str] = mapped_column(String(120), comment="Login")
+    mood: Mapped[Optional[Literal["happy", "sad"]]] = mapped_column(Enum("happy", "sad", name="mood"))
+    profile: Mapped[Optional[Any]] = mapped_column(JSON)
+    balance: Mapped[Decimal] = mapped_column(Numeric(12, 2))
+
+    posts: Mapped[List[Posts]] = relationship(foreign_keys="[Posts.user_id]", back_populates="user")
+"#
+        );
+    }
+
+    #[test]
+    fn mysql_snapshot() {
+        assert_eq!(
+            render(&SqlAlchemyExporter, &mysql_shop()),
+            r#"from __future__ import annotations
+
+from datetime import datetime
+from decimal import Decimal
+from typing import List, Literal, Optional
+
+from sqlalchemy import BigInteger, DateTime, Enum, ForeignKey, Integer, Numeric, String
+from sqlalchemy.orm import DeclarativeBase, Mapped, mapped_column, relationship
+
+
+class Base(DeclarativeBase):
+    pass
+
+
+class Customers(Base):
+    __tablename__ = "customers"
+    __table_args__ = {"schema": "shop"}
+
+    id: Mapped[int] = mapped_column(Integer, primary_key=True, autoincrement=True)
+    email: Mapped[str] = mapped_column(String(120))
+
+    orders: Mapped[List[Orders]] = relationship(foreign_keys="[Orders.customer_id]", back_populates="customer")
+
+
+class Orders(Base):
+    """Orders placed in the shop"""
+
+    __tablename__ = "orders"
+    __table_args__ = {"schema": "shop"}
+
+    id: Mapped[int] = mapped_column(BigInteger, primary_key=True, autoincrement=True)
+    customer_id: Mapped[int] = mapped_column(BigInteger, ForeignKey("shop.customers.id"))
+    status: Mapped[Literal["open", "paid"]] = mapped_column(Enum("open", "paid"))
+    code: Mapped[Optional[str]] = mapped_column(String(10))
+    total: Mapped[Decimal] = mapped_column(Numeric(10, 2))
+    created: Mapped[datetime] = mapped_column(DateTime)
+
+    customer: Mapped[Customers] = relationship(foreign_keys=[customer_id], back_populates="orders")
+"#
+        );
+    }
+}
@@ -0,0 +1,173 @@
+// export/template.rs
+// Renders a user-provided Tera template against the full metadata snapshot
+
+use crate::db::models::{ColumnMetadata, DbMetaDataSchema};
+use crate::ddl::generator::creation_plan;
+use super::codegen::type_mapping;
+use super::diagram::relationships;
+use super::naming::{camel_case, pascal_case, snake_case, upper_snake_case};
+use super::registry::Exporter;
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use tera::Tera;
+
+const TEMPLATE_NAME: &str = "template";
+
+/// Renders a Tera template with the snapshot as context (`db_metadata`, `source_database_type`,
+/// ...). Besides Tera's built-ins, templates can use these filters:
+///
+/// - `snake_case`, `camel_case`, `pascal_case`, `upper_snake_case` on strings
+/// - `type_map(lang=...)` on a column: its type in `rust`, `typescript`, `python`, `sqlalchemy` or `java`
+/// - `foreign_keys` and `referenced_by` on a table name: outgoing and incoming references, each
+///   with `child`, `column`, `parent` and `parent_column`
+/// - `topological` on the tables map or a list of table names: names with referenced tables first
+pub struct TemplateExporter {
+    path: String,
+    tera: Tera,
+}
+
+impl TemplateExporter {
+    /// Loads and parses the template, so syntax errors surface before the database is read.
+    pub fn load(path: &str) -> Result<Self> {
+        let mut tera = Tera::default();
+        tera.add_template_file(path, Some(TEMPLATE_NAME))
+            .with_context(|| format!("Failed to load template {}", path))?;
+        Ok(Self { path: path.to_string(), tera })
+    }
+}
+
+impl Exporter for TemplateExporter {
+    fn name(&self) -> &'static str {
+        "template"
+    }
+
+    fn description(&self) -> &'static str {
+        "Output of a user-provided Tera template"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "txt"
+    }
+
+    /// The template's file name without `.tera`, e.g. `report.md` for `templates/report.md.tera`.
+    fn default_output_path(&self) -> String {
+        let file_name = Path::new(&self.path).file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        match file_name.strip_suffix(".tera") {
+            Some(stem) if !stem.is_empty() => stem.to_string(),
+            _ => format!("output.{}", self.file_extension()),
+        }
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        // Filters that look at the snapshot are registered per render
+        let mut tera = self.tera.clone();
+        register_filters(&mut tera, schema);
+        let context = tera::Context::from_serialize(schema)?;
+        tera.render_to(TEMPLATE_NAME, &context, out)
+            .with_context(|| format!("Failed to render template {}", self.path))
+    }
+}
+
+fn register_filters(tera: &mut Tera, schema: &DbMetaDataSchema) {
+    tera.register_filter("snake_case", case_filter(snake_case));
+    tera.register_filter("camel_case", case_filter(camel_case));
+    tera.register_filter("pascal_case", case_filter(pascal_case));
+    tera.register_filter("upper_snake_case", case_filter(upper_snake_case));
+
+    let db_type = schema.source_database_type.clone().unwrap_or_default();
+    tera.register_filter("type_map", move |value: &Value, args: &HashMap<String, Value>| {
+        let col: ColumnMetadata = serde_json::from_value(value.clone())
+            .map_err(|e| tera::Error::msg(format!("type_map expects a column: {}", e)))?;
+        let mapping = type_mapping(&col, &db_type);
+        let mapped = match args.get("lang").and_then(Value::as_str).unwrap_or("rust") {
+            "rust" => mapping.rust,
+            "typescript" | "ts" => mapping.typescript,
+            "python" => mapping.python,
+            "sqlalchemy" => mapping.sqlalchemy,
+            "java" => mapping.java,
+            other => {
+                return Err(tera::Error::msg(format!(
+                    "type_map: unknown lang '{}', expected rust, typescript, python, sqlalchemy or java",
+                    other
+                )))
+            }
+        };
+        Ok(json!(mapped.name))
+    });
+
+    let references: Vec<Value> = relationships(&schema.db_metadata).iter()
+        .map(|rel| json!({ "child": rel.child, "column": rel.column, "parent": rel.parent, "parent_column": rel.parent_column }))
+        .collect();
+    let outgoing = references.clone();
+    tera.register_filter("foreign_keys", move |value: &Value, _: &HashMap<String, Value>| {
+        Ok(json!(outgoing.iter().filter(|r| r["child"] == *value).collect::<Vec<_>>()))
+    });
+    tera.register_filter("referenced_by", move |value: &Value, _: &HashMap<String, Value>| {
+        Ok(json!(references.iter().filter(|r| r["parent"] == *value).collect::<Vec<_>>()))
+    });
+
+    let order: Vec<String> = creation_plan(&schema.db_metadata).order.iter().map(|t| t.to_string()).collect();
+    tera.register_filter("topological", move |value: &Value, _: &HashMap<String, Value>| {
+        let selected: Vec<&str> = match value {
+            Value::Object(tables) => tables.keys().map(String::as_str).collect(),
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => return Err(tera::Error::msg("topological expects the tables map or a list of table names")),
+        };
+        Ok(json!(order.iter().filter(|t| selected.contains(&t.as_str())).collect::<Vec<_>>()))
+    });
+}
+
+fn case_filter(convert: fn(&str) -> String) -> impl tera::Filter {
+    move |value: &Value, _: &HashMap<String, Value>| match value.as_str() {
+        Some(text) => Ok(json!(convert(text))),
+        None => Err(tera::Error::msg("case filters expect a string")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{postgres_blog, render};
+    use std::fs;
+
+    fn load(name: &str, source: &str) -> TemplateExporter {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-template-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, source).unwrap();
+        let exporter = TemplateExporter::load(path.to_str().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        exporter
+    }
+
+    #[test]
+    fn filters_see_the_snapshot() {
+        let exporter = load("tables.md.tera", concat!(
+            "{% for name in db_metadata.tables | topological %}",
+            "{{ name | pascal_case }} ({{ name | upper_snake_case }}):",
+            "{% for col in db_metadata.tables[name].columns %} {{ col.name | camel_case }}: {{ col | type_map(lang=\"ts\") }}{% endfor %}\n",
+            "{% for fk in name | foreign_keys %}  {{ fk.column }} -> {{ fk.parent }}.{{ fk.parent_column }}\n{% endfor %}",
+            "{% for fk in name | referenced_by %}  <- {{ fk.child }}.{{ fk.column | snake_case }}\n{% endfor %}",
+            "{% endfor %}",
+        ));
+        assert_eq!(exporter.default_output_path(), "tables.md");
+        assert_eq!(
+            render(&exporter, &postgres_blog()),
+            r#"PublicUsers (PUBLIC_USERS): id: number email: string mood: 'happy' | 'sad' profile: unknown balance: string
+  <- public.posts.user_id
+PublicPosts (PUBLIC_POSTS): id: number userId: number title: string postedAt: string
+  user_id -> public.users.id
+"#
+        );
+    }
+
+    #[test]
+    fn unknown_languages_are_reported() {
+        let exporter = load("bad.tera", "{% for col in db_metadata.tables[\"public.users\"].columns %}{{ col | type_map(lang=\"cobol\") }}{% endfor %}");
+        let error = exporter.write(&postgres_blog(), &mut Vec::new()).unwrap_err();
+        assert!(format!("{:#}", error).contains("type_map: unknown lang 'cobol'"), "{:#}", error);
+    }
+}
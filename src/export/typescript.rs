@@ -0,0 +1,131 @@
+// export/typescript.rs
+// TypeScript code generator: one interface per table
+
+use crate::db::models::DbMetaDataSchema;
+use super::codegen::{class_names, type_mapping};
+use super::registry::Exporter;
+use anyhow::Result;
+use std::io::Write;
+
+/// Writes an exported interface per table describing a row as JSON, so dates, decimals and
+/// binary data are strings.
+pub struct TypeScriptExporter;
+
+impl Exporter for TypeScriptExporter {
+    fn name(&self) -> &'static str {
+        "typescript"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["ts"]
+    }
+
+    fn description(&self) -> &'static str {
+        "TypeScript interfaces of each table's rows"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ts"
+    }
+
+    fn write(&self, schema: &DbMetaDataSchema, out: &mut dyn Write) -> Result<()> {
+        let db_type = schema.source_database_type.as_deref().unwrap_or_default();
+        let class_names = class_names(&schema.db_metadata);
+        for (index, name) in schema.db_metadata.sorted_table_names().into_iter().enumerate() {
+            let table = &schema.db_metadata.tables[name];
+            if index > 0 {
+                writeln!(out)?;
+            }
+            if let Some(description) = &table.description {
+                writeln!(out, "{}", doc_comment("", description))?;
+            }
+            writeln!(out, "export interface {} {{", class_names[name.as_str()])?;
+            for col in &table.columns {
+                if let Some(description) = &col.description {
+                    writeln!(out, "{}", doc_comment("  ", description))?;
+                }
+                let ts_type = type_mapping(col, db_type).typescript.name;
+                let ts_type = if col.nullable { format!("{} | null", ts_type) } else { ts_type };
+                writeln!(out, "  {}: {};", property_name(&col.name), ts_type)?;
+            }
+            writeln!(out, "}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Column names that are not valid identifiers are written as string literals.
+fn property_name(name: &str) -> String {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if valid {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
+
+fn doc_comment(indent: &str, text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.len() == 1 {
+        return format!("{}/** {} */", indent, lines[0].replace("*/", "*\\/"));
+    }
+    let mut comment = format!("{}/**", indent);
+    for line in lines {
+        comment.push_str(&format!("\n{} * {}", indent, line.replace("*/", "*\\/")));
+    }
+    comment.push_str(&format!("\n{} */", indent));
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fixtures::{mysql_shop, postgres_blog, render};
+
+    #[test]
+    fn postgres_snapshot() {
+        assert_eq!(
+            render(&TypeScriptExporter, &postgres_blog()),
+            r#"export interface Posts {
+  id: number;
+  user_id: number | null;
+  title: string;
+  posted_at: string | null;
+}
+
+/** Registered users */
+export interface Users {
+  id: number;
+  /** Login */
+  email: string;
+  mood: 'happy' | 'sad' | null;
+  profile: unknown | null;
+  balance: string;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn mysql_snapshot() {
+        assert_eq!(
+            render(&TypeScriptExporter, &mysql_shop()),
+            r#"export interface Customers {
+  id: number;
+  email: string;
+}
+
+/** Orders placed in the shop */
+export interface Orders {
+  id: number;
+  customer_id: number;
+  status: 'open' | 'paid';
+  code: string | null;
+  total: string;
+  created: string;
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,225 @@
+// export/writer.rs
+// Shared serialization and file writing used by all exporters
+
+use anyhow::{Context, Result, anyhow, bail};
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Output path that writes to stdout instead of a file.
+pub const STDOUT: &str = "-";
+
+/// Serde-backed formats shared by the metadata and DATAMIMIC exporters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerializationFormat {
+    Json,
+    Yaml,
+}
+
+impl SerializationFormat {
+    pub fn parse(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            _ => Err(anyhow!("Unsupported format: '{}'. Supported formats: json, yaml", format)),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+        }
+    }
+
+    pub fn write<T: Serialize>(&self, value: &T, out: &mut dyn Write) -> Result<()> {
+        match self {
+            Self::Json => {
+                serde_json::to_writer_pretty(&mut *out, value)?;
+                out.write_all(b"\n")?;
+            }
+            Self::Yaml => serde_yaml::to_writer(&mut *out, value)?,
+        }
+        Ok(())
+    }
+}
+
+/// Compression applied to an output file, chosen by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    pub fn from_path(path: &str) -> Self {
+        let lower = path.to_lowercase();
+        if lower.ends_with(".gz") {
+            Self::Gzip
+        } else if lower.ends_with(".zst") || lower.ends_with(".zstd") {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+
+    /// `path` without the compression extension, e.g. `output.json` for `output.json.gz`.
+    pub fn strip(path: &str) -> &str {
+        match Self::from_path(path) {
+            Self::None => path,
+            _ => path.rsplit_once('.').map_or(path, |(stem, _)| stem),
+        }
+    }
+}
+
+/// Streams output produced by `write` to `output_file`, or to stdout for `-`. Files are written
+/// buffered to a temporary file next to the target, compressed according to the extension
+/// (`.gz`, `.zst`), and renamed into place only once complete, so a failed export never leaves a
+/// truncated file behind.
+pub fn write_file(output_file: &str, write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+    write_file_or(output_file, || std::io::stdout().lock(), write)
+}
+
+/// `write_file` with the stream that `-` writes to opened by `stdout`.
+fn write_file_or<W: Write>(output_file: &str, stdout: impl FnOnce() -> W, write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+    if output_file == STDOUT {
+        let mut out = BufWriter::new(stdout());
+        write(&mut out)?;
+        out.flush()?;
+        return Ok(());
+    }
+
+    // The temporary file lives next to the target so the final rename stays on one file system
+    let target = Path::new(output_file);
+    let file_name = target.file_name().ok_or_else(|| anyhow!("{} is not a file path", output_file))?;
+    let temp_path = target.with_file_name(format!(".{}.{}.tmp", file_name.to_string_lossy(), std::process::id()));
+    let result = write_compressed(&temp_path, Compression::from_path(output_file), write)
+        .and_then(|()| Ok(fs::rename(&temp_path, target)?));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result.with_context(|| format!("Failed to write {}", output_file))
+}
+
+fn write_compressed(path: &Path, compression: Compression, write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+    let file = File::create(path)?;
+    let mut out = BufWriter::new(&file);
+    match compression {
+        Compression::None => write(&mut out)?,
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(&mut out, flate2::Compression::default());
+            write(&mut encoder)?;
+            encoder.finish()?;
+        }
+        Compression::Zstd => {
+            let mut encoder = zstd::Encoder::new(&mut out, 0)?;
+            write(&mut encoder)?;
+            encoder.finish()?;
+        }
+    }
+    out.flush()?;
+    drop(out);
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Creates the directory of a format writing several files. Compression only applies to single
+/// files, so a `.gz` or `.zst` path is rejected instead of becoming a directory of that name.
+pub fn create_output_dir(output_path: &str) -> Result<&Path> {
+    if Compression::from_path(output_path) != Compression::None {
+        bail!("{} names a compressed file, but this format writes a directory; choose a path without .gz/.zst", output_path);
+    }
+    let root = Path::new(output_path);
+    fs::create_dir_all(root).with_context(|| format!("Failed to create {}", output_path))?;
+    Ok(root)
+}
+
+/// Streams a file of an output directory through `write_file`.
+pub fn write_file_in(dir: &Path, file_name: impl AsRef<Path>, write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+    write_file(&dir.join(file_name).to_string_lossy(), write)
+}
+
+/// Serializes `value` in the given format and streams it to `output_file`.
+pub fn write_serialized<T: Serialize>(value: &T, output_file: &str, format: SerializationFormat) -> Result<()> {
+    write_file(output_file, |out| format.write(value, out))
+}
+
+/// Writes already rendered output to `output_file`, replacing any existing file.
+pub fn write_string(contents: &str, output_file: &str) -> Result<()> {
+    write_file(output_file, |out| Ok(out.write_all(contents.as_bytes())?))
+}
+
+/// Writes rendered output to `output_file`, or to stdout when no file is given.
+pub fn write_output(contents: &str, output_file: Option<&str>) -> Result<()> {
+    write_string(contents, output_file.unwrap_or(STDOUT))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::path::PathBuf;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dbmetaexporter-writer-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir).unwrap().map(|e| e.unwrap().file_name().into_string().unwrap()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn compressed_files_round_trip() {
+        let dir = temp_dir("compressed");
+        let gzip = dir.join("schema.json.gz");
+        let zstd = dir.join("schema.json.zst");
+        write_string("{\"tables\": {}}\n", gzip.to_str().unwrap()).unwrap();
+        write_string("{\"tables\": {}}\n", zstd.to_str().unwrap()).unwrap();
+
+        let mut unpacked = String::new();
+        flate2::read::GzDecoder::new(File::open(&gzip).unwrap()).read_to_string(&mut unpacked).unwrap();
+        assert_eq!(unpacked, "{\"tables\": {}}\n");
+        let unpacked = zstd::decode_all(File::open(&zstd).unwrap()).unwrap();
+        assert_eq!(String::from_utf8(unpacked).unwrap(), "{\"tables\": {}}\n");
+        assert_eq!(file_names(&dir), ["schema.json.gz", "schema.json.zst"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_writes_keep_the_previous_file() {
+        let dir = temp_dir("atomic");
+        let target = dir.join("schema.json");
+        let output = target.to_str().unwrap();
+        write_string("old", output).unwrap();
+
+        let error = write_file(output, |out| {
+            out.write_all(b"partial")?;
+            bail!("extraction failed")
+        })
+        .unwrap_err();
+        assert_eq!(format!("{:#}", error), format!("Failed to write {}: extraction failed", output));
+        assert_eq!(fs::read_to_string(&target).unwrap(), "old");
+        assert_eq!(file_names(&dir), ["schema.json"]);
+
+        write_string("new", output).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        assert_eq!(file_names(&dir), ["schema.json"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dash_writes_to_stdout() {
+        let mut stdout = Vec::new();
+        write_file_or(STDOUT, || &mut stdout, |out| Ok(out.write_all(b"payload")?)).unwrap();
+        assert_eq!(stdout, b"payload");
+        assert!(!Path::new(STDOUT).exists());
+    }
+}
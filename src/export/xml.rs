@@ -0,0 +1,22 @@
+// export/xml.rs
+// Escaping shared by the exporters that write XML
+
+/// Escapes text for use in XML element content and double- or single-quoted attributes.
+pub fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup_and_quotes() {
+        assert_eq!(escape_xml(r#"a < b && c > 'd' "e""#), "a &lt; b &amp;&amp; c &gt; &apos;d&apos; &quot;e&quot;");
+    }
+}
@@ -0,0 +1,371 @@
+// history/store.rs
+// Local snapshot history kept in an embedded SQLite catalog
+
+use crate::db::models::{DatabaseMetadata, DbMetaDataSchema};
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::{Pool, Row, Sqlite, SqliteConnection, Transaction};
+use std::path::Path;
+
+/// Catalog of exported snapshots. Snapshot bodies are stored once per content hash; an export
+/// identical to the latest snapshot of its profile only bumps that snapshot's update time.
+pub struct HistoryStore {
+    pool: Pool<Sqlite>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SnapshotRecord {
+    pub id: i64,
+    pub profile: String,
+    pub hash: String,
+    pub source_database_type: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A snapshot saved in an open transaction. The id is already assigned, so it can be written
+/// into the export; the snapshot is only kept once `commit` is called after the export succeeded.
+pub struct PendingSnapshot {
+    transaction: Transaction<'static, Sqlite>,
+    pub record: SnapshotRecord,
+}
+
+impl PendingSnapshot {
+    /// Copies the catalog id and times into the snapshot about to be exported.
+    pub fn stamp(&self, schema: &mut DbMetaDataSchema) {
+        schema.id = Some(self.record.id);
+        schema.tc_creation = Some(self.record.created_at);
+        schema.tc_update = Some(self.record.updated_at);
+    }
+
+    pub async fn commit(self) -> Result<SnapshotRecord> {
+        self.transaction.commit().await?;
+        Ok(self.record)
+    }
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS snapshot_content (
+    hash TEXT PRIMARY KEY,
+    body TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS snapshot (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    profile TEXT NOT NULL,
+    hash TEXT NOT NULL REFERENCES snapshot_content (hash),
+    source_database_type TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS snapshot_profile_created ON snapshot (profile, created_at);
+";
+
+const SELECT_SNAPSHOT: &str = "SELECT id, profile, hash, source_database_type, created_at, updated_at FROM snapshot";
+
+impl HistoryStore {
+    /// Opens the catalog at `path`, creating the file and its directory if needed.
+    pub async fn open(path: &str) -> Result<Self> {
+        if let Some(parent) = Path::new(path).parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let options = SqliteConnectOptions::new().filename(path).create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .with_context(|| format!("Failed to open history catalog {}", path))?;
+        sqlx::raw_sql(SCHEMA).execute(&pool).await?;
+        Ok(Self { pool })
+    }
+
+    /// Saves the snapshot without committing it; dropping the result rolls the save back.
+    pub async fn save(&self, profile: &str, schema: &DbMetaDataSchema) -> Result<PendingSnapshot> {
+        let hash = content_hash(&schema.db_metadata)?;
+        let now = timestamp(&Utc::now());
+        let mut transaction = self.pool.begin().await?;
+
+        // Catalog fields are filled in when the snapshot is loaded again
+        let stored = DbMetaDataSchema { id: None, tc_creation: None, tc_update: None, ..schema.clone() };
+        sqlx::query("INSERT OR IGNORE INTO snapshot_content (hash, body) VALUES (?, ?)")
+            .bind(&hash)
+            .bind(serde_json::to_string(&stored)?)
+            .execute(&mut *transaction)
+            .await?;
+
+        let latest = sqlx::query(&format!("{} WHERE profile = ? ORDER BY id DESC LIMIT 1", SELECT_SNAPSHOT))
+            .bind(profile)
+            .fetch_optional(&mut *transaction)
+            .await?;
+        if let Some(latest) = latest.map(|row| record_from_row(&row)).transpose()? {
+            if latest.hash == hash {
+                sqlx::query("UPDATE snapshot SET updated_at = ? WHERE id = ?")
+                    .bind(&now)
+                    .bind(latest.id)
+                    .execute(&mut *transaction)
+                    .await?;
+                let record = fetch_record(&mut transaction, latest.id, None).await?;
+                return Ok(PendingSnapshot { transaction, record });
+            }
+        }
+
+        let id = sqlx::query("INSERT INTO snapshot (profile, hash, source_database_type, created_at, updated_at) VALUES (?, ?, ?, ?, ?)")
+            .bind(profile)
+            .bind(&hash)
+            .bind(&schema.source_database_type)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *transaction)
+            .await?
+            .last_insert_rowid();
+        let record = fetch_record(&mut transaction, id, None).await?;
+        Ok(PendingSnapshot { transaction, record })
+    }
+
+    /// Snapshots ordered oldest first, optionally restricted to one profile.
+    pub async fn list(&self, profile: Option<&str>) -> Result<Vec<SnapshotRecord>> {
+        let rows = match profile {
+            Some(profile) => {
+                sqlx::query(&format!("{} WHERE profile = ? ORDER BY created_at, id", SELECT_SNAPSHOT))
+                    .bind(profile)
+                    .fetch_all(&self.pool)
+                    .await?
+            }
+            None => sqlx::query(&format!("{} ORDER BY created_at, id", SELECT_SNAPSHOT)).fetch_all(&self.pool).await?,
+        };
+        rows.iter().map(record_from_row).collect()
+    }
+
+    /// Finds a snapshot by id, or the latest one taken at or before a date (`2024-05-01`,
+    /// meaning the end of that day in UTC) or an RFC 3339 timestamp. With a profile, only its
+    /// snapshots are considered.
+    pub async fn resolve(&self, reference: &str, profile: Option<&str>) -> Result<SnapshotRecord> {
+        if let Ok(id) = reference.parse::<i64>() {
+            return fetch_record(&mut *self.pool.acquire().await?, id, profile).await;
+        }
+        let until = if let Ok(time) = DateTime::parse_from_rfc3339(reference) {
+            time.with_timezone(&Utc)
+        } else if let Ok(date) = NaiveDate::parse_from_str(reference, "%Y-%m-%d") {
+            date.and_hms_micro_opt(23, 59, 59, 999_999).unwrap().and_utc()
+        } else {
+            bail!("'{}' is neither a snapshot id nor a date (YYYY-MM-DD or RFC 3339 timestamp)", reference);
+        };
+
+        let snapshots = self.list(profile).await?;
+        if profile.is_none() && snapshots.iter().any(|s| s.profile != snapshots[0].profile) {
+            bail!("History contains several profiles, pass --profile to look up '{}' by date", reference);
+        }
+        snapshots.into_iter()
+            .rev()
+            .find(|s| s.created_at <= until)
+            .ok_or_else(|| anyhow!("No snapshot taken at or before {}", reference))
+    }
+
+    /// The stored snapshot with `id`, `tc_creation` and `tc_update` taken from the catalog.
+    pub async fn load(&self, record: &SnapshotRecord) -> Result<DbMetaDataSchema> {
+        let body: String = sqlx::query("SELECT body FROM snapshot_content WHERE hash = ?")
+            .bind(&record.hash)
+            .fetch_one(&self.pool)
+            .await?
+            .get("body");
+        let mut schema: DbMetaDataSchema = serde_json::from_str(&body)
+            .with_context(|| format!("Snapshot {} in history is corrupt", record.id))?;
+        schema.id = Some(record.id);
+        schema.tc_creation = Some(record.created_at);
+        schema.tc_update = Some(record.updated_at);
+        Ok(schema)
+    }
+}
+
+/// SHA-256 of the metadata as JSON. serde_json sorts object keys, so the hash does not depend
+/// on the order tables were read in.
+pub fn content_hash(metadata: &DatabaseMetadata) -> Result<String> {
+    let canonical = serde_json::to_value(metadata)?.to_string();
+    Ok(format!("{:x}", Sha256::digest(canonical.as_bytes())))
+}
+
+/// Catalog location: `$DBMETAEXPORTER_HISTORY`, else `~/.dbmetaexporter/history.db`.
+pub fn default_history_path() -> String {
+    if let Ok(path) = std::env::var("DBMETAEXPORTER_HISTORY") {
+        return path;
+    }
+    match std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")) {
+        Ok(home) => Path::new(&home).join(".dbmetaexporter").join("history.db").to_string_lossy().into_owned(),
+        Err(_) => "dbmetaexporter_history.db".to_string(),
+    }
+}
+
+/// Connection parameters that are dropped from profiles.
+const CREDENTIAL_KEYS: [&str; 6] = ["user", "username", "password", "pwd", "passwd", "sslpassword"];
+
+/// Default profile for a connection: the connection string without credentials. URLs lose their
+/// user info and credential query parameters; libpq `key=value` strings lose credential keys.
+pub fn profile_from_connection(connection_string: &str) -> String {
+    let is_credential = |key: &str| CREDENTIAL_KEYS.iter().any(|k| k.eq_ignore_ascii_case(key.trim()));
+    if let Some((scheme, rest)) = connection_string.split_once("://") {
+        let (location, query) = match rest.split_once('?') {
+            Some((location, query)) => (location, Some(query)),
+            None => (rest, None),
+        };
+        let location = location.rsplit_once('@').map_or(location, |(_, host)| host);
+        let params: Vec<&str> = query.into_iter()
+            .flat_map(|q| q.split('&'))
+            .filter(|param| !param.is_empty() && !is_credential(param.split_once('=').map_or(*param, |(key, _)| key)))
+            .collect();
+        return if params.is_empty() {
+            format!("{}://{}", scheme, location)
+        } else {
+            format!("{}://{}?{}", scheme, location, params.join("&"))
+        };
+    }
+    if connection_string.contains('=') {
+        return libpq_pairs(connection_string).into_iter()
+            .filter(|(key, _)| !is_credential(key))
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+    connection_string.to_string()
+}
+
+/// Splits a libpq `key=value` string into pairs, keeping single-quoted values as written.
+fn libpq_pairs(connection_string: &str) -> Vec<(String, String)> {
+    let mut pairs = Vec::new();
+    let mut chars = connection_string.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=' && !c.is_whitespace())).collect();
+        if key.is_empty() {
+            break;
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.next_if_eq(&'=').is_none() {
+            pairs.push((key, String::new()));
+            continue;
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut value = String::new();
+        if chars.next_if_eq(&'\'').is_some() {
+            value.push('\'');
+            while let Some(c) = chars.next() {
+                value.push(c);
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '\'' => break,
+                    _ => {}
+                }
+            }
+        } else {
+            value.extend(std::iter::from_fn(|| chars.next_if(|c| !c.is_whitespace())));
+        }
+        pairs.push((key, value));
+    }
+    pairs
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    // Fixed width so that timestamps sort as text
+    time.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+async fn fetch_record(connection: &mut SqliteConnection, id: i64, profile: Option<&str>) -> Result<SnapshotRecord> {
+    let row = sqlx::query(&format!("{} WHERE id = ? AND (?2 IS NULL OR profile = ?2)", SELECT_SNAPSHOT))
+        .bind(id)
+        .bind(profile)
+        .fetch_optional(connection)
+        .await?
+        .ok_or_else(|| match profile {
+            Some(profile) => anyhow!("No snapshot with id {} in history of profile {}", id, profile),
+            None => anyhow!("No snapshot with id {} in history", id),
+        })?;
+    record_from_row(&row)
+}
+
+fn record_from_row(row: &sqlx::sqlite::SqliteRow) -> Result<SnapshotRecord> {
+    let parse = |column: &str| -> Result<DateTime<Utc>> {
+        Ok(DateTime::parse_from_rfc3339(&row.get::<String, _>(column))?.with_timezone(&Utc))
+    };
+    Ok(SnapshotRecord {
+        id: row.get("id"),
+        profile: row.get("profile"),
+        hash: row.get("hash"),
+        source_database_type: row.get("source_database_type"),
+        created_at: parse("created_at")?,
+        updated_at: parse("updated_at")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_user_info_is_removed() {
+        assert_eq!(profile_from_connection("postgres://app:s3cr@t@db.local:5432/shop"), "postgres://db.local:5432/shop");
+        assert_eq!(profile_from_connection("mysql://root@localhost/shop"), "mysql://localhost/shop");
+        assert_eq!(profile_from_connection("postgres://db.local/shop"), "postgres://db.local/shop");
+    }
+
+    #[test]
+    fn url_credential_parameters_are_removed() {
+        assert_eq!(
+            profile_from_connection("postgres://db.local/shop?user=app&password=secret&sslmode=require"),
+            "postgres://db.local/shop?sslmode=require"
+        );
+        assert_eq!(profile_from_connection("mysql://db.local/shop?PWD=secret"), "mysql://db.local/shop");
+        assert_eq!(profile_from_connection("sqlite:///tmp/shop.db?mode=ro"), "sqlite:///tmp/shop.db?mode=ro");
+    }
+
+    #[test]
+    fn libpq_credentials_are_removed() {
+        assert_eq!(
+            profile_from_connection("host=db.local port=5432 user=app password=secret dbname=shop"),
+            "host=db.local port=5432 dbname=shop"
+        );
+        assert_eq!(
+            profile_from_connection("host = db.local password='a \\'quoted\\' secret' dbname='my shop'"),
+            "host=db.local dbname='my shop'"
+        );
+    }
+
+    #[test]
+    fn plain_paths_are_kept() {
+        assert_eq!(profile_from_connection("/tmp/shop.db"), "/tmp/shop.db");
+    }
+
+    fn snapshot(table: &str) -> DbMetaDataSchema {
+        serde_json::from_value(serde_json::json!({
+            "system_environment_id": 0,
+            "source_database_type": "sqlite",
+            "db_metadata": { "tables": { table: { "columns": [], "primary_keys": [], "foreign_keys": {} } } },
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn snapshots_are_kept_only_when_committed() {
+        let path = std::env::temp_dir().join(format!("dbmetaexporter-history-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let store = HistoryStore::open(path.to_str().unwrap()).await.unwrap();
+
+        let failed = store.save("shop", &snapshot("orders")).await.unwrap();
+        drop(failed);
+        assert!(store.list(None).await.unwrap().is_empty());
+
+        let pending = store.save("shop", &snapshot("orders")).await.unwrap();
+        let mut exported = snapshot("orders");
+        pending.stamp(&mut exported);
+        let record = pending.commit().await.unwrap();
+        assert_eq!(exported.id, Some(record.id));
+        assert_eq!(store.list(Some("shop")).await.unwrap().len(), 1);
+
+        let other = store.save("crm", &snapshot("contacts")).await.unwrap().commit().await.unwrap();
+        let id = other.id.to_string();
+        assert_eq!(store.resolve(&id, None).await.unwrap().profile, "crm");
+        assert_eq!(store.resolve(&id, Some("crm")).await.unwrap().id, other.id);
+        let error = store.resolve(&id, Some("shop")).await.unwrap_err();
+        assert_eq!(error.to_string(), format!("No snapshot with id {} in history of profile shop", other.id));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
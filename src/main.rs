@@ -1,93 +1,212 @@
 // src/main.rs
 mod app;
+mod commands;
 mod db;
+mod ddl;
+mod diff;
 mod export;
 mod datamimic;
+mod history;
 mod models;
 
-use clap::Parser;
-use anyhow::{Result, anyhow};
+use clap::{Parser, Subcommand};
+use anyhow::{Context, Result, anyhow};
 use chrono::Utc;
 use db::accessors::*;
+use db::ddl_accessor::DdlAccessor;
 use db::models::*;
-use export::exporter::MetadataExporter;
-use datamimic::datamimic::DataMimicModelGenerator;
+use export::dbt::DbtOptions;
+use export::diagram::DiagramOptions;
+use export::exporter::{MetadataExporter, export_to};
+use export::registry::{ExportOptions, Exporter, ExporterRegistry};
+use export::template::TemplateExporter;
+use export::writer::{Compression, STDOUT, SerializationFormat};
+use datamimic::datamimic::{DataMimicFormat, DataMimicModelGenerator};
+use history::store::{HistoryStore, default_history_path, profile_from_connection};
 use app::tui::run_tui;
 
+/// Progress messages go to stderr while the export itself is written to stdout.
+macro_rules! status {
+    ($to_stdout:expr, $($arg:tt)*) => {
+        if $to_stdout { eprintln!($($arg)*) } else { println!($($arg)*) }
+    };
+}
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
     #[arg(long)]
     db_type: Option<String>,
     #[arg(long)]
     connection_string: Option<String>,
+    /// Read the metadata from these SQL DDL scripts instead of connecting; --db-type selects the dialect
+    #[arg(long, value_delimiter = ',', conflicts_with = "connection_string")]
+    ddl_file: Vec<String>,
     #[arg(long)]
     schema_or_database: Option<String>,
+    /// Output file (or directory); `-` writes to stdout, a `.gz` or `.zst` extension compresses the file
     #[arg(long)]
     output_file: Option<String>,
+    /// Output format of the metadata file; use `--format help` to list all formats
     #[arg(long, default_value = "json")]
     format: String,
+    /// json/yaml: write a directory with a manifest plus one file per table instead of a single file
+    #[arg(long, default_value_t = false)]
+    split: bool,
+    /// Render this Tera template against the metadata instead of writing --format
+    #[arg(long)]
+    template: Option<String>,
+    /// Path of the DATAMIMIC model file (defaults to <output_file stem>_datamimic.<ext>, not written
+    /// when the metadata goes to stdout)
+    #[arg(long)]
+    datamimic_output: Option<String>,
+    /// Format of the DATAMIMIC model: json, yaml or xml (defaults to --format)
+    #[arg(long)]
+    datamimic_format: Option<String>,
+    /// Diagram formats: only draw these tables (comma separated, `schema.table` or `table`)
+    #[arg(long, value_delimiter = ',')]
+    diagram_tables: Vec<String>,
+    /// Diagram formats: also draw neighbors up to this many foreign key hops away from --diagram-tables
+    #[arg(long, default_value_t = 0)]
+    diagram_hops: usize,
+    /// Diagram formats: draw tables without their columns
+    #[arg(long, default_value_t = false)]
+    diagram_hide_columns: bool,
+    /// dbt format: also write a staging model stub per table
+    #[arg(long, default_value_t = false)]
+    dbt_staging: bool,
+    /// History profile the export is saved under (defaults to the connection string without credentials)
+    #[arg(long)]
+    profile: Option<String>,
+    /// History catalog (defaults to $DBMETAEXPORTER_HISTORY or ~/.dbmetaexporter/history.db)
+    #[arg(long)]
+    history_db: Option<String>,
+    /// Do not save the export in the local snapshot history
+    #[arg(long, default_value_t = false)]
+    no_history: bool,
     #[arg(long, default_value_t = false)]
     tui: bool,
 }
 
+/// Commands working on exported snapshots and baselines. Without a command, metadata is exported from a database.
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Generate CREATE TABLE / CREATE INDEX / foreign key DDL from a metadata snapshot
+    Ddl(commands::ddl::DdlArgs),
+    /// Compare two metadata snapshots and report added, removed and changed objects
+    Diff(commands::diff::DiffArgs),
+    /// Generate forward and backward migration scripts between two metadata snapshots
+    Migrate(commands::migrate::MigrateArgs),
+    /// Compare a live database with a baseline snapshot and fail when it has drifted
+    Check(commands::check::CheckArgs),
+    /// List, show and compare snapshots saved by previous exports
+    History(commands::history::HistoryArgs),
+    /// Check a metadata snapshot or DATAMIMIC model against the published JSON Schema
+    Validate(commands::validate::ValidateArgs),
+    /// Print the JSON Schema of the metadata snapshot or DATAMIMIC model format
+    Schema(commands::validate::SchemaArgs),
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
+    if let Some(command) = &args.command {
+        return match command {
+            Command::Ddl(ddl_args) => commands::ddl::run(ddl_args),
+            Command::Diff(diff_args) => commands::diff::run(diff_args),
+            Command::Migrate(migrate_args) => commands::migrate::run(migrate_args),
+            Command::Check(check_args) => commands::check::run(check_args).await,
+            Command::History(history_args) => commands::history::run(history_args).await,
+            Command::Validate(validate_args) => commands::validate::run_validate(validate_args),
+            Command::Schema(schema_args) => commands::validate::run_schema(schema_args),
+        };
+    }
     if args.tui {
         run_tui().await?;
         return Ok(());
     }
 
-    let db_type = args.db_type.as_deref().ok_or_else(|| anyhow!("Missing --db-type"))?;
-    let connection_string = args.connection_string.as_deref().ok_or_else(|| anyhow!("Missing --connection-string"))?;
+    let export_options = ExportOptions {
+        diagram: DiagramOptions {
+            tables: args.diagram_tables.clone(),
+            hops: args.diagram_hops,
+            hide_columns: args.diagram_hide_columns,
+        },
+        dbt: DbtOptions { staging: args.dbt_staging },
+    };
+    let exporter = MetadataExporter::new(ExporterRegistry::builtin(&export_options));
+    if args.format == "help" {
+        print!("{}", exporter.registry().help());
+        return Ok(());
+    }
 
-    // Determine the correct file extension based on format
-    let ext = match args.format.as_str() {
-        "yaml" | "yml" => "yaml",
-        _ => "json",
+    let db_type = args.db_type.as_deref().ok_or_else(|| anyhow!("Missing --db-type"))?;
+    // DDL scripts stand in for the connection, also as the default history profile
+    let ddl_files = args.ddl_file.join(",");
+    let connection_string = match &args.connection_string {
+        Some(connection_string) => connection_string.as_str(),
+        None if !args.ddl_file.is_empty() => ddl_files.as_str(),
+        None => return Err(anyhow!("Missing --connection-string (or --ddl-file)")),
     };
-    // Set output_file to user value or default to output.<ext>
-    let output_file = args.output_file.clone().unwrap_or_else(|| format!("output.{}", ext));
 
-    // Automatically generate datamimic_output based on output_file and format
-    let datamimic_output = if let Some(dot_idx) = output_file.rfind('.') {
-        format!("{}{}_datamimic.{}", &output_file[..dot_idx], &output_file[dot_idx..dot_idx], ext)
-    } else {
-        format!("{}_datamimic.{}", output_file, ext)
+    // Set output_file to user value or default to the format's output path (output.<ext>)
+    let template_exporter = args.template.as_deref().map(TemplateExporter::load).transpose()?;
+    let format_exporter: &dyn Exporter = match &template_exporter {
+        Some(template) => template,
+        None => exporter.registry().require(&args.format)?,
     };
+    if args.split {
+        if args.template.is_some() {
+            return Err(anyhow!("--split cannot be combined with --template"));
+        }
+        SerializationFormat::parse(&args.format).context("--split writes one JSON or YAML file per table")?;
+    }
+    let output_file = args.output_file.clone().unwrap_or_else(|| {
+        if args.split { "output".to_string() } else { format_exporter.default_output_path() }
+    });
+
+    // The DATAMIMIC model follows the metadata format unless requested otherwise
+    let datamimic_format = args.datamimic_format.clone().unwrap_or_else(|| {
+        SerializationFormat::parse(&args.format).map_or("json", |f| f.extension()).to_string()
+    });
+    let datamimic_ext = DataMimicFormat::parse(&datamimic_format)?.extension();
+    let to_stdout = output_file == STDOUT;
+    // Automatically generate datamimic_output based on output_file unless given explicitly,
+    // compressed like the metadata file
+    let datamimic_output = args.datamimic_output.clone().or_else(|| {
+        let uncompressed = Compression::strip(&output_file);
+        let compression_ext = &output_file[uncompressed.len()..];
+        let stem = uncompressed.rfind('.').map_or(uncompressed, |dot_idx| &uncompressed[..dot_idx]);
+        (!to_stdout).then(|| format!("{}_datamimic.{}{}", stem, datamimic_ext, compression_ext))
+    });
     let creation_source = "metaextractor".to_string();
 
-    println!("--- Database Metadata Export and DATAMIMIC Generator ---");
-    println!("Database Type: {}", db_type);
-    println!("Connection: [REDACTED]");
+    status!(to_stdout, "--- Database Metadata Export and DATAMIMIC Generator ---");
+    status!(to_stdout, "Database Type: {}", db_type);
+    status!(to_stdout, "Connection: [REDACTED]");
     if let Some(schema_or_db) = &args.schema_or_database {
-        println!("Schema/DB Filter: {}", schema_or_db);
+        status!(to_stdout, "Schema/DB Filter: {}", schema_or_db);
     }
-    println!("Metadata Output: {} ({})", output_file, args.format);
-    println!("DATAMIMIC Output: {}", datamimic_output);
-    println!("-------------------------------------------------------");
-
-    let mut db_accessor: Box<dyn DatabaseAccessor> = match db_type.to_lowercase().as_str() {
-        "postgres" | "postgresql" => {
-            println!("Initializing PostgreSQL accessor...");
-            Box::new(PostgresAccessor::new(connection_string).await?)
-        }
-        "mysql" | "mariadb" => {
-            println!("Initializing MySQL accessor...");
-            Box::new(MySqlAccessor::new(connection_string).await?)
-        }
-        "sqlite" => {
-            println!("Initializing SQLite accessor...");
-            Box::new(SqliteAccessor::new(connection_string).await?)
-        }
-        _ => {
-            return Err(anyhow!("Unsupported database type: '{}'. Supported types: postgres, mysql, sqlite", db_type));
-        }
+    status!(to_stdout, "Metadata Output: {} ({})", output_file, args.template.as_deref().unwrap_or(&args.format));
+    match &datamimic_output {
+        Some(path) => status!(to_stdout, "DATAMIMIC Output: {} ({})", path, datamimic_format),
+        None => status!(to_stdout, "DATAMIMIC Output: skipped while writing to stdout (set --datamimic-output)"),
+    }
+    status!(to_stdout, "-------------------------------------------------------");
+
+    let mut db_accessor: Box<dyn DatabaseAccessor + Send> = if !args.ddl_file.is_empty() {
+        status!(to_stdout, "Reading DDL scripts as {}...", db_type);
+        Box::new(DdlAccessor::from_files(db_type, &args.ddl_file)?)
+    } else {
+        status!(to_stdout, "Connecting to {}...", db_type);
+        connect(db_type, connection_string).await?
     };
 
     let extracted_metadata = db_accessor.extract_full_metadata(args.schema_or_database.as_deref()).await?;
-    let final_schema = DbMetaDataSchema {
+    let mut final_schema = DbMetaDataSchema {
+        format_version: Some(FORMAT_VERSION),
         id: None,
         system_environment_id: 0, // Not relevant anymore
         tc_creation_src: Some(creation_source.clone()),
@@ -96,17 +215,41 @@ async fn main() -> Result<()> {
         tc_update: Some(Utc::now()),
         db_metadata: extracted_metadata,
         user_config_db_metadata: None,
+        source_database_type: Some(db_type.to_lowercase()),
     };
 
-    let exporter = MetadataExporter;
-    exporter.export_schema_to_file(&final_schema, &output_file, &args.format)?;
+    // The history catalog assigns the snapshot id; unchanged schemas keep their id and creation time.
+    // The snapshot is only kept once every output has been written.
+    let history = if !args.no_history {
+        let history_path = args.history_db.clone().unwrap_or_else(default_history_path);
+        let profile = args.profile.clone().unwrap_or_else(|| profile_from_connection(connection_string));
+        let store = HistoryStore::open(&history_path).await?;
+        let pending = store.save(&profile, &final_schema).await?;
+        pending.stamp(&mut final_schema);
+        Some((pending, profile, history_path))
+    } else {
+        None
+    };
 
-    let generator = DataMimicModelGenerator;
-    let datamimic_model = generator.generate_from_metadata(&final_schema.db_metadata, db_type)?;
+    if args.split {
+        exporter.export_schema_to_dir(&final_schema, &output_file, &args.format)?;
+    } else {
+        export_to(format_exporter, &final_schema, &output_file)?;
+    }
 
-    generator.export_model_to_file(&datamimic_model, &datamimic_output)?;
+    if let Some(datamimic_output) = &datamimic_output {
+        let generator = DataMimicModelGenerator;
+        let datamimic_model = generator.generate_from_metadata(&final_schema.db_metadata, db_type)?;
+
+        generator.export_model_to_file(&datamimic_model, datamimic_output, &datamimic_format)?;
+    }
+
+    if let Some((pending, profile, history_path)) = history {
+        let record = pending.commit().await?;
+        status!(to_stdout, "Saved as snapshot {} of profile {} in {}", record.id, profile, history_path);
+    }
 
-    println!("-------------------------------------------------------");
-    println!("Process completed successfully!");
+    status!(to_stdout, "-------------------------------------------------------");
+    status!(to_stdout, "Process completed successfully!");
     Ok(())
 }